@@ -0,0 +1,152 @@
+//! Timings of batch inflection against inflecting names one by one, on names drawn from
+//! a Zipf distribution and on names that are mostly distinct.
+//!
+//! Run with `cargo bench --bench batch`.
+
+use std::collections::HashSet;
+use std::time::Instant;
+
+use petrovich::{inflect_each, inflect_many, lastname, Case, Gender, NamePart};
+
+const NAMES: usize = 1_000_000;
+
+// Distinct surnames of both genders, made of a stem, an infix of one or more letters and
+// an ending
+fn distinct(count: usize) -> Vec<(Gender, String)> {
+    let stems = [
+        "Иван",
+        "Петр",
+        "Сидор",
+        "Смирн",
+        "Кузнец",
+        "Попов",
+        "Васильев",
+        "Соколов",
+        "Михайл",
+        "Новик",
+        "Федор",
+        "Морозов",
+        "Волков",
+        "Алексеев",
+        "Лебедев",
+        "Семен",
+        "Егор",
+        "Павл",
+        "Козл",
+        "Степан",
+    ];
+    let endings = [
+        ("ов", "ова"),
+        ("ин", "ина"),
+        ("ский", "ская"),
+        ("енко", "енко"),
+        ("ых", "ых"),
+    ];
+    let letters: Vec<char> = "абвгдежзиклмнопрстуф".chars().collect();
+    let mut names = Vec::with_capacity(count);
+    for i in 0.. {
+        let mut infix = String::new();
+        let mut rest = i;
+        loop {
+            infix.push(letters[rest % letters.len()]);
+            rest /= letters.len();
+            if rest == 0 {
+                break;
+            }
+        }
+        for stem in &stems {
+            for &(male, female) in &endings {
+                for &(gender, ending) in &[(Gender::Male, male), (Gender::Female, female)] {
+                    if names.len() == count {
+                        return names;
+                    }
+                    names.push((gender, format!("{}{}{}", stem, infix, ending)));
+                }
+            }
+        }
+    }
+    unreachable!()
+}
+
+// xorshift64, so every run times the same input
+fn random(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+// Names drawn from a Zipf distribution over 100k surnames, where the n-th most common one
+// is n times rarer than the first, as surnames are in population registers
+fn zipf() -> Vec<(Gender, String)> {
+    let distinct = distinct(100_000);
+    let mut total = 0.0;
+    let weights: Vec<f64> = (1..=distinct.len())
+        .map(|rank| {
+            total += 1.0 / rank as f64;
+            total
+        })
+        .collect();
+    let mut state = 0x2545_f491_4f6c_dd1d;
+    (0..NAMES)
+        .map(|_| {
+            let x = (random(&mut state) >> 11) as f64 / (1u64 << 53) as f64 * total;
+            let rank = weights.partition_point(|&weight| weight < x);
+            distinct[rank.min(distinct.len() - 1)].clone()
+        })
+        .collect()
+}
+
+// Names where most appear once: 800k surnames, a quarter of them repeated once
+fn few_repeats() -> Vec<(Gender, String)> {
+    let mut names = distinct(NAMES * 4 / 5);
+    let repeated: Vec<_> = names.iter().step_by(4).cloned().collect();
+    names.extend(repeated);
+    let mut state = 0x9e37_79b9_7f4a_7c15;
+    for i in (1..names.len()).rev() {
+        names.swap(i, random(&mut state) as usize % (i + 1));
+    }
+    names
+}
+
+fn time(label: &str, run: impl FnOnce() -> usize) {
+    let start = Instant::now();
+    let len = run();
+    let elapsed = start.elapsed();
+    println!("{:<20} {:>8.2?} ({} bytes)", label, elapsed, len);
+}
+
+fn run(label: &str, names: &[(Gender, String)]) {
+    let distinct: HashSet<_> = names.iter().collect();
+    println!(
+        "{}: {} names, {} distinct",
+        label,
+        names.len(),
+        distinct.len()
+    );
+    let items = || names.iter().map(|(gender, name)| (*gender, name.as_str()));
+
+    time("lastname in a loop", || {
+        items()
+            .map(|(gender, name)| lastname(gender, name, Case::Dative).len())
+            .sum()
+    });
+    time("inflect_many", || {
+        inflect_many(NamePart::Last, Case::Dative, items())
+            .iter()
+            .map(String::len)
+            .sum()
+    });
+    time("inflect_each", || {
+        let mut len = 0;
+        inflect_each(NamePart::Last, Case::Dative, items(), |name| {
+            len += name.len()
+        });
+        len
+    });
+}
+
+fn main() {
+    run("Zipf", &zipf());
+    run("Few repeats", &few_repeats());
+}
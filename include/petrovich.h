@@ -0,0 +1,141 @@
+/* Generated by cbindgen from src/ffi.rs, do not edit */
+
+#ifndef PETROVICH_H
+#define PETROVICH_H
+
+#include <stddef.h>
+
+// Call succeeded
+#define PETROVICH_OK 0
+
+// Required pointer argument is null
+#define PETROVICH_ERROR_NULL_POINTER -1
+
+// Name is not valid UTF-8
+#define PETROVICH_ERROR_INVALID_UTF8 -2
+
+// Gender code is out of range
+#define PETROVICH_ERROR_INVALID_GENDER -3
+
+// Case code is out of range
+#define PETROVICH_ERROR_INVALID_CASE -4
+
+// Output buffer can't hold the result and its terminating NUL
+#define PETROVICH_ERROR_BUFFER_TOO_SMALL -5
+
+// Name contains a NUL character, so the result can't be returned as a C string
+#define PETROVICH_ERROR_INTERIOR_NUL -6
+
+// Inflection panicked; this is a bug in the crate
+#define PETROVICH_ERROR_INTERNAL -7
+
+// `Gender::Male`
+#define PETROVICH_GENDER_MALE 0
+
+// `Gender::Female`
+#define PETROVICH_GENDER_FEMALE 1
+
+// `Gender::Androgynous`
+#define PETROVICH_GENDER_ANDROGYNOUS 2
+
+// `Gender::Unknown`
+#define PETROVICH_GENDER_UNKNOWN 3
+
+// `Case::Nominative`
+#define PETROVICH_CASE_NOMINATIVE 0
+
+// `Case::Genitive`
+#define PETROVICH_CASE_GENITIVE 1
+
+// `Case::Dative`
+#define PETROVICH_CASE_DATIVE 2
+
+// `Case::Accusative`
+#define PETROVICH_CASE_ACCUSATIVE 3
+
+// `Case::Instrumental`
+#define PETROVICH_CASE_INSTRUMENTAL 4
+
+// `Case::Prepositional`
+#define PETROVICH_CASE_PREPOSITIONAL 5
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Inflects first name into the buffer `out` of `out_len` bytes
+//
+// # Safety
+//
+// `name` must be a NUL-terminated string and `out` must be valid for writes of `out_len`
+// bytes.
+int petrovich_firstname(int gender, const char *name, int case_, char *out, size_t out_len);
+
+// Inflects first name into a new string stored in `*out`, see `petrovich_firstname`
+//
+// The string must be released with `petrovich_free`. `*out` is left untouched on error.
+//
+// # Safety
+//
+// `name` must be a NUL-terminated string and `out` must be valid for writes.
+int petrovich_firstname_alloc(int gender, const char *name, int case_, char **out);
+
+// Inflects last name into the buffer `out` of `out_len` bytes
+//
+// # Safety
+//
+// `name` must be a NUL-terminated string and `out` must be valid for writes of `out_len`
+// bytes.
+int petrovich_lastname(int gender, const char *name, int case_, char *out, size_t out_len);
+
+// Inflects last name into a new string stored in `*out`, see `petrovich_lastname`
+//
+// The string must be released with `petrovich_free`. `*out` is left untouched on error.
+//
+// # Safety
+//
+// `name` must be a NUL-terminated string and `out` must be valid for writes.
+int petrovich_lastname_alloc(int gender, const char *name, int case_, char **out);
+
+// Inflects middle name into the buffer `out` of `out_len` bytes
+//
+// # Safety
+//
+// `name` must be a NUL-terminated string and `out` must be valid for writes of `out_len`
+// bytes.
+int petrovich_middlename(int gender, const char *name, int case_, char *out, size_t out_len);
+
+// Inflects middle name into a new string stored in `*out`, see `petrovich_middlename`
+//
+// The string must be released with `petrovich_free`. `*out` is left untouched on error.
+//
+// # Safety
+//
+// `name` must be a NUL-terminated string and `out` must be valid for writes.
+int petrovich_middlename_alloc(int gender, const char *name, int case_, char **out);
+
+// Detects gender from the parts given and stores its code in `*out`
+//
+// Missing parts are passed as null.
+//
+// # Safety
+//
+// Every part must be null or a NUL-terminated string, and `out` must be valid for
+// writes.
+int petrovich_detect_gender(const char *lastname,
+                            const char *firstname,
+                            const char *middlename,
+                            int *out);
+
+// Releases a string returned by one of the `*_alloc` functions; null is ignored
+//
+// # Safety
+//
+// `s` must be null or come from a `*_alloc` function and not be released already.
+void petrovich_free(char *s);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* PETROVICH_H */
@@ -0,0 +1,13 @@
+//! Rewrites the outdated copies of the generated code in `src/generated` of `petrovich`.
+//!
+//! Run with `cargo run -p petrovich-build --bin regenerate` after changing the data.
+
+use std::path::Path;
+
+fn main() -> std::io::Result<()> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    for path in petrovich_build::regenerate(&root)? {
+        println!("{} is regenerated", path.display());
+    }
+    Ok(())
+}
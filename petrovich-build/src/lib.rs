@@ -0,0 +1,414 @@
+//! Code generation from the rule, gender, diminutive and yofication data, run by the build
+//! script of `petrovich` with the `generate-rules` feature and by the `regenerate` binary
+//! refreshing the copies in `src/generated`.
+//!
+//! Paths are relative to the root of the `petrovich` package, where the build script runs.
+
+use serde::Deserialize;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+pub mod rules;
+use rules::{
+    generate_rule_sets, generate_rules, read_rules, read_rules_with_cases, UKRAINIAN_CASES,
+};
+
+#[derive(Deserialize)]
+struct GenderMapping {
+    #[serde(default = "Vec::new")]
+    androgynous: Vec<String>,
+    #[serde(default = "Vec::new")]
+    male: Vec<String>,
+    #[serde(default = "Vec::new")]
+    female: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct GenderHeuristic {
+    exceptions: Option<GenderMapping>,
+    suffixes: GenderMapping,
+}
+
+#[derive(Deserialize)]
+struct GenderHeuristics {
+    lastname: GenderHeuristic,
+    firstname: GenderHeuristic,
+    middlename: GenderHeuristic,
+}
+
+#[derive(Deserialize)]
+struct GenderHeuristicsList {
+    gender: GenderHeuristics,
+}
+
+fn generate_gender_rules(rules: &[String], output: &mut impl Write) -> std::io::Result<()> {
+    for rule in rules {
+        writeln!(output, "                {:?},", rule)?;
+    }
+    Ok(())
+}
+
+fn generate_gender_mapping(
+    mapping: &GenderMapping,
+    output: &mut impl Write,
+) -> std::io::Result<()> {
+    writeln!(output, "            androgynous: &[")?;
+    generate_gender_rules(&mapping.androgynous, output)?;
+    writeln!(output, "            ],")?;
+    writeln!(output, "            male: &[")?;
+    generate_gender_rules(&mapping.male, output)?;
+    writeln!(output, "            ],")?;
+    writeln!(output, "            female: &[")?;
+    generate_gender_rules(&mapping.female, output)?;
+    writeln!(output, "            ],")
+}
+
+fn generate_gender_heuristic(
+    heuristic: &GenderHeuristic,
+    output: &mut impl Write,
+) -> std::io::Result<()> {
+    writeln!(output, "GenderHeuristic {{")?;
+    if let Some(mapping) = &heuristic.exceptions {
+        writeln!(output, "        exceptions: Some(GenderMapping {{")?;
+        generate_gender_mapping(mapping, output)?;
+        writeln!(output, "        }}),")?;
+    } else {
+        writeln!(output, "        exceptions: None,")?;
+    }
+    writeln!(output, "        suffixes: GenderMapping {{")?;
+    generate_gender_mapping(&heuristic.suffixes, output)?;
+    writeln!(output, "        }},")?;
+    writeln!(output, "    }},")
+}
+
+fn generate_gender(gender: &GenderHeuristics, output: &mut impl Write) -> std::io::Result<()> {
+    writeln!(output, "GenderHeuristics {{")?;
+    write!(output, "    lastname: ")?;
+    generate_gender_heuristic(&gender.lastname, output)?;
+    write!(output, "    firstname: ")?;
+    generate_gender_heuristic(&gender.firstname, output)?;
+    write!(output, "    middlename: ")?;
+    generate_gender_heuristic(&gender.middlename, output)?;
+    writeln!(output, "}}")
+}
+
+#[derive(Deserialize)]
+struct Diminutive {
+    name: String,
+    gender: String,
+    forms: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct DiminutiveList {
+    diminutives: Vec<Diminutive>,
+}
+
+// Writes `(form, full name, gender)` entries for the diminutives and the full names
+// themselves, keyed by the lowercase form with ё folded to е and sorted by it
+fn generate_diminutives(list: &DiminutiveList, output: &mut impl Write) -> std::io::Result<()> {
+    let mut entries = Vec::new();
+    for diminutive in &list.diminutives {
+        let gender = match diminutive.gender.as_str() {
+            "male" => "Male",
+            "female" => "Female",
+            gender => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{}: unknown gender {:?}", diminutive.name, gender),
+                ))
+            }
+        };
+        for form in std::iter::once(&diminutive.name).chain(&diminutive.forms) {
+            let key = form.to_lowercase().replace('ё', "е");
+            entries.push((key, diminutive.name.as_str(), gender));
+        }
+    }
+    entries.sort();
+    entries.dedup();
+    writeln!(output, "&[")?;
+    for (key, name, gender) in entries {
+        writeln!(output, "    ({:?}, {:?}, Gender::{}),", key, name, gender)?;
+    }
+    writeln!(output, "]")
+}
+
+#[derive(Deserialize)]
+struct YoficationList {
+    firstname: Vec<String>,
+    middlename: Vec<String>,
+    lastname: Vec<String>,
+    ambiguous: Vec<String>,
+}
+
+// Writes `(key, spelling, part, ambiguous)` entries for the names spelled with ё, keyed by
+// the lowercase name with ё folded to е and sorted by it; female forms of the last names
+// on -в and -н are added
+fn generate_yofication(list: &YoficationList, output: &mut impl Write) -> std::io::Result<()> {
+    let parts = [
+        (&list.firstname, "First", false),
+        (&list.middlename, "Middle", false),
+        (&list.lastname, "Last", false),
+        (&list.ambiguous, "Last", true),
+    ];
+    let mut entries = Vec::new();
+    for (names, part, ambiguous) in parts.iter() {
+        for name in names.iter() {
+            let spelling = name.to_lowercase();
+            if !spelling.contains('ё') {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{}: no ё in the spelling", name),
+                ));
+            }
+            let mut spellings = vec![spelling.clone()];
+            if *part == "Last" && (spelling.ends_with('в') || spelling.ends_with('н')) {
+                spellings.push(spelling + "а");
+            }
+            for spelling in spellings {
+                let key = spelling.replace('ё', "е");
+                entries.push((key, spelling, *part, *ambiguous));
+            }
+        }
+    }
+    entries.sort();
+    entries.dedup();
+    writeln!(output, "&[")?;
+    for (key, spelling, part, ambiguous) in entries {
+        writeln!(
+            output,
+            "    ({:?}, {:?}, NamePart::{}, {}),",
+            key, spelling, part, ambiguous
+        )?;
+    }
+    writeln!(output, "]")
+}
+
+struct YamlError(serde_yaml::Error);
+
+impl From<YamlError> for std::io::Error {
+    fn from(YamlError(error): YamlError) -> std::io::Error {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, error)
+    }
+}
+
+// Reads a rule file and reports its dead rules as warnings, or as an error when
+// `PETROVICH_DENY_DEAD_RULES` is set
+fn read_checked_rules(path: &Path) -> std::io::Result<rules::Rules> {
+    let rules = read_rules(path)?;
+    let dead = rules.dead_rules();
+    if dead.is_empty() {
+        return Ok(rules);
+    }
+    if std::env::var_os("PETROVICH_DENY_DEAD_RULES").is_some() {
+        let messages: Vec<_> = dead
+            .iter()
+            .map(|message| format!("{}: {}", path.display(), message))
+            .collect();
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            messages.join("\n"),
+        ));
+    }
+    for message in dead {
+        println!("cargo:warning={}: {}", path.display(), message);
+    }
+    Ok(rules)
+}
+
+// Data files read besides the rules, whichever features use them
+const DATA: &[&str] = &[
+    "src/rules_uk.yml",
+    "src/gender.yml",
+    "src/gender_uk.yml",
+    "src/diminutives.yml",
+    "src/yofication.yml",
+];
+
+fn read_gender(path: &str) -> std::io::Result<GenderHeuristicsList> {
+    let gender_yaml = std::fs::File::open(path)?;
+    Ok(serde_yaml::from_reader(BufReader::new(gender_yaml)).map_err(YamlError)?)
+}
+
+fn read_ukrainian_rules() -> std::io::Result<rules::Rules> {
+    let path = Path::new("src/rules_uk.yml");
+    read_rules_with_cases(path, UKRAINIAN_CASES)
+}
+
+fn read_diminutives() -> std::io::Result<DiminutiveList> {
+    let diminutives_yaml = std::fs::File::open("src/diminutives.yml")?;
+    Ok(serde_yaml::from_reader(BufReader::new(diminutives_yaml)).map_err(YamlError)?)
+}
+
+fn read_yofication() -> std::io::Result<YoficationList> {
+    let yofication_yaml = std::fs::File::open("src/yofication.yml")?;
+    Ok(serde_yaml::from_reader(BufReader::new(yofication_yaml)).map_err(YamlError)?)
+}
+
+fn create(path: &Path) -> std::io::Result<BufWriter<std::fs::File>> {
+    let file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?;
+    Ok(BufWriter::new(file))
+}
+
+// Whether the crate is built with the feature, given in the `CARGO_FEATURE_` spelling
+fn has_feature(name: &str) -> bool {
+    std::env::var_os(format!("CARGO_FEATURE_{}", name)).is_some()
+}
+
+// Code for the copies in `src/generated` used without the `generate-rules` feature, named
+// by file; the rule lists fit every set of features and the tables always hold the
+// Ukrainian rules
+pub fn precompiled(rules_path: &Path) -> std::io::Result<Vec<(&'static str, Vec<u8>)>> {
+    let rules = read_rules(rules_path)?;
+    let ukrainian = read_ukrainian_rules()?;
+    let (mut rules_code, mut ukrainian_code, mut tables_code) =
+        (Vec::new(), Vec::new(), Vec::new());
+    generate_rule_sets(
+        &mut [(&rules, &mut rules_code), (&ukrainian, &mut ukrainian_code)],
+        true,
+        &mut tables_code,
+    )?;
+    let mut gender_code = Vec::new();
+    generate_gender(&read_gender("src/gender.yml")?.gender, &mut gender_code)?;
+    let mut ukrainian_gender_code = Vec::new();
+    generate_gender(
+        &read_gender("src/gender_uk.yml")?.gender,
+        &mut ukrainian_gender_code,
+    )?;
+    let mut diminutives_code = Vec::new();
+    generate_diminutives(&read_diminutives()?, &mut diminutives_code)?;
+    let mut yofication_code = Vec::new();
+    generate_yofication(&read_yofication()?, &mut yofication_code)?;
+    Ok(vec![
+        ("rules.inc", rules_code),
+        ("rules_uk.inc", ukrainian_code),
+        ("rule_tables.inc", tables_code),
+        ("gender.inc", gender_code),
+        ("gender_uk.inc", ukrainian_gender_code),
+        ("diminutives.inc", diminutives_code),
+        ("yofication.inc", yofication_code),
+    ])
+}
+
+// Writes the code generated from the data next to the build output and fails with the
+// copies in `src/generated` that differ from it
+fn check_precompiled(rules_path: &Path, out_dir: &Path) -> std::io::Result<()> {
+    let candidates = out_dir.join("generated");
+    std::fs::create_dir_all(&candidates)?;
+    let mut outdated = Vec::new();
+    for (name, code) in precompiled(rules_path)? {
+        let candidate = candidates.join(name);
+        std::fs::write(&candidate, &code)?;
+        let path = Path::new("src/generated").join(name);
+        if std::fs::read(&path).ok().as_deref() != Some(&code[..]) {
+            eprintln!("{} differs from {}", path.display(), candidate.display());
+            outdated.push(path.display().to_string());
+        }
+    }
+    if outdated.is_empty() {
+        return Ok(());
+    }
+    Err(std::io::Error::other(format!(
+        "outdated {}, run `cargo run -p petrovich-build --bin regenerate`",
+        outdated.join(", ")
+    )))
+}
+
+// Rewrites the copies in `src/generated` of the `petrovich` package at `root` that differ
+// from the code generated from its data, returning their paths
+pub fn regenerate(root: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let rules_path = if root.join("src/rules.json").exists() {
+        "src/rules.json"
+    } else {
+        "src/rules.yml"
+    };
+    std::env::set_current_dir(root)?;
+    let mut rewritten = Vec::new();
+    for (name, code) in precompiled(Path::new(rules_path))? {
+        let path = Path::new("src/generated").join(name);
+        if std::fs::read(&path).ok().as_deref() != Some(&code[..]) {
+            std::fs::write(&path, &code)?;
+            rewritten.push(path);
+        }
+    }
+    Ok(rewritten)
+}
+
+pub fn main() -> std::io::Result<()> {
+    let out_dir = std::env::var_os("OUT_DIR").unwrap();
+    let out_dir = Path::new(&out_dir);
+
+    // The upstream rules.json can be dropped in verbatim in place of rules.yml
+    let rules_path = if Path::new("src/rules.json").exists() {
+        Path::new("src/rules.json")
+    } else {
+        Path::new("src/rules.yml")
+    };
+    println!("cargo:rerun-if-changed={}", rules_path.display());
+    for path in DATA {
+        println!("cargo:rerun-if-changed={}", path);
+    }
+    println!("cargo:rerun-if-env-changed=PETROVICH_DENY_DEAD_RULES");
+    let mut rules = read_checked_rules(rules_path)?;
+
+    if has_feature("REGEN_RULES") {
+        println!("cargo:rerun-if-changed=src/generated");
+        check_precompiled(rules_path, out_dir)?;
+    }
+
+    // Extra rule files take precedence over the built-in rules, earlier files over later ones
+    println!("cargo:rerun-if-env-changed=PETROVICH_EXTRA_RULES");
+    if let Some(paths) = std::env::var_os("PETROVICH_EXTRA_RULES") {
+        let paths = std::env::split_paths(&paths)
+            .filter(|path| !path.as_os_str().is_empty())
+            .collect::<Vec<_>>();
+        for path in paths.iter().rev() {
+            println!("cargo:rerun-if-changed={}", path.display());
+            rules.merge(read_checked_rules(path)?);
+        }
+    }
+    let parts = [
+        has_feature("LASTNAME_RULES"),
+        has_feature("FIRSTNAME_RULES"),
+        has_feature("MIDDLENAME_RULES"),
+    ];
+    rules.retain(parts[0], parts[1], parts[2]);
+    let mut rules_output = create(&out_dir.join("rules.inc"))?;
+    let mut tables_output = create(&out_dir.join("rule_tables.inc"))?;
+    if has_feature("LANG_UK") {
+        let mut ukrainian = read_ukrainian_rules()?;
+        ukrainian.retain(parts[0], parts[1], parts[2]);
+        generate_rule_sets(
+            &mut [
+                (&rules, &mut rules_output),
+                (&ukrainian, &mut create(&out_dir.join("rules_uk.inc"))?),
+            ],
+            false,
+            &mut tables_output,
+        )?;
+        let gender = read_gender("src/gender_uk.yml")?;
+        generate_gender(&gender.gender, &mut create(&out_dir.join("gender_uk.inc"))?)?;
+    } else {
+        generate_rules(&rules, false, &mut rules_output, &mut tables_output)?;
+    }
+
+    if has_feature("GENDER_DETECTION") {
+        let gender = read_gender("src/gender.yml")?;
+        generate_gender(&gender.gender, &mut create(&out_dir.join("gender.inc"))?)?;
+    }
+
+    if has_feature("DIMINUTIVES") {
+        let diminutives = read_diminutives()?;
+        generate_diminutives(&diminutives, &mut create(&out_dir.join("diminutives.inc"))?)?;
+    }
+
+    if has_feature("YOFICATION") {
+        let yofication = read_yofication()?;
+        generate_yofication(&yofication, &mut create(&out_dir.join("yofication.inc"))?)?;
+    }
+    Ok(())
+}
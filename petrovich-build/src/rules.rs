@@ -0,0 +1,694 @@
+//! Rule schema shared by `rules.yml` and the upstream `rules.json`, and the code generator
+//! producing `rules.inc` and `rule_tables.inc` from it.
+//!
+//! Included by the build script and by the tests checking both formats generate the same code.
+
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufReader, Write};
+use std::path::Path;
+
+#[derive(Deserialize, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+enum Gender {
+    #[serde(rename(deserialize = "male"))]
+    Male,
+    #[serde(rename(deserialize = "female"))]
+    Female,
+    #[serde(rename(deserialize = "androgynous"))]
+    Androgynous,
+}
+
+#[derive(Deserialize, Debug, Eq, PartialEq)]
+enum RuleTag {
+    #[serde(rename(deserialize = "first_word"))]
+    FirstWord,
+    #[serde(rename(deserialize = "fleeting_vowel"))]
+    FleetingVowel,
+    #[serde(rename(deserialize = "particle"))]
+    Particle,
+    #[serde(rename(deserialize = "stressed_ending"))]
+    StressedEnding,
+}
+
+#[derive(Deserialize)]
+struct Rule {
+    gender: Gender,
+    test: Vec<String>,
+    mods: Vec<String>,
+    #[serde(default = "Vec::new")]
+    tags: Vec<RuleTag>,
+}
+
+#[derive(Deserialize, Default)]
+struct RuleList {
+    #[serde(default = "Vec::new")]
+    exceptions: Vec<Rule>,
+    #[serde(default = "Vec::new")]
+    suffixes: Vec<Rule>,
+}
+
+// Number of oblique cases the rules of each language give a modifier for: genitive,
+// dative, accusative, instrumental and prepositional, plus the vocative in Ukrainian
+pub const RUSSIAN_CASES: usize = 5;
+pub const UKRAINIAN_CASES: usize = 6;
+
+// Checks the test strings and modifiers of a rule
+fn validate_rule(section: &str, index: usize, rule: &Rule, cases: usize) -> Result<(), String> {
+    let section = format!("{}[{}]", section, index);
+    if rule.mods.len() != cases {
+        return Err(format!(
+            "{}: expected {} mods, found {}",
+            section,
+            cases,
+            rule.mods.len()
+        ));
+    }
+    let shortest = rule
+        .test
+        .iter()
+        .min_by_key(|test| test.chars().count())
+        .ok_or_else(|| format!("{}: rule has no test strings", section))?;
+    for test in &rule.test {
+        if test.is_empty() {
+            return Err(format!("{}: empty test string", section));
+        }
+        // Rule tests need composed letters, e.g. "й" rather than "и" with a combining breve
+        if test.chars().any(|c| ('\u{300}'..='\u{36f}').contains(&c)) {
+            return Err(format!("{}: test {:?} is not in NFC", section, test));
+        }
+        if let Some(c) = test
+            .chars()
+            .find(|&c| !('\u{400}'..='\u{4ff}').contains(&c) || !c.is_alphabetic())
+        {
+            return Err(format!(
+                "{}: test {:?} has non-Cyrillic character {:?}",
+                section, test, c
+            ));
+        }
+        if test.chars().any(char::is_uppercase) {
+            return Err(format!("{}: test {:?} is not lowercase", section, test));
+        }
+    }
+    for modifier in &rule.mods {
+        let strip = modifier.len() - modifier.trim_start_matches('-').len();
+        if strip > shortest.chars().count() {
+            return Err(format!(
+                "{}: modifier {:?} strips {} letters, more than test {:?} has",
+                section, modifier, strip, shortest
+            ));
+        }
+    }
+    Ok(())
+}
+
+impl Gender {
+    const ALL: [Gender; 3] = [Gender::Male, Gender::Female, Gender::Androgynous];
+
+    fn name(self) -> &'static str {
+        match self {
+            Gender::Male => "male",
+            Gender::Female => "female",
+            Gender::Androgynous => "androgynous",
+        }
+    }
+}
+
+// Whether names inflected for the gender can get the rule
+fn reaches(rule: &Rule, gender: Gender) -> bool {
+    rule.gender == gender || rule.gender == Gender::Androgynous
+}
+
+// Rule that gets the names of the gender `rules[index]` would get with the test. Exceptions go
+// to the first rule the gender reaches, suffixes of equal length to the first rule of exactly
+// the gender, then to the first androgynous one. Tags of the other rule must not rule out
+// names `rules[index]` allows
+fn taken_by(
+    rules: &[Rule],
+    index: usize,
+    test: &str,
+    gender: Gender,
+    exception: bool,
+) -> Option<usize> {
+    let rule = &rules[index];
+    let rank = |i: usize, other: &Rule| (!exception && other.gender != gender, i);
+    rules
+        .iter()
+        .enumerate()
+        .filter(|&(i, other)| {
+            i != index
+                && other.test.iter().any(|other| other == test)
+                && reaches(other, gender)
+                && other.tags.iter().all(|tag| rule.tags.contains(tag))
+                && rank(i, other) < rank(index, rule)
+        })
+        .min_by_key(|&(i, other)| rank(i, other))
+        .map(|(i, _)| i)
+}
+
+// Why the test of `rules[index]` can never apply, if it can't: every gender reaching the rule
+// has the names ending with it taken by another rule
+fn dead_test(
+    section: &str,
+    rules: &[Rule],
+    index: usize,
+    test: &str,
+    exception: bool,
+) -> Option<String> {
+    let rule = &rules[index];
+    let takers = Gender::ALL
+        .iter()
+        .filter(|&&gender| reaches(rule, gender))
+        .map(|&gender| Some((gender, taken_by(rules, index, test, gender, exception)?)))
+        .collect::<Option<Vec<_>>>()?;
+    if takers.iter().all(|&(_, taker)| taker == takers[0].1) {
+        return Some(format!(
+            "test {:?} is taken by {}[{}]",
+            test, section, takers[0].1
+        ));
+    }
+    let takers: Vec<_> = takers
+        .iter()
+        .map(|&(gender, taker)| format!("{}[{}] for {}", section, taker, gender.name()))
+        .collect();
+    let (last, rest) = takers.split_last()?;
+    Some(format!(
+        "test {:?} is taken by {} and {} names",
+        test,
+        rest.join(", "),
+        last
+    ))
+}
+
+fn dead_rules(section: &str, rules: &[Rule], exception: bool, warnings: &mut Vec<String>) {
+    for (index, rule) in rules.iter().enumerate() {
+        if rule.tags.contains(&RuleTag::FirstWord) && rule.tags.contains(&RuleTag::Particle) {
+            warnings.push(format!(
+                "{}[{}]: rule is unreachable, tags first_word and particle exclude each other",
+                section, index
+            ));
+            continue;
+        }
+        let dead: Vec<_> = rule
+            .test
+            .iter()
+            .enumerate()
+            .filter_map(|(i, test)| {
+                if rule.test[..i].contains(test) {
+                    return Some(format!("test {:?} is listed twice", test));
+                }
+                dead_test(section, rules, index, test, exception)
+            })
+            .collect();
+        if dead.is_empty() {
+            continue;
+        }
+        if dead.len() == rule.test.len() {
+            warnings.push(format!(
+                "{}[{}]: rule is unreachable, {}",
+                section,
+                index,
+                dead.join(", ")
+            ));
+        } else {
+            for message in dead {
+                warnings.push(format!("{}[{}]: {}", section, index, message));
+            }
+        }
+    }
+}
+
+impl RuleList {
+    // Checks every rule, and that no name is an exception of the same gender twice with
+    // different modifiers or tags
+    fn validate(&self, part: &str, cases: usize) -> Result<(), String> {
+        let section = format!("{}.exceptions", part);
+        let mut exceptions: HashMap<(&str, Gender), usize> = HashMap::new();
+        for (index, rule) in self.exceptions.iter().enumerate() {
+            validate_rule(&section, index, rule, cases)?;
+            for test in &rule.test {
+                match exceptions.get(&(test.as_str(), rule.gender)) {
+                    Some(&other)
+                        if self.exceptions[other].mods != rule.mods
+                            || self.exceptions[other].tags != rule.tags =>
+                    {
+                        return Err(format!(
+                            "{}[{}]: test {:?} contradicts {}[{}] for {:?} gender",
+                            section, index, test, section, other, rule.gender
+                        ));
+                    }
+                    Some(_) => {}
+                    None => {
+                        exceptions.insert((test, rule.gender), index);
+                    }
+                }
+            }
+        }
+        let section = format!("{}.suffixes", part);
+        for (index, rule) in self.suffixes.iter().enumerate() {
+            validate_rule(&section, index, rule, cases)?;
+        }
+        Ok(())
+    }
+
+    fn dead_rules(&self, part: &str, warnings: &mut Vec<String>) {
+        let section = format!("{}.exceptions", part);
+        dead_rules(&section, &self.exceptions, true, warnings);
+        let section = format!("{}.suffixes", part);
+        dead_rules(&section, &self.suffixes, false, warnings);
+    }
+
+    // Puts the rules of `extra` in front of these ones
+    fn merge(&mut self, mut extra: RuleList) {
+        extra.exceptions.append(&mut self.exceptions);
+        self.exceptions = extra.exceptions;
+        extra.suffixes.append(&mut self.suffixes);
+        self.suffixes = extra.suffixes;
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Rules {
+    #[serde(default)]
+    lastname: RuleList,
+    #[serde(default)]
+    firstname: RuleList,
+    #[serde(default)]
+    middlename: RuleList,
+}
+
+impl Rules {
+    // Checks the rules of a single file with modifiers for `cases` oblique cases, naming
+    // the section, the test string and the modifier at fault
+    pub fn validate(&self, cases: usize) -> Result<(), String> {
+        self.lastname.validate("lastname", cases)?;
+        self.firstname.validate("firstname", cases)?;
+        self.middlename.validate("middlename", cases)
+    }
+
+    // Rules and tests no name can reach because earlier rules take every name they match
+    pub fn dead_rules(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        self.lastname.dead_rules("lastname", &mut warnings);
+        self.firstname.dead_rules("firstname", &mut warnings);
+        self.middlename.dead_rules("middlename", &mut warnings);
+        warnings
+    }
+
+    pub fn merge(&mut self, extra: Rules) {
+        self.lastname.merge(extra.lastname);
+        self.firstname.merge(extra.firstname);
+        self.middlename.merge(extra.middlename);
+    }
+
+    // Empties the rule lists of the parts left out of the build
+    pub fn retain(&mut self, lastname: bool, firstname: bool, middlename: bool) {
+        for (list, keep) in [
+            (&mut self.lastname, lastname),
+            (&mut self.firstname, firstname),
+            (&mut self.middlename, middlename),
+        ] {
+            if !keep {
+                *list = RuleList::default();
+            }
+        }
+    }
+}
+
+// Tables the generated rules refer to by position. Strings, modifiers and tag lists are
+// stored once however many rules share them
+#[derive(Default)]
+struct Tables {
+    strings: String,
+    string_ranges: BTreeMap<String, (u32, u32)>,
+    tests: Vec<(u32, u32)>,
+    // The first modifier keeps the name as is
+    modifiers: Vec<Option<(u32, u32, u32)>>,
+    modifier_positions: BTreeMap<(u32, u32, u32), u32>,
+    tags: Vec<String>,
+    tag_ranges: BTreeMap<Vec<String>, (u32, u32)>,
+    rules: Vec<EmbeddedRule>,
+}
+
+// Rule as positions in the tables
+struct EmbeddedRule {
+    // Range of `Tables::tests`
+    tests: (u32, u32),
+    // Positions in `Tables::modifiers` by case, the vocative of Ukrainian rules last
+    mods: [u32; 7],
+    // Range of `Tables::tags`
+    tags: (u32, u32),
+}
+
+impl Tables {
+    fn new() -> Tables {
+        Tables {
+            modifiers: vec![None],
+            ..Tables::default()
+        }
+    }
+
+    fn string(&mut self, string: &str) -> (u32, u32) {
+        if let Some(&range) = self.string_ranges.get(string) {
+            return range;
+        }
+        let start = self.strings.len() as u32;
+        self.strings.push_str(string);
+        let range = (start, self.strings.len() as u32);
+        self.string_ranges.insert(string.to_owned(), range);
+        range
+    }
+
+    fn modifier(&mut self, modifier: &str) -> u32 {
+        if modifier == "." {
+            return 0;
+        }
+        let ending = modifier.trim_start_matches('-');
+        let (start, end) = self.string(ending);
+        let key = ((modifier.len() - ending.len()) as u32, start, end);
+        if let Some(&position) = self.modifier_positions.get(&key) {
+            return position;
+        }
+        let position = self.modifiers.len() as u32;
+        self.modifiers.push(Some(key));
+        self.modifier_positions.insert(key, position);
+        position
+    }
+
+    fn tags(&mut self, tags: &[RuleTag]) -> (u32, u32) {
+        let tags: Vec<_> = tags
+            .iter()
+            .map(|tag| format!("RuleTag::{:?}", tag))
+            .collect();
+        if tags.is_empty() {
+            return (0, 0);
+        }
+        if let Some(&range) = self.tag_ranges.get(&tags) {
+            return range;
+        }
+        let start = self.tags.len() as u32;
+        self.tags.extend(tags.iter().cloned());
+        let range = (start, self.tags.len() as u32);
+        self.tag_ranges.insert(tags, range);
+        range
+    }
+
+    // Stores the rule and gives its position
+    fn rule(&mut self, rule: &Rule) -> u32 {
+        let start = self.tests.len() as u32;
+        for test in &rule.test {
+            let range = self.string(test);
+            self.tests.push(range);
+        }
+        let tests = (start, self.tests.len() as u32);
+        let mut mods = [0; 7];
+        for (slot, modifier) in mods[1..].iter_mut().zip(&rule.mods) {
+            *slot = self.modifier(modifier);
+        }
+        let tags = self.tags(&rule.tags);
+        self.rules.push(EmbeddedRule { tests, mods, tags });
+        (self.rules.len() - 1) as u32
+    }
+
+    fn generate(&self, output: &mut impl Write) -> std::io::Result<()> {
+        writeln!(output, "RuleTables {{")?;
+        writeln!(output, "    strings: {:?},", self.strings)?;
+        writeln!(output, "    tests: &{:?},", self.tests)?;
+        writeln!(output, "    modifiers: &{:?},", self.modifiers)?;
+        writeln!(output, "    tags: &[{}],", self.tags.join(", "))?;
+        writeln!(output, "    rules: &[")?;
+        for rule in &self.rules {
+            writeln!(
+                output,
+                "        EmbeddedRule {{ tests: {:?}, mods: {:?}, tags: {:?} }},",
+                rule.tests, rule.mods, rule.tags
+            )?;
+        }
+        writeln!(output, "    ],")?;
+        writeln!(output, "}}")
+    }
+}
+
+#[derive(Default)]
+struct SuffixNode {
+    children: BTreeMap<char, usize>,
+    rules: Vec<usize>,
+}
+
+fn fold_yo(c: char) -> char {
+    if c == 'ё' {
+        'е'
+    } else {
+        c
+    }
+}
+
+// Lookup tables of the tests with ё folded to е: exception tests sorted along with their
+// rules, and a trie of the suffix tests read from the end, every node listing the rules
+// having a test spelled by the path to it. `first_exception` is the position of the
+// first exception in the tables
+fn generate_index(
+    list: &RuleList,
+    tables: &Tables,
+    first_exception: u32,
+    output: &mut impl Write,
+) -> std::io::Result<()> {
+    let mut exceptions = Vec::new();
+    for (i, rule) in list.exceptions.iter().enumerate() {
+        let (start, _) = tables.rules[first_exception as usize + i].tests;
+        for (test, position) in rule.test.iter().zip(start..) {
+            let key = test.chars().map(fold_yo).collect::<String>();
+            exceptions.push((key, i, position));
+        }
+    }
+    exceptions.sort();
+    exceptions.dedup_by(|a, b| (&a.0, a.1) == (&b.0, b.1));
+    let exceptions: Vec<_> = exceptions
+        .into_iter()
+        .map(|(_, rule, test)| (test, rule))
+        .collect();
+    let mut nodes = vec![SuffixNode::default()];
+    for (i, rule) in list.suffixes.iter().enumerate() {
+        for test in &rule.test {
+            let mut node = 0;
+            for c in test.chars().rev().map(fold_yo) {
+                node = match nodes[node].children.get(&c) {
+                    Some(&child) => child,
+                    None => {
+                        nodes.push(SuffixNode::default());
+                        let child = nodes.len() - 1;
+                        nodes[node].children.insert(c, child);
+                        child
+                    }
+                };
+            }
+            if nodes[node].rules.last() != Some(&i) {
+                nodes[node].rules.push(i);
+            }
+        }
+    }
+    let (mut children, mut rules): (Vec<(&char, &usize)>, Vec<usize>) = (Vec::new(), Vec::new());
+    writeln!(output, "        index: Some(&RuleIndex {{")?;
+    writeln!(output, "            exceptions: &{:?},", exceptions)?;
+    writeln!(output, "            suffixes: &[")?;
+    for node in &nodes {
+        let child_range = (children.len(), children.len() + node.children.len());
+        children.extend(node.children.iter());
+        let rule_range = (rules.len(), rules.len() + node.rules.len());
+        rules.extend(node.rules.iter().copied());
+        writeln!(
+            output,
+            "                SuffixNode {{ children: {:?}, rules: {:?} }},",
+            child_range, rule_range
+        )?;
+    }
+    writeln!(output, "            ],")?;
+    writeln!(output, "            children: &{:?},", children)?;
+    writeln!(output, "            rules: &{:?},", rules)?;
+    writeln!(output, "        }}),")?;
+    writeln!(output, "        unindexed_exceptions: 0,")
+}
+
+fn generate_rule_list(
+    list: &RuleList,
+    tables: &mut Tables,
+    output: &mut impl Write,
+) -> std::io::Result<()> {
+    let first_exception = tables.rules.len() as u32;
+    writeln!(output, "RuleList {{")?;
+    for (field, rules) in [
+        ("exceptions", &list.exceptions),
+        ("suffixes", &list.suffixes),
+    ] {
+        writeln!(output, "        {}: Cow::Borrowed(&[", field)?;
+        for rule in rules {
+            let position = tables.rule(rule);
+            writeln!(
+                output,
+                "            Rule {{ gender: Gender::{:?}, data: RuleData::Embedded({}) }},",
+                rule.gender, position
+            )?;
+        }
+        writeln!(output, "        ]),")?;
+    }
+    generate_index(list, tables, first_exception, output)?;
+    writeln!(output, "    }},")
+}
+
+// Writes the rule lists to `output` and the tables they refer to to `tables_output`. With
+// `gate_parts` every list is left empty unless its `*-rules` feature is enabled, so the
+// output fits any set of features; the tables still hold the rules of every part
+pub fn generate_rules(
+    rules: &Rules,
+    gate_parts: bool,
+    output: &mut impl Write,
+    tables_output: &mut impl Write,
+) -> std::io::Result<()> {
+    generate_rule_sets(
+        &mut [(rules, output as &mut dyn Write)],
+        gate_parts,
+        tables_output,
+    )
+}
+
+// Writes several rule sets, such as the Russian and the Ukrainian ones, each to its own
+// output, sharing the tables written to `tables_output`
+pub fn generate_rule_sets(
+    sets: &mut [(&Rules, &mut dyn Write)],
+    gate_parts: bool,
+    tables_output: &mut impl Write,
+) -> std::io::Result<()> {
+    let mut tables = Tables::new();
+    for (rules, output) in sets.iter_mut() {
+        generate_rule_set(rules, gate_parts, &mut tables, &mut **output)?;
+    }
+    tables.generate(tables_output)
+}
+
+fn generate_rule_set(
+    rules: &Rules,
+    gate_parts: bool,
+    tables: &mut Tables,
+    mut output: &mut dyn Write,
+) -> std::io::Result<()> {
+    writeln!(output, "Rules {{")?;
+    for (part, list) in [
+        ("lastname", &rules.lastname),
+        ("firstname", &rules.firstname),
+        ("middlename", &rules.middlename),
+    ] {
+        if gate_parts {
+            writeln!(output, "    #[cfg(feature = \"{}-rules\")]", part)?;
+        }
+        write!(output, "    {}: ", part)?;
+        generate_rule_list(list, tables, &mut output)?;
+        if gate_parts {
+            writeln!(output, "    #[cfg(not(feature = \"{}-rules\"))]", part)?;
+            write!(output, "    {}: ", part)?;
+            generate_rule_list(&RuleList::default(), tables, &mut output)?;
+        }
+    }
+    writeln!(output, "}}")
+}
+
+#[derive(Debug)]
+enum Format {
+    Yaml,
+    Json,
+}
+
+impl Format {
+    // Picks the parser by file extension; anything but `.json` is read as YAML
+    fn of(path: &Path) -> Format {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("json") => Format::Json,
+            _ => Format::Yaml,
+        }
+    }
+}
+
+fn check_rules(path: &Path, rules: Rules, cases: usize) -> std::io::Result<Rules> {
+    rules.validate(cases).map_err(|message| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{}: {}", path.display(), message),
+        )
+    })?;
+    Ok(rules)
+}
+
+// Reads and validates a rule file in the `rules.yml` or the upstream `rules.json` format,
+// naming the file and the offending line or rule on failure
+pub fn read_rules(path: &Path) -> std::io::Result<Rules> {
+    read_rules_with_cases(path, RUSSIAN_CASES)
+}
+
+// Reads and validates a rule file giving modifiers for `cases` oblique cases
+pub fn read_rules_with_cases(path: &Path, cases: usize) -> std::io::Result<Rules> {
+    let file = std::fs::File::open(path).map_err(|error| {
+        std::io::Error::new(error.kind(), format!("{}: {}", path.display(), error))
+    })?;
+    let reader = BufReader::new(file);
+    let (line, message) = match Format::of(path) {
+        Format::Yaml => match serde_yaml::from_reader(reader) {
+            Ok(rules) => return check_rules(path, rules, cases),
+            Err(error) => (
+                error.location().map(|location| location.line()),
+                error.to_string(),
+            ),
+        },
+        Format::Json => match serde_json::from_reader(reader) {
+            Ok(rules) => return check_rules(path, rules, cases),
+            Err(error) => (Some(error.line()), error.to_string()),
+        },
+    };
+    let line = line.map_or_else(String::new, |line| format!(":{}", line));
+    Err(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("{}{}: {}", path.display(), line, message),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(gender: Gender, test: &str, tags: Vec<RuleTag>) -> Rule {
+        Rule {
+            gender,
+            test: vec![test.to_owned()],
+            mods: vec![".".to_owned(); RUSSIAN_CASES],
+            tags,
+        }
+    }
+
+    #[test]
+    fn should_take_suffixes_by_exact_gender_first() {
+        let rules = [
+            rule(Gender::Androgynous, "ша", vec![]),
+            rule(Gender::Male, "ша", vec![]),
+            rule(Gender::Androgynous, "ша", vec![]),
+        ];
+        assert_eq!(taken_by(&rules, 0, "ша", Gender::Male, false), Some(1));
+        assert_eq!(taken_by(&rules, 0, "ша", Gender::Female, false), None);
+        assert_eq!(taken_by(&rules, 1, "ша", Gender::Male, false), None);
+        assert_eq!(taken_by(&rules, 2, "ша", Gender::Male, false), Some(1));
+        assert_eq!(taken_by(&rules, 2, "ша", Gender::Female, false), Some(0));
+        assert_eq!(
+            taken_by(&rules, 2, "ша", Gender::Androgynous, false),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn should_take_exceptions_by_position() {
+        let rules = [
+            rule(Gender::Male, "лев", vec![]),
+            rule(Gender::Androgynous, "лев", vec![RuleTag::FirstWord]),
+            rule(Gender::Androgynous, "лев", vec![]),
+        ];
+        assert_eq!(taken_by(&rules, 2, "лев", Gender::Male, true), Some(0));
+        assert_eq!(taken_by(&rules, 2, "лев", Gender::Female, true), None);
+        assert_eq!(taken_by(&rules, 0, "лев", Gender::Male, true), None);
+        assert_eq!(dead_test("s", &rules, 2, "лев", true), None);
+    }
+}
@@ -0,0 +1,167 @@
+//! Derive macro for the `Inflectable` trait of the `petrovich` crate. Use it through
+//! `petrovich` with the `derive` feature enabled.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Field, Fields, Member, Type};
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Role {
+    Last,
+    First,
+    Middle,
+    Gender,
+}
+
+impl Role {
+    fn name(self) -> &'static str {
+        match self {
+            Role::Last => "last",
+            Role::First => "first",
+            Role::Middle => "middle",
+            Role::Gender => "gender",
+        }
+    }
+}
+
+// Role given to the field by its `#[petrovich(...)]` attributes, if any
+fn field_role(field: &Field) -> syn::Result<Option<Role>> {
+    let mut role = None;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("petrovich"))
+    {
+        attr.parse_nested_meta(|meta| {
+            let found = [Role::Last, Role::First, Role::Middle, Role::Gender]
+                .iter()
+                .copied()
+                .find(|role| meta.path.is_ident(role.name()))
+                .ok_or_else(|| meta.error("expected `last`, `first`, `middle` or `gender`"))?;
+            if role.is_some() {
+                return Err(meta.error("field already has a role"));
+            }
+            role = Some(found);
+            Ok(())
+        })?;
+    }
+    Ok(role)
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+// Body of the accessor of a name part
+fn part_accessor(member: &Member, field: &Field) -> TokenStream2 {
+    if is_option(&field.ty) {
+        quote_spanned!(field.ty.span()=> ::std::option::Option::as_deref(&self.#member))
+    } else {
+        quote_spanned! {field.ty.span()=>
+            ::std::option::Option::Some(::std::convert::AsRef::<str>::as_ref(&self.#member))
+        }
+    }
+}
+
+fn derive_inflectable_impl(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "`Inflectable` can only be derived for structs",
+            ))
+        }
+    };
+    let members: Vec<(Member, &Field)> = match fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|field| (Member::Named(field.ident.clone().unwrap()), field))
+            .collect(),
+        Fields::Unnamed(fields) => fields
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(i, field)| (Member::Unnamed(i.into()), field))
+            .collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    let mut methods = Vec::new();
+    let mut seen = Vec::new();
+    for (member, field) in &members {
+        let role = match field_role(field)? {
+            Some(role) => role,
+            None => continue,
+        };
+        if seen.contains(&role) {
+            let span = field
+                .ident
+                .as_ref()
+                .map_or_else(|| field.ty.span(), |ident| ident.span());
+            return Err(Error::new(
+                span,
+                format!("duplicate `#[petrovich({})]` field", role.name()),
+            ));
+        }
+        seen.push(role);
+        let method = match role {
+            Role::Last => quote!(last),
+            Role::First => quote!(first),
+            Role::Middle => quote!(middle),
+            Role::Gender => {
+                methods.push(quote_spanned! {field.ty.span()=>
+                    fn gender(&self) -> ::petrovich::Gender {
+                        self.#member
+                    }
+                });
+                continue;
+            }
+        };
+        let accessor = part_accessor(member, field);
+        methods.push(quote! {
+            fn #method(&self) -> ::std::option::Option<&str> {
+                #accessor
+            }
+        });
+    }
+    if seen.iter().all(|&role| role == Role::Gender) {
+        return Err(Error::new(
+            input.ident.span(),
+            "`Inflectable` needs a field marked `#[petrovich(last)]`, `#[petrovich(first)]` \
+             or `#[petrovich(middle)]`",
+        ));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::petrovich::Inflectable for #name #ty_generics #where_clause {
+            #(#methods)*
+        }
+    })
+}
+
+/// Implements `Inflectable` for a struct whose fields are marked `#[petrovich(last)]`,
+/// `#[petrovich(first)]`, `#[petrovich(middle)]` and `#[petrovich(gender)]`
+///
+/// Name fields are anything that is `AsRef<str>`, or an `Option` of something that
+/// derefs to `str`. The gender field is a `Gender`; without it gender is detected from
+/// the names.
+#[proc_macro_derive(Inflectable, attributes(petrovich))]
+pub fn derive_inflectable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive_inflectable_impl(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
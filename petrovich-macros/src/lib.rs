@@ -0,0 +1,162 @@
+//! Procedural macros for the `petrovich` crate. Use them through `petrovich` with the
+//! `macros` feature enabled.
+
+use std::path::Path;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use serde::Deserialize;
+use syn::{parse_macro_input, LitStr};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Gender {
+    Male,
+    Female,
+    Androgynous,
+}
+
+#[derive(Deserialize)]
+struct Rule {
+    gender: Gender,
+    test: Vec<String>,
+    mods: Vec<String>,
+    #[serde(default = "Vec::new")]
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct RuleList {
+    #[serde(default = "Vec::new")]
+    exceptions: Vec<Rule>,
+    #[serde(default = "Vec::new")]
+    suffixes: Vec<Rule>,
+}
+
+#[derive(Deserialize)]
+struct Rules {
+    #[serde(default)]
+    lastname: RuleList,
+    #[serde(default)]
+    firstname: RuleList,
+    #[serde(default)]
+    middlename: RuleList,
+}
+
+fn generate_modifier(modifier: &str) -> TokenStream2 {
+    if modifier == "." {
+        quote!(::std::option::Option::None)
+    } else {
+        let ending = modifier.trim_start_matches('-');
+        let skip = modifier.len() - ending.len();
+        quote!(::std::option::Option::Some((#skip, ::std::borrow::Cow::Borrowed(#ending))))
+    }
+}
+
+fn generate_rule(section: &str, index: usize, rule: &Rule) -> Result<TokenStream2, String> {
+    if rule.test.is_empty() {
+        return Err(format!("{}[{}]: rule has no test strings", section, index));
+    }
+    if rule.mods.len() != 5 {
+        return Err(format!(
+            "{}[{}]: expected 5 mods, found {}",
+            section,
+            index,
+            rule.mods.len()
+        ));
+    }
+    let gender = match rule.gender {
+        Gender::Male => quote!(::petrovich::Gender::Male),
+        Gender::Female => quote!(::petrovich::Gender::Female),
+        Gender::Androgynous => quote!(::petrovich::Gender::Androgynous),
+    };
+    let test = rule.test.iter().map(|test| test.to_lowercase());
+    let test_count = rule.test.len();
+    let mods = rule.mods.iter().map(|modifier| generate_modifier(modifier));
+    let tags = rule.tags.iter().map(|tag| match tag.as_str() {
+        "first_word" => quote!(::petrovich::RuleTag::FirstWord),
+        "fleeting_vowel" => quote!(::petrovich::RuleTag::FleetingVowel),
+        "particle" => quote!(::petrovich::RuleTag::Particle),
+        "stressed_ending" => quote!(::petrovich::RuleTag::StressedEnding),
+        _ => quote!(::petrovich::RuleTag::Custom(::std::borrow::Cow::Borrowed(#tag))),
+    });
+    let tag_count = rule.tags.len();
+    Ok(quote! {
+        ::petrovich::Rule::from_static(#gender, {
+            static PARTS: ::petrovich::RuleParts = ::petrovich::RuleParts::new(
+                {
+                    static TEST: [::std::borrow::Cow<'static, str>; #test_count] =
+                        [#(::std::borrow::Cow::Borrowed(#test)),*];
+                    &TEST
+                },
+                [::std::option::Option::None, #(#mods),*],
+                {
+                    static TAGS: [::petrovich::RuleTag; #tag_count] = [#(#tags),*];
+                    &TAGS
+                },
+            );
+            &PARTS
+        })
+    })
+}
+
+fn generate_rules(section: &str, rules: &[Rule]) -> Result<TokenStream2, String> {
+    let count = rules.len();
+    let rules = rules
+        .iter()
+        .enumerate()
+        .map(|(index, rule)| generate_rule(section, index, rule))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(quote! {
+        {
+            static RULES: [::petrovich::Rule; #count] = [#(#rules),*];
+            &RULES
+        }
+    })
+}
+
+fn generate_rule_list(part: &str, list: &RuleList) -> Result<TokenStream2, String> {
+    let exceptions = generate_rules(&format!("{}.exceptions", part), &list.exceptions)?;
+    let suffixes = generate_rules(&format!("{}.suffixes", part), &list.suffixes)?;
+    Ok(quote!(::petrovich::RuleList::from_static(#exceptions, #suffixes)))
+}
+
+// Errors name the file as given to the macro
+fn include_rules_impl(root: &Path, file: &str) -> Result<TokenStream2, String> {
+    let path = root.join(file);
+    let source = std::fs::read_to_string(&path)
+        .map_err(|error| format!("can't read {}: {}", file, error))?;
+    let rules: Rules =
+        serde_yaml::from_str(&source).map_err(|error| format!("{}: {}", file, error))?;
+    let lastname = generate_rule_list("lastname", &rules.lastname)
+        .map_err(|error| format!("{}: {}", file, error))?;
+    let firstname = generate_rule_list("firstname", &rules.firstname)
+        .map_err(|error| format!("{}: {}", file, error))?;
+    let middlename = generate_rule_list("middlename", &rules.middlename)
+        .map_err(|error| format!("{}: {}", file, error))?;
+    let path = path.to_string_lossy();
+    Ok(quote! {
+        {
+            // Rebuild when the rules file changes
+            const _: &str = include_str!(#path);
+            ::petrovich::Rules::new(#lastname, #firstname, #middlename)
+        }
+    })
+}
+
+/// Embeds rules from a YAML file with the same structure as `rules.yml` at compile time
+///
+/// The path is relative to the crate root. Sections may be omitted. Expands to a
+/// constant `Rules` expression, so it can initialize a `static`.
+#[proc_macro]
+pub fn include_rules(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    match include_rules_impl(Path::new(&root), &path.value()) {
+        Ok(tokens) => tokens.into(),
+        Err(message) => syn::Error::new(path.span(), message)
+            .to_compile_error()
+            .into(),
+    }
+}
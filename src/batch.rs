@@ -0,0 +1,238 @@
+//! Inflection of many names at once.
+
+use std::collections::HashMap;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+#[cfg(all(feature = "rayon", feature = "gender-detection"))]
+use super::detect_gender;
+#[cfg(feature = "rayon")]
+use super::inflect;
+use super::{inflect_into, Case, Gender, NamePart, ENDING_RESERVE};
+
+// Appends the inflected name to the buffer
+fn inflect_onto(part: NamePart, gender: Gender, name: &str, case: Case, buffer: &mut String) {
+    // Writing to a String never fails
+    let _ = inflect_into(part, gender, name, case, buffer);
+}
+
+/// Inflects names of the same part into the same case, in input order
+///
+/// Gives the same results as calling `inflect` for every name, but the rules are matched
+/// only once for a name repeated in the input with the same gender; later copies reuse
+/// the first result. That is where all of the gain comes from, so input where most names
+/// are distinct is inflected no faster than with `inflect`.
+///
+/// ```
+/// # #[cfg(feature = "lastname-rules")]
+/// # {
+/// use petrovich::{inflect_many, Case, Gender, NamePart};
+///
+/// let names = [
+///     (Gender::Male, "Иванов"),
+///     (Gender::Female, "Иванова"),
+///     (Gender::Male, "Иванов"),
+/// ];
+/// assert_eq!(
+///     inflect_many(NamePart::Last, Case::Dative, names.iter().copied()),
+///     ["Иванову", "Ивановой", "Иванову"]
+/// );
+/// # }
+/// ```
+pub fn inflect_many<'a, I>(part: NamePart, case: Case, items: I) -> Vec<String>
+where
+    I: IntoIterator<Item = (Gender, &'a str)>,
+{
+    let items = items.into_iter();
+    let mut output: Vec<String> = Vec::with_capacity(items.size_hint().0);
+    let mut seen: HashMap<(Gender, &'a str), usize> = HashMap::new();
+    for (gender, name) in items {
+        let inflected = match seen.get(&(gender, name)) {
+            Some(&index) => output[index].clone(),
+            None => {
+                seen.insert((gender, name), output.len());
+                let mut inflected = String::with_capacity(name.len() + ENDING_RESERVE);
+                inflect_onto(part, gender, name, case, &mut inflected);
+                inflected
+            }
+        };
+        output.push(inflected);
+    }
+    output
+}
+
+/// Inflects names of the same part into the same case, passing each result to the callback
+/// in input order
+///
+/// A single buffer is reused for all the names, so nothing is allocated per name when the
+/// callback only writes the result somewhere, and the input can be streamed.
+///
+/// ```
+/// # #[cfg(feature = "firstname-rules")]
+/// # {
+/// use petrovich::{inflect_each, Case, Gender, NamePart};
+///
+/// let mut lines = String::new();
+/// inflect_each(
+///     NamePart::First,
+///     Case::Genitive,
+///     vec![(Gender::Male, "Саша"), (Gender::Female, "Анна")],
+///     |name| {
+///         lines.push_str(name);
+///         lines.push('\n');
+///     },
+/// );
+/// assert_eq!(lines, "Саши\nАнны\n");
+/// # }
+/// ```
+pub fn inflect_each<'a, I, F>(part: NamePart, case: Case, items: I, mut callback: F)
+where
+    I: IntoIterator<Item = (Gender, &'a str)>,
+    F: FnMut(&str),
+{
+    let mut buffer = String::new();
+    for (gender, name) in items {
+        buffer.clear();
+        inflect_onto(part, gender, name, case, &mut buffer);
+        callback(&buffer);
+    }
+}
+
+/// Inflects names of the same part into the same case on the rayon thread pool, keeping the
+/// input order
+///
+/// ```
+/// use petrovich::{par_inflect_many, Case, Gender, NamePart};
+///
+/// let names = vec![(Gender::Male, "Иванов"), (Gender::Female, "Петрова")];
+/// assert_eq!(
+///     par_inflect_many(NamePart::Last, Case::Genitive, names),
+///     ["Иванова", "Петровой"]
+/// );
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_inflect_many<'a, I>(part: NamePart, case: Case, items: I) -> Vec<String>
+where
+    I: IntoParallelIterator<Item = (Gender, &'a str)>,
+{
+    items
+        .into_par_iter()
+        .map(|(gender, name)| inflect(part, gender, name, case))
+        .collect()
+}
+
+/// Detects gender of many `(last, first, middle)` names on the rayon thread pool, keeping
+/// the input order; see `detect_gender`
+#[cfg(all(feature = "rayon", feature = "gender-detection"))]
+pub fn par_detect_gender_many<'a, I>(items: I) -> Vec<Gender>
+where
+    I: IntoParallelIterator<Item = (Option<&'a str>, Option<&'a str>, Option<&'a str>)>,
+{
+    items
+        .into_par_iter()
+        .map(|(last, first, middle)| detect_gender(last, first, middle))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inflect;
+
+    const NAMES: &[(Gender, &str)] = &[
+        (Gender::Male, "Лёша"),
+        (Gender::Female, "Лёша"),
+        (Gender::Male, "Бонч-Бруевич"),
+        (Gender::Male, "Лёша"),
+        (Gender::Female, "Blabla"),
+        (Gender::Male, ""),
+        (Gender::Androgynous, "Саша"),
+    ];
+
+    #[test]
+    fn should_match_single_inflection() {
+        for &part in &[NamePart::First, NamePart::Last, NamePart::Middle] {
+            for &case in Case::ALL.iter() {
+                let expected = NAMES
+                    .iter()
+                    .map(|&(gender, name)| inflect(part, gender, name, case))
+                    .collect::<Vec<_>>();
+                assert_eq!(inflect_many(part, case, NAMES.iter().copied()), expected);
+
+                let mut streamed = Vec::new();
+                inflect_each(part, case, NAMES.iter().copied(), |name| {
+                    streamed.push(name.to_owned())
+                });
+                assert_eq!(streamed, expected);
+            }
+        }
+    }
+
+    // Deterministic names built from common stems, endings and genders, some hyphenated
+    #[cfg(feature = "rayon")]
+    fn fixture(count: usize) -> Vec<(Gender, String)> {
+        const STEMS: &[&str] = &[
+            "Иван",
+            "Петр",
+            "Сидор",
+            "Кузнец",
+            "Смирн",
+            "Соколов",
+            "Лебедев",
+            "Козл",
+            "Новик",
+            "Морозов",
+            "Волк",
+            "Семён",
+            "Голуб",
+            "Богдан",
+            "Фёдор",
+            "Михайл",
+            "Ли",
+        ];
+        const ENDINGS: &[&str] = &[
+            "ов", "ова", "ин", "ина", "ский", "ская", "енко", "ич", "", "ых",
+        ];
+        const GENDERS: &[Gender] = &[Gender::Male, Gender::Female, Gender::Androgynous];
+        (0..count)
+            .map(|i| {
+                let mut name = STEMS[i % STEMS.len()].to_owned();
+                name.push_str(ENDINGS[i / STEMS.len() % ENDINGS.len()]);
+                if i % 7 == 0 {
+                    name.push('-');
+                    name.push_str(STEMS[i / 7 % STEMS.len()]);
+                    name.push('а');
+                }
+                (GENDERS[i / 3 % GENDERS.len()], name)
+            })
+            .collect()
+    }
+
+    #[cfg(all(feature = "rayon", feature = "gender-detection"))]
+    #[test]
+    fn should_inflect_in_parallel_like_serially() {
+        let names = fixture(100_000);
+        let items = names
+            .iter()
+            .map(|(gender, name)| (*gender, name.as_str()))
+            .collect::<Vec<_>>();
+        for &part in &[NamePart::First, NamePart::Last, NamePart::Middle] {
+            assert_eq!(
+                par_inflect_many(part, Case::Instrumental, items.clone()),
+                inflect_many(part, Case::Instrumental, items.iter().copied())
+            );
+        }
+        let triples = names
+            .iter()
+            .map(|(_, name)| (Some(name.as_str()), None, None))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            par_detect_gender_many(triples.clone()),
+            triples
+                .iter()
+                .map(|&(last, first, middle)| detect_gender(last, first, middle))
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,302 @@
+//! Command-line interface to petrovich.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::process::exit;
+
+use petrovich::{
+    coverage_report_with_limit, detect_gender, inflect, parse_fullname, Case, Gender, NamePart,
+    DEFAULT_PASS_THROUGH_LIMIT,
+};
+
+const USAGE: &str = "\
+Usage: petrovich coverage [--part last|first|middle] [--limit N] FILE
+       petrovich inflect [--case CASE] [--parts last,first,middle] [--detect-gender] [--csv]
+       petrovich gender [--csv]
+       petrovich parse [--csv]
+
+coverage  Reads one name per line, optionally followed by a tab and a gender
+          (male, female or androgynous), and reports which names the rules handle.
+inflect   Reads rows of last, first and middle names and a gender from stdin and
+          writes them back with the inflected parts appended. The gender column may
+          be left out or empty with --detect-gender, the detected gender is written
+          in its place. CASE defaults to genitive.
+gender    Reads rows of last, first and middle names and appends the detected gender.
+parse     Reads one full name per line and writes its last, first and middle names
+          and the detected gender.
+
+Rows are tab-separated, or comma-separated with --csv; fields can't be quoted.";
+
+fn fail(message: &str) -> ! {
+    eprintln!("petrovich: {}\n\n{}", message, USAGE);
+    exit(2)
+}
+
+// Problem with a row of the input, reported after the rows before it are written
+struct RowError {
+    row: usize,
+    message: String,
+}
+
+fn row_error(row: usize, message: impl Into<String>) -> RowError {
+    RowError {
+        row,
+        message: message.into(),
+    }
+}
+
+// Runs a command writing rows to stdout, flushing the rows written before a malformed one
+// and then reporting it
+fn write_rows(rows: impl FnOnce(&mut BufWriter<io::StdoutLock<'static>>) -> Result<(), RowError>) {
+    let mut output = BufWriter::new(io::stdout().lock());
+    let result = rows(&mut output);
+    if output.flush().is_err() {
+        exit(1)
+    }
+    if let Err(error) = result {
+        eprintln!("petrovich: row {}: {}", error.row, error.message);
+        exit(1)
+    }
+}
+
+fn parse_part(value: &str) -> NamePart {
+    value
+        .parse()
+        .unwrap_or_else(|error: petrovich::Error| fail(&error.to_string()))
+}
+
+fn parse_gender(value: &str, line: usize) -> Gender {
+    value.parse().unwrap_or_else(|error| {
+        eprintln!("petrovich: {} on line {}", error, line);
+        exit(1)
+    })
+}
+
+// Lines of stdin numbered from 1, read one at a time
+fn lines() -> impl Iterator<Item = Result<(usize, String), RowError>> {
+    io::stdin().lock().lines().enumerate().map(|(i, line)| {
+        line.map(|line| (i + 1, line))
+            .map_err(|error| row_error(i + 1, error.to_string()))
+    })
+}
+
+// Writes a row, stopping quietly when the reader has gone away
+fn write_row<'a>(
+    output: &mut impl Write,
+    delimiter: char,
+    fields: impl IntoIterator<Item = &'a str>,
+) {
+    let mut line = String::new();
+    for (i, field) in fields.into_iter().enumerate() {
+        if i > 0 {
+            line.push(delimiter);
+        }
+        line.push_str(field);
+    }
+    line.push('\n');
+    if output.write_all(line.as_bytes()).is_err() {
+        exit(1)
+    }
+}
+
+fn non_empty(field: &str) -> Option<&str> {
+    Some(field.trim()).filter(|field| !field.is_empty())
+}
+
+// Checks the row has the last, first and middle name columns
+fn name_columns<'a>(row: usize, fields: &[&'a str]) -> Result<[Option<&'a str>; 3], RowError> {
+    if fields.len() < 3 {
+        return Err(row_error(
+            row,
+            format!(
+                "expected last, first and middle name columns, found {}",
+                fields.len()
+            ),
+        ));
+    }
+    Ok([
+        non_empty(fields[0]),
+        non_empty(fields[1]),
+        non_empty(fields[2]),
+    ])
+}
+
+fn inflect_rows(mut args: impl Iterator<Item = String>) {
+    let mut case = Case::Genitive;
+    let mut parts = vec![NamePart::Last, NamePart::First, NamePart::Middle];
+    let mut detect = false;
+    let mut delimiter = '\t';
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--case" => {
+                case = args
+                    .next()
+                    .unwrap_or_default()
+                    .parse()
+                    .unwrap_or_else(|error: petrovich::Error| fail(&error.to_string()))
+            }
+            "--parts" => {
+                parts = args
+                    .next()
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(parse_part)
+                    .collect()
+            }
+            "--detect-gender" => detect = true,
+            "--csv" => delimiter = ',',
+            _ => fail(&format!("unexpected argument {:?}", arg)),
+        }
+    }
+    write_rows(|output| {
+        for line in lines() {
+            let (row, line) = line?;
+            let fields = line.split(delimiter).collect::<Vec<_>>();
+            let [last, first, middle] = name_columns(row, &fields)?;
+            if fields.len() > 4 {
+                return Err(row_error(
+                    row,
+                    format!("expected at most 4 columns, found {}", fields.len()),
+                ));
+            }
+            let gender = match fields.get(3).and_then(|field| non_empty(field)) {
+                Some(gender) => gender
+                    .parse()
+                    .map_err(|error: petrovich::Error| row_error(row, error.to_string()))?,
+                None if detect => detect_gender(last, first, middle),
+                None => {
+                    return Err(row_error(
+                        row,
+                        "missing gender, use --detect-gender to detect it",
+                    ))
+                }
+            };
+            let inflected = parts
+                .iter()
+                .map(|&part| {
+                    let name = match part {
+                        NamePart::Last => last,
+                        NamePart::First => first,
+                        NamePart::Middle => middle,
+                    };
+                    name.map_or_else(String::new, |name| inflect(part, gender, name, case))
+                })
+                .collect::<Vec<_>>();
+            write_row(
+                output,
+                delimiter,
+                fields[..3]
+                    .iter()
+                    .copied()
+                    .chain(Some(gender.name()))
+                    .chain(inflected.iter().map(String::as_str)),
+            );
+        }
+        Ok(())
+    })
+}
+
+fn gender_rows(args: impl Iterator<Item = String>) {
+    let delimiter = csv_flag(args);
+    write_rows(|output| {
+        for line in lines() {
+            let (row, line) = line?;
+            let fields = line.split(delimiter).collect::<Vec<_>>();
+            let [last, first, middle] = name_columns(row, &fields)?;
+            let gender = detect_gender(last, first, middle);
+            write_row(
+                output,
+                delimiter,
+                fields.iter().copied().chain(Some(gender.name())),
+            );
+        }
+        Ok(())
+    })
+}
+
+fn parse_rows(args: impl Iterator<Item = String>) {
+    let delimiter = csv_flag(args);
+    write_rows(|output| {
+        for line in lines() {
+            let (row, line) = line?;
+            let name = parse_fullname(&line).map_err(|error| row_error(row, error.to_string()))?;
+            write_row(
+                output,
+                delimiter,
+                vec![
+                    name.last().unwrap_or_default(),
+                    name.first().unwrap_or_default(),
+                    name.middle().unwrap_or_default(),
+                    name.gender().name(),
+                ],
+            );
+        }
+        Ok(())
+    })
+}
+
+// Delimiter chosen by the only allowed flag, --csv
+fn csv_flag(args: impl Iterator<Item = String>) -> char {
+    let mut delimiter = '\t';
+    for arg in args {
+        match arg.as_str() {
+            "--csv" => delimiter = ',',
+            _ => fail(&format!("unexpected argument {:?}", arg)),
+        }
+    }
+    delimiter
+}
+
+fn coverage(mut args: impl Iterator<Item = String>) {
+    let mut part = NamePart::Last;
+    let mut limit = DEFAULT_PASS_THROUGH_LIMIT;
+    let mut path = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => part = parse_part(&args.next().unwrap_or_default()),
+            "--limit" => {
+                limit = args
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or_else(|| fail("--limit expects a number"))
+            }
+            _ if path.is_none() => path = Some(arg),
+            _ => fail(&format!("unexpected argument {:?}", arg)),
+        }
+    }
+    let path = path.unwrap_or_else(|| fail("missing input file"));
+    let file = File::open(&path).unwrap_or_else(|error| {
+        eprintln!("petrovich: can't open {}: {}", path, error);
+        exit(1)
+    });
+    let names = BufReader::new(file)
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let line = line.unwrap_or_else(|error| {
+                eprintln!("petrovich: can't read line {}: {}", i + 1, error);
+                exit(1)
+            });
+            let mut columns = line.splitn(2, '\t');
+            let name = columns.next().unwrap_or_default().trim().to_owned();
+            let gender = columns.next().map_or(Gender::Androgynous, |gender| {
+                parse_gender(gender.trim(), i + 1)
+            });
+            (gender, name)
+        })
+        .filter(|(_, name)| !name.is_empty());
+    println!("{}", coverage_report_with_limit(part, names, limit));
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    match args.next().as_deref() {
+        Some("coverage") => coverage(args),
+        Some("inflect") => inflect_rows(args),
+        Some("gender") => gender_rows(args),
+        Some("parse") => parse_rows(args),
+        Some("--help") | Some("-h") => println!("{}", USAGE),
+        Some(command) => fail(&format!("unknown command {:?}", command)),
+        None => fail("missing command"),
+    }
+}
@@ -0,0 +1,371 @@
+//! Inflection with a cache of recently inflected names.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use super::{Case, Gender, Inflector, NamePart};
+
+type Key = (NamePart, Gender, Case);
+
+const NIL: usize = usize::MAX;
+
+struct Entry {
+    key: Key,
+    name: String,
+    inflected: String,
+    prev: usize,
+    next: usize,
+}
+
+// Least recently used cache; entries form a list from the most to the least recently used
+struct Lru {
+    capacity: usize,
+    entries: Vec<Entry>,
+    // Names are looked up by `&str`, so they are indexed separately for every key
+    index: HashMap<Key, HashMap<String, usize>>,
+    head: usize,
+    tail: usize,
+}
+
+impl Lru {
+    fn new(capacity: usize) -> Lru {
+        Lru {
+            capacity,
+            entries: Vec::new(),
+            index: HashMap::new(),
+            head: NIL,
+            tail: NIL,
+        }
+    }
+
+    fn unlink(&mut self, i: usize) {
+        let (prev, next) = (self.entries[i].prev, self.entries[i].next);
+        match prev {
+            NIL => self.head = next,
+            prev => self.entries[prev].next = next,
+        }
+        match next {
+            NIL => self.tail = prev,
+            next => self.entries[next].prev = prev,
+        }
+    }
+
+    fn push_front(&mut self, i: usize) {
+        self.entries[i].prev = NIL;
+        self.entries[i].next = self.head;
+        match self.head {
+            NIL => self.tail = i,
+            head => self.entries[head].prev = i,
+        }
+        self.head = i;
+    }
+
+    fn get(&mut self, key: Key, name: &str) -> Option<&str> {
+        let i = *self.index.get(&key)?.get(name)?;
+        self.unlink(i);
+        self.push_front(i);
+        Some(&self.entries[i].inflected)
+    }
+
+    fn insert(&mut self, key: Key, name: &str, inflected: String) {
+        if self.capacity == 0 {
+            return;
+        }
+        if let Some(&i) = self.index.get(&key).and_then(|names| names.get(name)) {
+            self.entries[i].inflected = inflected;
+            self.unlink(i);
+            self.push_front(i);
+            return;
+        }
+        let i = if self.entries.len() < self.capacity {
+            self.entries.push(Entry {
+                key,
+                name: name.to_owned(),
+                inflected,
+                prev: NIL,
+                next: NIL,
+            });
+            self.entries.len() - 1
+        } else {
+            // Reuse the least recently used entry
+            let i = self.tail;
+            self.unlink(i);
+            let entry = &mut self.entries[i];
+            if let Some(names) = self.index.get_mut(&entry.key) {
+                names.remove(&entry.name);
+            }
+            entry.key = key;
+            entry.name.clear();
+            entry.name.push_str(name);
+            entry.inflected = inflected;
+            i
+        };
+        self.push_front(i);
+        self.index
+            .entry(key)
+            .or_default()
+            .insert(name.to_owned(), i);
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn clear(&mut self) {
+        *self = Lru::new(self.capacity);
+    }
+}
+
+/// Numbers of cache lookups, see `CachedInflector::stats`
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Default)]
+pub struct CacheStats {
+    /// Names found in the cache
+    pub hits: u64,
+    /// Names inflected by the rules
+    pub misses: u64,
+    /// Names currently cached
+    pub len: usize,
+}
+
+/// Inflector remembering up to `capacity` recently inflected names
+///
+/// Names are cached exactly as given, so "ИВАНОВ", "Иванов" and "Иванов" spelled with ё
+/// are separate entries and never get each other's forms. Use `SyncCachedInflector` to
+/// share a cache between threads.
+///
+/// ```
+/// use petrovich::{Case, CachedInflector, Gender, Inflector};
+///
+/// let mut inflector = CachedInflector::new(Inflector::default(), 1000);
+/// assert_eq!(inflector.lastname(Gender::Male, "Иванов", Case::Dative), "Иванову");
+/// assert_eq!(inflector.lastname(Gender::Male, "Иванов", Case::Dative), "Иванову");
+/// assert_eq!(inflector.stats().hits, 1);
+/// assert_eq!(inflector.stats().misses, 1);
+/// ```
+pub struct CachedInflector {
+    inflector: Inflector,
+    cache: Lru,
+    hits: u64,
+    misses: u64,
+}
+
+impl CachedInflector {
+    /// Wraps the inflector with a cache of the given capacity
+    pub fn new(inflector: Inflector, capacity: usize) -> CachedInflector {
+        CachedInflector {
+            inflector,
+            cache: Lru::new(capacity),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Inflects a name using the rules for the given part, see `Inflector::inflect`
+    pub fn inflect(&mut self, part: NamePart, gender: Gender, name: &str, case: Case) -> String {
+        if let Some(inflected) = self.cache.get((part, gender, case), name) {
+            self.hits += 1;
+            return inflected.to_owned();
+        }
+        self.misses += 1;
+        let inflected = self.inflector.inflect(part, gender, name, case);
+        self.cache
+            .insert((part, gender, case), name, inflected.clone());
+        inflected
+    }
+
+    /// Inflects first name
+    pub fn firstname(&mut self, gender: Gender, name: &str, case: Case) -> String {
+        self.inflect(NamePart::First, gender, name, case)
+    }
+
+    /// Inflects last name
+    pub fn lastname(&mut self, gender: Gender, name: &str, case: Case) -> String {
+        self.inflect(NamePart::Last, gender, name, case)
+    }
+
+    /// Inflects middle name
+    pub fn middlename(&mut self, gender: Gender, name: &str, case: Case) -> String {
+        self.inflect(NamePart::Middle, gender, name, case)
+    }
+
+    /// Numbers of hits and misses since creation and the number of cached names
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            len: self.cache.len(),
+        }
+    }
+
+    /// Forgets the cached names, keeping the counters
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    /// Wrapped inflector
+    pub fn inflector(&self) -> &Inflector {
+        &self.inflector
+    }
+}
+
+/// Inflector with a cache shared between threads, see `CachedInflector`
+///
+/// Inflection itself runs outside the lock, so two threads missing the same name may
+/// both inflect it.
+pub struct SyncCachedInflector {
+    inflector: Inflector,
+    cache: Mutex<Lru>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl SyncCachedInflector {
+    /// Wraps the inflector with a cache of the given capacity
+    pub fn new(inflector: Inflector, capacity: usize) -> SyncCachedInflector {
+        SyncCachedInflector {
+            inflector,
+            cache: Mutex::new(Lru::new(capacity)),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    // A panic can't leave the cache inconsistent, so a poisoned lock is still usable
+    fn cache(&self) -> std::sync::MutexGuard<'_, Lru> {
+        self.cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Inflects a name using the rules for the given part, see `Inflector::inflect`
+    pub fn inflect(&self, part: NamePart, gender: Gender, name: &str, case: Case) -> String {
+        if let Some(inflected) = self.cache().get((part, gender, case), name) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return inflected.to_owned();
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let inflected = self.inflector.inflect(part, gender, name, case);
+        self.cache()
+            .insert((part, gender, case), name, inflected.clone());
+        inflected
+    }
+
+    /// Inflects first name
+    pub fn firstname(&self, gender: Gender, name: &str, case: Case) -> String {
+        self.inflect(NamePart::First, gender, name, case)
+    }
+
+    /// Inflects last name
+    pub fn lastname(&self, gender: Gender, name: &str, case: Case) -> String {
+        self.inflect(NamePart::Last, gender, name, case)
+    }
+
+    /// Inflects middle name
+    pub fn middlename(&self, gender: Gender, name: &str, case: Case) -> String {
+        self.inflect(NamePart::Middle, gender, name, case)
+    }
+
+    /// Numbers of hits and misses since creation and the number of cached names
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            len: self.cache().len(),
+        }
+    }
+
+    /// Forgets the cached names, keeping the counters
+    pub fn clear(&self) {
+        self.cache().clear();
+    }
+
+    /// Wrapped inflector
+    pub fn inflector(&self) -> &Inflector {
+        &self.inflector
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_evict_least_recently_used() {
+        let mut inflector = CachedInflector::new(Inflector::default(), 2);
+        inflector.lastname(Gender::Male, "Иванов", Case::Dative);
+        inflector.lastname(Gender::Male, "Петров", Case::Dative);
+        // Refreshes "Иванов", so "Петров" is evicted next
+        inflector.lastname(Gender::Male, "Иванов", Case::Dative);
+        inflector.lastname(Gender::Male, "Сидоров", Case::Dative);
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Иванов", Case::Dative),
+            "Иванову"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Петров", Case::Dative),
+            "Петрову"
+        );
+        assert_eq!(
+            inflector.stats(),
+            CacheStats {
+                hits: 2,
+                misses: 4,
+                len: 2
+            }
+        );
+        inflector.clear();
+        assert_eq!(inflector.stats().len, 0);
+    }
+
+    #[test]
+    fn should_update_entries_inserted_twice() {
+        // Threads missing the same name at once both insert it
+        let key = (NamePart::Last, Gender::Male, Case::Dative);
+        let mut lru = Lru::new(2);
+        lru.insert(key, "Иванов", "Иванову".to_owned());
+        lru.insert(key, "Петров", "Петрову".to_owned());
+        lru.insert(key, "Иванов", "Иванову".to_owned());
+        assert_eq!(lru.len(), 2);
+        lru.insert(key, "Сидоров", "Сидорову".to_owned());
+        assert_eq!(lru.get(key, "Иванов"), Some("Иванову"));
+        assert_eq!(lru.get(key, "Петров"), None);
+        assert_eq!(lru.get(key, "Сидоров"), Some("Сидорову"));
+    }
+
+    #[test]
+    fn should_keep_spellings_apart() {
+        let inflector = SyncCachedInflector::new(Inflector::default(), 16);
+        assert_eq!(
+            inflector.lastname(Gender::Male, "ИВАНОВ", Case::Dative),
+            Inflector::default().lastname(Gender::Male, "ИВАНОВ", Case::Dative)
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Иванов", Case::Dative),
+            "Иванову"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Female, "Иванов", Case::Dative),
+            "Иванов"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Иванов", Case::Genitive),
+            "Иванова"
+        );
+        assert_eq!(inflector.stats().misses, 4);
+        assert_eq!(inflector.stats().hits, 0);
+    }
+
+    #[test]
+    fn should_share_between_threads() {
+        fn assert_sync<T: Send + Sync>() {}
+        assert_sync::<SyncCachedInflector>();
+
+        let inflector = SyncCachedInflector::new(Inflector::default(), 0);
+        assert_eq!(
+            inflector.firstname(Gender::Male, "Саша", Case::Dative),
+            "Саше"
+        );
+        assert_eq!(inflector.stats().len, 0);
+    }
+}
@@ -0,0 +1,199 @@
+//! Grammatical cases and their names.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use super::Error;
+
+/// Возможные падежи
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+#[non_exhaustive]
+pub enum Case {
+    /// Именительный | _Кто? Что?_
+    #[cfg_attr(feature = "serde", serde(alias = "именительный"))]
+    Nominative,
+    /// Родительный  | _Кого? Чего?_
+    #[cfg_attr(feature = "serde", serde(alias = "родительный"))]
+    Genitive,
+    /// Дательный    | _Кому? Чему?_
+    #[cfg_attr(feature = "serde", serde(alias = "дательный"))]
+    Dative,
+    /// Винительный  | _Кого? Что?_
+    #[cfg_attr(feature = "serde", serde(alias = "винительный"))]
+    Accusative,
+    /// Творительный | _Кем? Чем?_
+    #[cfg_attr(feature = "serde", serde(alias = "творительный"))]
+    Instrumental,
+    /// Предложный   | _О ком? О чём?_
+    #[cfg_attr(feature = "serde", serde(alias = "предложный"))]
+    Prepositional,
+}
+
+impl Case {
+    /// All cases in the order of their indices
+    pub const ALL: [Case; 6] = [
+        Case::Nominative,
+        Case::Genitive,
+        Case::Dative,
+        Case::Accusative,
+        Case::Instrumental,
+        Case::Prepositional,
+    ];
+
+    /// Iterates over all cases in the order of their indices
+    pub fn iter() -> impl Iterator<Item = Case> {
+        Case::ALL.iter().copied()
+    }
+
+    /// English identifier of the case, e.g. "genitive"
+    pub fn name(self) -> &'static str {
+        match self {
+            Case::Nominative => "nominative",
+            Case::Genitive => "genitive",
+            Case::Dative => "dative",
+            Case::Accusative => "accusative",
+            Case::Instrumental => "instrumental",
+            Case::Prepositional => "prepositional",
+        }
+    }
+
+    /// Russian name of the case, e.g. "родительный"
+    pub fn russian_name(self) -> &'static str {
+        match self {
+            Case::Nominative => "именительный",
+            Case::Genitive => "родительный",
+            Case::Dative => "дательный",
+            Case::Accusative => "винительный",
+            Case::Instrumental => "творительный",
+            Case::Prepositional => "предложный",
+        }
+    }
+
+    /// Standard Russian abbreviation of the case, e.g. "род."
+    pub fn russian_abbreviation(self) -> &'static str {
+        match self {
+            Case::Nominative => "им.",
+            Case::Genitive => "род.",
+            Case::Dative => "дат.",
+            Case::Accusative => "вин.",
+            Case::Instrumental => "тв.",
+            Case::Prepositional => "предл.",
+        }
+    }
+}
+
+/// Index of the case, also used to look up rule modifiers
+impl From<Case> for usize {
+    fn from(case: Case) -> usize {
+        case as usize
+    }
+}
+
+impl TryFrom<usize> for Case {
+    type Error = Error;
+
+    fn try_from(index: usize) -> Result<Case, Error> {
+        Case::ALL
+            .get(index)
+            .copied()
+            .ok_or(Error::CaseIndexOutOfRange(index))
+    }
+}
+
+/// Prints the English identifier, or the Russian name with the alternate flag (`{:#}`)
+impl fmt::Display for Case {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.write_str(self.russian_name())
+        } else {
+            f.write_str(self.name())
+        }
+    }
+}
+
+/// Parses English and Russian names and Russian abbreviations, ignoring letter case
+impl FromStr for Case {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Case, Error> {
+        let lowercase = s.trim().to_lowercase();
+        match lowercase.trim_end_matches('.') {
+            "nominative" | "именительный" | "им" => Ok(Case::Nominative),
+            "genitive" | "родительный" | "род" => Ok(Case::Genitive),
+            "dative" | "дательный" | "дат" => Ok(Case::Dative),
+            "accusative" | "винительный" | "вин" => Ok(Case::Accusative),
+            "instrumental" | "творительный" | "тв" | "твор" => {
+                Ok(Case::Instrumental)
+            }
+            "prepositional" | "предложный" | "предл" | "пр" => {
+                Ok(Case::Prepositional)
+            }
+            _ => Err(Error::UnknownCase(s.to_owned())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_round_trip_display() {
+        for case in Case::iter() {
+            assert_eq!(case.to_string().parse(), Ok(case));
+            assert_eq!(format!("{:#}", case).parse(), Ok(case));
+            assert_eq!(case.russian_abbreviation().parse(), Ok(case));
+        }
+    }
+
+    #[test]
+    fn should_convert_indices() {
+        for (index, case) in Case::ALL.iter().copied().enumerate() {
+            assert_eq!(usize::from(case), index);
+            assert_eq!(Case::try_from(index), Ok(case));
+        }
+        assert_eq!(Case::try_from(6), Err(Error::CaseIndexOutOfRange(6)));
+        assert_eq!(
+            Error::CaseIndexOutOfRange(6).to_string(),
+            "case index 6 is out of range 0..6"
+        );
+    }
+
+    #[test]
+    fn should_parse_case_insensitively() {
+        assert_eq!("Дательный".parse(), Ok(Case::Dative));
+        assert_eq!(" ДАТ. ".parse(), Ok(Case::Dative));
+        assert_eq!("дат".parse(), Ok(Case::Dative));
+        assert_eq!("Dative".parse(), Ok(Case::Dative));
+        assert_eq!("тв.".parse(), Ok(Case::Instrumental));
+        assert_eq!("предл.".parse(), Ok(Case::Prepositional));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_serialize_as_lowercase_string() {
+        for case in Case::iter() {
+            let json = serde_json::to_string(&case).unwrap();
+            assert_eq!(json, format!("\"{}\"", case));
+            assert_eq!(serde_json::from_str::<Case>(&json).unwrap(), case);
+            let russian = format!("\"{:#}\"", case);
+            assert_eq!(serde_json::from_str::<Case>(&russian).unwrap(), case);
+        }
+        assert!(serde_json::from_str::<Case>("\"vocative\"").is_err());
+    }
+
+    #[test]
+    fn should_reject_unknown_case() {
+        assert_eq!(
+            "звательный".parse::<Case>(),
+            Err(Error::UnknownCase("звательный".to_owned()))
+        );
+        assert!("".parse::<Case>().is_err());
+    }
+}
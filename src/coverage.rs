@@ -0,0 +1,138 @@
+//! Reports on how well the rules cover a set of names.
+
+use std::fmt;
+
+use super::rules::{NoCustomTags, RULES};
+use super::{match_name, Gender, InflectOptions, MatchKind, NamePart};
+
+/// Default number of pass-through names kept in a `CoverageReport`
+pub const DEFAULT_PASS_THROUGH_LIMIT: usize = 100;
+
+/// Numbers of names by the kind of rules that matched them
+#[derive(Eq, PartialEq, Clone, Debug, Default)]
+pub struct CoverageReport {
+    /// Total number of names
+    pub total: usize,
+    /// Names where every segment matched and at least one matched an exception
+    pub exceptions: usize,
+    /// Names where every segment matched a suffix rule
+    pub suffixes: usize,
+    /// Names where only some of the segments matched
+    pub partial: usize,
+    /// Names passed through unchanged because no segment matched
+    pub pass_through: usize,
+    /// Pass-through names, in input order and up to the configured limit
+    pub pass_through_names: Vec<(Gender, String)>,
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "total: {}", self.total)?;
+        writeln!(f, "exceptions: {}", self.exceptions)?;
+        writeln!(f, "suffixes: {}", self.suffixes)?;
+        writeln!(f, "partial: {}", self.partial)?;
+        write!(f, "pass-through: {}", self.pass_through)?;
+        for (_, name) in &self.pass_through_names {
+            write!(f, "\n  {}", name)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reports which names the embedded rules handle, keeping up to
+/// `DEFAULT_PASS_THROUGH_LIMIT` pass-through names
+pub fn coverage_report<I>(part: NamePart, names: I) -> CoverageReport
+where
+    I: IntoIterator<Item = (Gender, String)>,
+{
+    coverage_report_with_limit(part, names, DEFAULT_PASS_THROUGH_LIMIT)
+}
+
+/// Reports which names the embedded rules handle, keeping up to `limit` pass-through names
+pub fn coverage_report_with_limit<I>(part: NamePart, names: I, limit: usize) -> CoverageReport
+where
+    I: IntoIterator<Item = (Gender, String)>,
+{
+    let rule_list = RULES.list(part);
+    let mut report = CoverageReport::default();
+    for (gender, name) in names {
+        report.total += 1;
+        let segments = match_name(
+            gender,
+            &name,
+            rule_list,
+            &NoCustomTags,
+            &InflectOptions::DEFAULT,
+        )
+        .unwrap_or_default();
+        let matched = segments
+            .iter()
+            .filter(|segment| segment.rule.is_some())
+            .count();
+        if matched == 0 {
+            report.pass_through += 1;
+            if report.pass_through_names.len() < limit {
+                report.pass_through_names.push((gender, name));
+            }
+        } else if matched < segments.len() {
+            report.partial += 1;
+        } else if segments
+            .iter()
+            .any(|segment| matches!(segment.rule, Some((MatchKind::Exception, _))))
+        {
+            report.exceptions += 1;
+        } else {
+            report.suffixes += 1;
+        }
+    }
+    report
+}
+
+#[cfg(all(test, feature = "lastname-rules"))]
+mod tests {
+    use super::*;
+
+    fn fixture() -> Vec<(Gender, String)> {
+        vec![
+            (Gender::Male, "Иванов"),
+            (Gender::Female, "Иванова"),
+            (Gender::Male, "Цой"),
+            (Gender::Male, "Бонч-Бруевич"),
+            (Gender::Male, "Иванов-Smith"),
+            (Gender::Male, "Smith"),
+            (Gender::Female, "Qwerty"),
+        ]
+        .into_iter()
+        .map(|(gender, name)| (gender, name.to_owned()))
+        .collect()
+    }
+
+    #[test]
+    fn should_report_coverage() {
+        let report = coverage_report(NamePart::Last, fixture());
+        assert_eq!(
+            report,
+            CoverageReport {
+                total: 7,
+                exceptions: 2,
+                suffixes: 2,
+                partial: 1,
+                pass_through: 2,
+                pass_through_names: vec![
+                    (Gender::Male, "Smith".to_owned()),
+                    (Gender::Female, "Qwerty".to_owned()),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn should_cap_pass_through_names() {
+        let report = coverage_report_with_limit(NamePart::Last, fixture(), 1);
+        assert_eq!(report.pass_through, 2);
+        assert_eq!(
+            report.pass_through_names,
+            vec![(Gender::Male, "Smith".to_owned())]
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! All case forms of a name, computed with a single rule lookup, and matching of strings
+//! against them.
+
+use std::ops::Index;
+
+use super::rules::{NoCustomTags, RuleList, TagFilter, RULES};
+use super::{match_name, nfc, Case, Error, Gender, InflectOptions, NamePart, ENDING_RESERVE};
+
+/// Forms of a name in every case, including the nominative
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Declensions {
+    forms: [String; 6],
+}
+
+impl Declensions {
+    /// Form of the name in the given case
+    pub fn get(&self, case: Case) -> &str {
+        &self.forms[usize::from(case)]
+    }
+
+    /// First case whose form equals the candidate, ignoring letter case and the difference
+    /// between ё and е
+    ///
+    /// Computing the forms once and calling this for many candidates is the fastest way to
+    /// look for a name in a large text.
+    pub fn find_case(&self, candidate: &str) -> Option<Case> {
+        self.iter()
+            .find(|(_, form)| folded_eq(form.chars(), candidate))
+            .map(|(case, _)| case)
+    }
+
+    /// Rewrites every form
+    #[cfg(feature = "old-orthography")]
+    pub(crate) fn map_forms(mut self, mut f: impl FnMut(&str) -> String) -> Declensions {
+        for form in self.forms.iter_mut() {
+            *form = f(form);
+        }
+        self
+    }
+
+    /// Iterates over `(Case, form)` pairs in the order of `Case::ALL`
+    pub fn iter(&self) -> impl Iterator<Item = (Case, &str)> {
+        Case::iter().zip(self.forms.iter().map(String::as_str))
+    }
+}
+
+impl Index<Case> for Declensions {
+    type Output = str;
+
+    fn index(&self, case: Case) -> &str {
+        self.get(case)
+    }
+}
+
+impl IntoIterator for Declensions {
+    type Item = (Case, String);
+    type IntoIter = std::iter::Zip<std::array::IntoIter<Case, 6>, std::array::IntoIter<String, 6>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter(Case::ALL).zip(self.forms)
+    }
+}
+
+pub(crate) fn declensions_with(
+    gender: Gender,
+    name: &str,
+    rule_list: &RuleList,
+    filter: &impl TagFilter,
+    options: &InflectOptions,
+) -> Result<Declensions, Error> {
+    let name = &*nfc(name);
+    let segments = match_name(gender, name, rule_list, filter, options)?;
+    let mut forms: [String; 6] = Default::default();
+    for (case, form) in Case::iter().zip(forms.iter_mut()) {
+        form.reserve(name.len() + ENDING_RESERVE);
+        for segment in &segments {
+            segment.inflect_into(case, form);
+        }
+    }
+    Ok(Declensions { forms })
+}
+
+// Lowercase characters with ё replaced by е
+fn fold(c: char) -> impl Iterator<Item = char> {
+    c.to_lowercase().map(|c| if c == 'ё' { 'е' } else { c })
+}
+
+fn folded_eq(form: impl Iterator<Item = char>, candidate: &str) -> bool {
+    form.flat_map(fold).eq(candidate.chars().flat_map(fold))
+}
+
+/// Finds the case in which the nominative takes the form of the candidate, comparing
+/// case-insensitively and treating ё as е
+///
+/// Forms are compared character by character without being built. Cases are tried in the
+/// order of `Case::ALL`, so a form shared by several cases is reported as the first of them.
+///
+/// ```
+/// # #[cfg(all(feature = "firstname-rules", feature = "lastname-rules"))]
+/// # {
+/// use petrovich::{matching_case, Case, Gender, NamePart};
+///
+/// assert_eq!(
+///     matching_case(Gender::Male, "Иванов", "ивановым", NamePart::Last),
+///     Some(Case::Instrumental)
+/// );
+/// assert_eq!(
+///     matching_case(Gender::Male, "Пётр", "Петру", NamePart::First),
+///     Some(Case::Dative)
+/// );
+/// # }
+/// ```
+pub fn matching_case(
+    gender: Gender,
+    nominative: &str,
+    candidate: &str,
+    part: NamePart,
+) -> Option<Case> {
+    let (nominative, candidate) = (&*nfc(nominative), &*nfc(candidate));
+    let segments = match_name(
+        gender,
+        nominative,
+        RULES.list(part),
+        &NoCustomTags,
+        &InflectOptions::DEFAULT,
+    )
+    .ok()?;
+    Case::iter().find(|&case| {
+        folded_eq(
+            segments
+                .iter()
+                .flat_map(|segment| segment.inflected_chars(case)),
+            candidate,
+        )
+    })
+}
+
+/// Whether the candidate is some case form of the nominative, see `matching_case`
+pub fn matches_any_case(gender: Gender, nominative: &str, candidate: &str, part: NamePart) -> bool {
+    matching_case(gender, nominative, candidate, part).is_some()
+}
+
+/// Inflects a name into every case at once, matching the rules only once
+pub fn declensions(gender: Gender, name: &str, part: NamePart) -> Declensions {
+    declensions_with(
+        gender,
+        name,
+        RULES.list(part),
+        &NoCustomTags,
+        &InflectOptions::DEFAULT,
+    )
+    .unwrap_or_else(|_| Declensions {
+        forms: [(); 6].map(|_| name.to_owned()),
+    })
+}
+
+#[cfg(all(test, feature = "firstname-rules"))]
+mod tests {
+    use super::*;
+    #[cfg(feature = "lastname-rules")]
+    use crate::{firstname, lastname};
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_match_any_case() {
+        assert!(matches_any_case(
+            Gender::Male,
+            "Иванов",
+            "Иванове",
+            NamePart::Last
+        ));
+        assert!(matches_any_case(
+            Gender::Male,
+            "Иванов",
+            "ИВАНОВ",
+            NamePart::Last
+        ));
+        assert!(!matches_any_case(
+            Gender::Male,
+            "Иванов",
+            "Ивановой",
+            NamePart::Last
+        ));
+        assert_eq!(
+            matching_case(
+                Gender::Female,
+                "Иванова-Сидорова",
+                "ивановой-сидоровой",
+                NamePart::Last
+            ),
+            Some(Case::Genitive)
+        );
+        assert_eq!(
+            matching_case(Gender::Male, "Семен", "Семёном", NamePart::First),
+            Some(Case::Instrumental)
+        );
+        assert_eq!(
+            matching_case(Gender::Male, "Иванов", "Иванову-", NamePart::Last),
+            None
+        );
+    }
+
+    #[test]
+    fn should_find_case_in_declensions() {
+        let forms = declensions(Gender::Male, "Лёша", NamePart::First);
+        assert_eq!(forms.find_case("лешей"), Some(Case::Instrumental));
+        assert_eq!(forms.find_case("Лёшу"), Some(Case::Accusative));
+        assert_eq!(forms.find_case("Лёх"), None);
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_match_single_case_inflection() {
+        for &(gender, name) in &[
+            (Gender::Male, "Бонч-Бруевич"),
+            (Gender::Female, "Иванова"),
+            (Gender::Male, "Blabla"),
+        ] {
+            let forms = declensions(gender, name, NamePart::Last);
+            for (case, form) in forms.iter() {
+                assert_eq!(form, lastname(gender, name, case));
+            }
+        }
+        let forms = declensions(Gender::Male, "Лёша", NamePart::First);
+        assert_eq!(&forms[Case::Nominative], "Лёша");
+        assert_eq!(&forms[Case::Instrumental], "Лёшей");
+        assert_eq!(
+            forms.into_iter().collect::<Vec<_>>(),
+            Case::iter()
+                .map(|case| (case, firstname(Gender::Male, "Лёша", case)))
+                .collect::<Vec<_>>()
+        );
+    }
+}
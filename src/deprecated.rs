@@ -1,20 +1,32 @@
 //! Deprecated types. Don't use them.
 
-use super::{Case, Gender};
+use super::strict::inflect_checked;
+use super::{Case, Error, Gender, NamePart};
+
+// The old API reports errors as static strings
+fn describe(error: Error) -> &'static str {
+    match error {
+        Error::EmptyName => "name is empty",
+        Error::InvalidCharacter { .. } => "name contains an invalid character",
+        Error::NonCyrillicInput(_) => "name has no Cyrillic letters",
+        Error::NoRuleMatched(_) => "no rule matched the name",
+        _ => "name can't be inflected",
+    }
+}
 
 #[deprecated(
-    since = "0.2",
+    since = "0.2.0",
     note = "Use free functions in the 'petrovich' module instead"
 )]
 pub struct Petrovich;
 
-#[allow(deprecated)]
+#[allow(deprecated, clippy::new_without_default)]
 impl Petrovich {
     pub fn new() -> Petrovich {
         Petrovich
     }
 
-    #[deprecated(since = "0.2", note = "Use petrovich::firstname function")]
+    #[deprecated(since = "0.2.0", note = "Use petrovich::firstname_checked function")]
     #[inline]
     pub fn firstname(
         &self,
@@ -22,10 +34,10 @@ impl Petrovich {
         name: &str,
         case: Case,
     ) -> Result<String, &'static str> {
-        Ok(super::firstname(gender, name, case))
+        inflect_checked(NamePart::First, gender, name, case).map_err(describe)
     }
 
-    #[deprecated(since = "0.2", note = "Use petrovich::middlename function")]
+    #[deprecated(since = "0.2.0", note = "Use petrovich::middlename_checked function")]
     #[inline]
     pub fn middlename(
         &self,
@@ -33,17 +45,18 @@ impl Petrovich {
         name: &str,
         case: Case,
     ) -> Result<String, &'static str> {
-        Ok(super::middlename(gender, name, case))
+        inflect_checked(NamePart::Middle, gender, name, case).map_err(describe)
     }
 
-    #[deprecated(since = "0.2", note = "Use petrovich::lastname function")]
+    #[deprecated(since = "0.2.0", note = "Use petrovich::lastname_checked function")]
     #[inline]
     pub fn lastname(&self, gender: Gender, name: &str, case: Case) -> Result<String, &'static str> {
-        Ok(super::lastname(gender, name, case))
+        inflect_checked(NamePart::Last, gender, name, case).map_err(describe)
     }
 
-    #[deprecated(since = "0.2", note = "Use petrovich::detect_gender function")]
+    #[deprecated(since = "0.2.0", note = "Use petrovich::detect_gender function")]
     #[inline]
+    #[cfg(feature = "gender-detection")]
     pub fn detect_gender(middlename: &str) -> Gender {
         super::detect_gender(None, None, Some(middlename))
     }
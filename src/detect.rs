@@ -0,0 +1,690 @@
+//! Gender detection from the name parts.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Range;
+
+#[cfg(feature = "diminutives")]
+use super::diminutives::diminutive_gender;
+use super::options::is_cyrillic_word;
+use super::rules::{ends_with, fold_yo_char, lowercase, RuleTag, RULES};
+use super::{nfc, Gender, NamePart};
+
+struct GenderMapping {
+    androgynous: &'static [&'static str],
+    male: &'static [&'static str],
+    female: &'static [&'static str],
+}
+
+struct GenderHeuristic {
+    exceptions: Option<GenderMapping>,
+    suffixes: GenderMapping,
+}
+
+/// Kind of heuristic rule a name part matched
+#[derive(Clone, Copy)]
+enum Evidence {
+    Exception,
+    /// Suffix with the given length in characters
+    Suffix(usize),
+}
+
+impl GenderHeuristic {
+    // Detects every word and hyphen-separated part of the name on its own. A male or female
+    // part outvotes androgynous and unknown ones, the most reliable match giving the
+    // evidence; parts pointing to both male and female cancel out
+    fn detect(&self, name: &str) -> Option<(Gender, Evidence)> {
+        let verdicts = name
+            .split(|c: char| c.is_whitespace() || c == '-')
+            .filter(|part| !part.is_empty())
+            .filter_map(|part| self.detect_part(part))
+            .collect::<Vec<_>>();
+        let has = |gender| verdicts.iter().any(|&(verdict, _)| verdict == gender);
+        if has(Gender::Male) && has(Gender::Female) {
+            return None;
+        }
+        let strongest = |verdicts: &mut dyn Iterator<Item = &(Gender, Evidence)>| {
+            verdicts
+                .max_by_key(|(_, evidence)| match evidence {
+                    Evidence::Exception => usize::MAX,
+                    Evidence::Suffix(length) => *length,
+                })
+                .copied()
+        };
+        strongest(
+            &mut verdicts
+                .iter()
+                .filter(|(gender, _)| *gender != Gender::Androgynous),
+        )
+        .or_else(|| strongest(&mut verdicts.iter()))
+    }
+
+    // ё and е match each other, so "Семен" is known as well as "Семён". Exceptions win over
+    // suffixes, and the longest suffix wins whichever gender it is listed for; the same
+    // exception or suffix listed for several genders counts as androgynous, then female
+    fn detect_part(&self, name: &str) -> Option<(Gender, Evidence)> {
+        let lists = |mapping: &GenderMapping| {
+            [
+                (Gender::Androgynous, mapping.androgynous),
+                (Gender::Female, mapping.female),
+                (Gender::Male, mapping.male),
+            ]
+        };
+        let exception = self.exceptions.as_ref().and_then(|mapping| {
+            lists(mapping).iter().find_map(|&(gender, exceptions)| {
+                exceptions
+                    .iter()
+                    .any(|exception| {
+                        exception
+                            .chars()
+                            .map(fold_yo_char)
+                            .eq(name.chars().map(fold_yo_char))
+                    })
+                    .then_some((gender, Evidence::Exception))
+            })
+        });
+        exception.or_else(|| {
+            lists(&self.suffixes)
+                .iter()
+                // `max_by_key` returns the last of equal maximums, so the lists are reversed
+                .rev()
+                .flat_map(|&(gender, suffixes)| {
+                    suffixes
+                        .iter()
+                        .filter(|&suffix| ends_with(name, suffix, true))
+                        .map(move |suffix| (gender, suffix.chars().count()))
+                })
+                .max_by_key(|&(_, length)| length)
+                .map(|(gender, length)| (gender, Evidence::Suffix(length)))
+        })
+    }
+}
+
+struct GenderHeuristics {
+    lastname: GenderHeuristic,
+    firstname: GenderHeuristic,
+    middlename: GenderHeuristic,
+}
+
+#[cfg(feature = "generate-rules")]
+const GENDER: GenderHeuristics = include!(concat!(env!("OUT_DIR"), "/gender.inc"));
+#[cfg(not(feature = "generate-rules"))]
+const GENDER: GenderHeuristics = include!("generated/gender.inc");
+
+#[cfg(all(feature = "lang-uk", feature = "generate-rules"))]
+const GENDER_UK: GenderHeuristics = include!(concat!(env!("OUT_DIR"), "/gender_uk.inc"));
+#[cfg(all(feature = "lang-uk", not(feature = "generate-rules")))]
+const GENDER_UK: GenderHeuristics = include!("generated/gender_uk.inc");
+
+// Drops the leading words of a lowercase last name that the rules keep unchanged, like
+// "фон" or "тер", as they tell nothing about gender
+fn strip_particles(lastname: &str) -> &str {
+    let mut rest = lastname;
+    while let Some((word, tail)) = rest.split_once(|c: char| c.is_whitespace() || c == '-') {
+        let is_particle = RULES
+            .lastname
+            .exceptions
+            .iter()
+            .any(|rule| rule.has_tag(&RuleTag::FirstWord) && rule.fully_matches(word, true));
+        if !is_particle {
+            break;
+        }
+        rest = tail.trim_start();
+    }
+    rest
+}
+
+// Lowercase name without the Latin words and other non-Cyrillic parts at its ends, so
+// "Анна-Marie" is detected as "анна"; borrowed when the name is lowercase and composed
+fn detection_key(name: &str) -> Cow<'_, str> {
+    let key = match nfc(name) {
+        Cow::Borrowed(name) => lowercase(name),
+        Cow::Owned(name) => Cow::Owned(name.to_lowercase()),
+    };
+    let range = cyrillic_range(&key);
+    match key {
+        Cow::Borrowed(key) => Cow::Borrowed(&key[range]),
+        Cow::Owned(key) => Cow::Owned(key[range].to_owned()),
+    }
+}
+
+// Byte range of the name without the non-Cyrillic words at its ends and surrounding
+// whitespace, empty when no word is Cyrillic
+fn cyrillic_range(name: &str) -> Range<usize> {
+    let is_separator = |c: char| c.is_whitespace() || c == '-';
+    let mut key = name;
+    while let Some((word, rest)) = key.split_once(is_separator) {
+        if is_cyrillic_word(word) {
+            break;
+        }
+        key = rest;
+    }
+    while let Some((rest, word)) = key.rsplit_once(is_separator) {
+        if is_cyrillic_word(word) {
+            break;
+        }
+        key = rest;
+    }
+    if key.split(is_separator).any(is_cyrillic_word) {
+        let key = key.trim();
+        let start = key.as_ptr() as usize - name.as_ptr() as usize;
+        start..start + key.len()
+    } else {
+        0..0
+    }
+}
+
+fn detect_lastname(lastname: &str) -> Option<(Gender, Evidence)> {
+    GENDER
+        .lastname
+        .detect(strip_particles(&detection_key(lastname)))
+}
+
+/// Whether the last name heuristics know the name
+pub(crate) fn looks_like_lastname(name: &str) -> bool {
+    detect_lastname(name).is_some()
+}
+
+fn detect_firstname(firstname: &str) -> Option<(Gender, Evidence)> {
+    let key = detection_key(firstname);
+    let verdict = GENDER.firstname.detect(&key);
+    // Diminutives like "Дима" are looked up when the heuristics aren't sure
+    #[cfg(feature = "diminutives")]
+    if !matches!(verdict, Some((Gender::Male | Gender::Female, _))) {
+        if let Some(gender) = diminutive_gender(&key) {
+            return Some((gender, Evidence::Exception));
+        }
+    }
+    verdict
+}
+
+fn detect_part(part: NamePart, name: &str) -> Option<(Gender, Evidence)> {
+    match part {
+        NamePart::Last => detect_lastname(name),
+        NamePart::First => detect_firstname(name),
+        NamePart::Middle => GENDER.middlename.detect(&detection_key(name)),
+    }
+}
+
+// Gender the heuristics for the part give on their own
+fn verdict(name: Option<&str>, part: NamePart) -> Option<Gender> {
+    detect_part(part, name?).map(|(gender, _)| gender)
+}
+
+// First male or female verdict, or the first androgynous one; verdicts go from the most
+// reliable part
+fn most_reliable(verdicts: &[Option<Gender>]) -> Option<Gender> {
+    verdicts
+        .iter()
+        .flatten()
+        .find(|&&gender| gender != Gender::Androgynous)
+        .or_else(|| verdicts.iter().flatten().next())
+        .copied()
+}
+
+/// Detects gender from Ukrainian name parts with the heuristics of `gender_uk.yml`,
+/// combining them like `detect_gender_strict`
+#[cfg(feature = "lang-uk")]
+pub(crate) fn detect_gender_uk(
+    lastname: Option<&str>,
+    firstname: Option<&str>,
+    middlename: Option<&str>,
+) -> Option<Gender> {
+    let verdict = |heuristic: &GenderHeuristic, name: Option<&str>| {
+        heuristic
+            .detect(&detection_key(name?))
+            .map(|(gender, _)| gender)
+    };
+    most_reliable(&[
+        verdict(&GENDER_UK.middlename, middlename),
+        verdict(&GENDER_UK.firstname, firstname),
+        verdict(&GENDER_UK.lastname, lastname),
+    ])
+}
+
+/// Genders detected for every name part when they point to both male and female
+///
+/// A part is `None` when it wasn't given or no heuristic matched it.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct GenderConflict {
+    /// Gender of the last name
+    pub lastname: Option<Gender>,
+    /// Gender of the first name
+    pub firstname: Option<Gender>,
+    /// Gender of the middle name
+    pub middlename: Option<Gender>,
+}
+
+impl fmt::Display for GenderConflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("name parts disagree on gender")?;
+        let parts = [
+            ("last", self.lastname),
+            ("first", self.firstname),
+            ("middle", self.middlename),
+        ];
+        let known = parts
+            .iter()
+            .filter_map(|&(part, gender)| Some((part, gender?)));
+        for (i, (part, gender)) in known.enumerate() {
+            let separator = if i == 0 { ": " } else { ", " };
+            write!(f, "{}{} name is {}", separator, part, gender)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for GenderConflict {}
+
+/// Detects gender like `detect_gender`, but reports a conflict when some part points to a
+/// male gender and another to a female one
+///
+/// Such names usually have swapped or corrupted fields. Androgynous parts agree with
+/// either gender.
+///
+/// ```
+/// use petrovich::{detect_gender_checked, Gender};
+///
+/// assert_eq!(
+///     detect_gender_checked(Some("Иванова"), Some("Ольга"), None),
+///     Ok(Gender::Female)
+/// );
+/// let conflict = detect_gender_checked(Some("Иванова"), Some("Олег"), Some("Петрович"))
+///     .unwrap_err();
+/// assert_eq!(conflict.lastname, Some(Gender::Female));
+/// assert_eq!(conflict.middlename, Some(Gender::Male));
+/// ```
+pub fn detect_gender_checked(
+    lastname: Option<&str>,
+    firstname: Option<&str>,
+    middlename: Option<&str>,
+) -> Result<Gender, GenderConflict> {
+    let conflict = GenderConflict {
+        lastname: verdict(lastname, NamePart::Last),
+        firstname: verdict(firstname, NamePart::First),
+        middlename: verdict(middlename, NamePart::Middle),
+    };
+    let verdicts = [conflict.middlename, conflict.firstname, conflict.lastname];
+    let has = |gender| verdicts.contains(&Some(gender));
+    if has(Gender::Male) && has(Gender::Female) {
+        return Err(conflict);
+    }
+    Ok(most_reliable(&verdicts).unwrap_or(Gender::Unknown))
+}
+
+/// Detects gender from any of the name parts, returning `None` when no part matched
+///
+/// Parts are checked in order of reliability: middle name, first name, last name. The
+/// first part pointing to a male or female gender wins. `Gender::Androgynous` is returned
+/// only when a part explicitly matched an androgynous rule, like "Саша".
+pub fn detect_gender_strict(
+    lastname: Option<&str>,
+    firstname: Option<&str>,
+    middlename: Option<&str>,
+) -> Option<Gender> {
+    most_reliable(&[
+        verdict(middlename, NamePart::Middle),
+        verdict(firstname, NamePart::First),
+        verdict(lastname, NamePart::Last),
+    ])
+}
+
+/// Detects gender from any of the name parts, fallbacks to `Gender::Unknown`
+///
+/// See `detect_gender_strict` for how the parts are combined.
+pub fn detect_gender(
+    lastname: Option<&str>,
+    firstname: Option<&str>,
+    middlename: Option<&str>,
+) -> Gender {
+    detect_gender_strict(lastname, firstname, middlename).unwrap_or(Gender::Unknown)
+}
+
+/// Detected gender with a confidence between 0 and 1
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct GenderScore {
+    /// Detected gender, `Gender::Unknown` when nothing matched or the parts cancel out
+    pub gender: Gender,
+    /// Confidence in `gender`, 0 when nothing is known
+    pub confidence: f32,
+}
+
+// How much a single match of each part can be trusted: (exception, suffix of 3+ chars)
+const MIDDLENAME_WEIGHT: (f32, f32) = (0.99, 0.99);
+const FIRSTNAME_WEIGHT: (f32, f32) = (0.95, 0.7);
+const LASTNAME_WEIGHT: (f32, f32) = (0.9, 0.85);
+
+fn weigh(evidence: Evidence, (exception, suffix): (f32, f32)) -> f32 {
+    match evidence {
+        Evidence::Exception => exception,
+        // Shorter suffixes are weaker: one character keeps 80% of the weight
+        Evidence::Suffix(length) => suffix * (0.7 + 0.1 * length.min(3) as f32),
+    }
+}
+
+/// Detects gender from any of the name parts along with a confidence score
+///
+/// The score depends on which part matched (a middle name is near-certain, a first name
+/// suffix is weak), whether the part is a known exception and how long the matched
+/// suffix was. Agreeing parts reinforce each other, parts pointing to different genders
+/// lower the score. `Gender::Androgynous` is reported only when no part pointed to a male
+/// or female gender.
+pub fn detect_gender_scored(
+    lastname: Option<&str>,
+    firstname: Option<&str>,
+    middlename: Option<&str>,
+) -> GenderScore {
+    let parts = [
+        (middlename, NamePart::Middle, MIDDLENAME_WEIGHT),
+        (firstname, NamePart::First, FIRSTNAME_WEIGHT),
+        (lastname, NamePart::Last, LASTNAME_WEIGHT),
+    ];
+    // Probability that none of the matches for the gender is right
+    let (mut male_doubt, mut female_doubt, mut androgynous) = (1.0, 1.0, 0.0f32);
+    for &(name, part, weight) in &parts {
+        let verdict = name.and_then(|name| detect_part(part, name));
+        if let Some((gender, evidence)) = verdict {
+            let score = weigh(evidence, weight);
+            match gender {
+                Gender::Male => male_doubt *= 1.0 - score,
+                Gender::Female => female_doubt *= 1.0 - score,
+                _ => androgynous = androgynous.max(score),
+            }
+        }
+    }
+    let (male, female) = (1.0 - male_doubt, 1.0 - female_doubt);
+    let (gender, confidence) = if male > female {
+        (Gender::Male, male * (1.0 - female))
+    } else if female > male {
+        (Gender::Female, female * (1.0 - male))
+    } else if male == 0.0 && androgynous > 0.0 {
+        (Gender::Androgynous, androgynous)
+    } else {
+        (Gender::Unknown, 0.0)
+    };
+    GenderScore { gender, confidence }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_detect_nothing_for_unknown_parts() {
+        assert_eq!(detect_gender_strict(None, None, None), None);
+        assert_eq!(
+            detect_gender_strict(Some("Qwerty"), Some("Blabla"), Some("Оно")),
+            None
+        );
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_prefer_reliable_parts_when_they_disagree() {
+        assert_eq!(
+            detect_gender_strict(Some("Иванова"), Some("Олег"), Some("Петрович")),
+            Some(Gender::Male)
+        );
+        assert_eq!(
+            detect_gender_strict(Some("Иванова"), Some("Олег"), None),
+            Some(Gender::Male)
+        );
+        assert_eq!(
+            detect_gender_strict(Some("Иванова"), Some("Саша"), None),
+            Some(Gender::Female)
+        );
+        assert_eq!(
+            detect_gender_strict(Some("Осипчук"), Some("Саша"), None),
+            Some(Gender::Androgynous)
+        );
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_match_yo_as_ye() {
+        // Exception spelled "лёва", suffix spelled "ёва"
+        assert_eq!(
+            detect_gender_strict(None, Some("Лева"), None),
+            Some(Gender::Male)
+        );
+        assert_eq!(
+            detect_gender_strict(Some("Киселева"), None, None),
+            Some(Gender::Female)
+        );
+        // Suffix spelled "ев"
+        assert_eq!(
+            detect_gender_strict(Some("Пугачёв"), None, None),
+            Some(Gender::Male)
+        );
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_ignore_nobiliary_particles() {
+        // "грин" is a known androgynous last name, while the suffix "ин" is male
+        assert_eq!(
+            detect_gender_strict(Some("фон Грин"), None, None),
+            Some(Gender::Androgynous)
+        );
+        assert_eq!(
+            detect_gender_strict(Some("Ван Дер Бова"), None, None),
+            Some(Gender::Androgynous)
+        );
+        assert_eq!(
+            detect_gender_scored(Some("де Грин"), None, None),
+            detect_gender_scored(Some("Грин"), None, None)
+        );
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_prefer_longest_suffix() {
+        // "ля" is listed as female, "емеля" and "коля" as male
+        assert_eq!(
+            detect_gender_strict(None, Some("Емеля"), None),
+            Some(Gender::Male)
+        );
+        assert_eq!(
+            detect_gender_strict(None, Some("Николя"), None),
+            Some(Gender::Male)
+        );
+        assert_eq!(
+            detect_gender_strict(None, Some("Оля"), None),
+            Some(Gender::Female)
+        );
+        assert_eq!(
+            detect_gender_scored(None, Some("Емеля"), None).gender,
+            Gender::Male
+        );
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_borrow_lowercase_detection_keys() {
+        assert!(matches!(detection_key("анна"), Cow::Borrowed("анна")));
+        assert!(matches!(detection_key("анна-marie"), Cow::Borrowed("анна")));
+        assert_eq!(detection_key("Анна-Marie"), "анна");
+        assert_eq!(detection_key("СЁМА"), "сёма");
+        assert_eq!(detection_key("Smith"), "");
+        assert_eq!(detect_gender(Some("ЁЛКИНА"), None, None), Gender::Female);
+        assert_eq!(detect_gender(None, Some("СЕМЁН"), None), Gender::Male);
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_detect_parts_of_compound_names() {
+        // "Черных" tells nothing, the first part decides
+        assert_eq!(
+            detect_gender_strict(Some("Петрова-Черных"), None, None),
+            Some(Gender::Female)
+        );
+        assert_eq!(
+            detect_gender_strict(Some("Петрова-Смит"), None, None),
+            Some(Gender::Female)
+        );
+        assert_eq!(
+            detect_gender_strict(Some("Сидоров-Оглы"), None, None),
+            Some(Gender::Male)
+        );
+        assert_eq!(
+            detect_gender_strict(None, Some("Анна-Мария"), None),
+            Some(Gender::Female)
+        );
+        // A male part outvotes an androgynous one
+        assert_eq!(
+            detect_gender_strict(None, Some("Саша-Олег"), None),
+            Some(Gender::Male)
+        );
+        // Parts pointing to different genders cancel out
+        assert_eq!(
+            detect_gender_strict(Some("Иванов-Петрова"), None, None),
+            None
+        );
+        assert_eq!(
+            detect_gender_scored(Some("Петрова-Черных"), None, None),
+            detect_gender_scored(Some("Петрова"), None, None)
+        );
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_report_gender_conflicts() {
+        let conflict = detect_gender_checked(Some("Иванова"), Some("Олег"), Some("Петрович"));
+        assert_eq!(
+            conflict,
+            Err(GenderConflict {
+                lastname: Some(Gender::Female),
+                firstname: Some(Gender::Male),
+                middlename: Some(Gender::Male),
+            })
+        );
+        assert_eq!(
+            conflict.unwrap_err().to_string(),
+            "name parts disagree on gender: last name is female, first name is male, \
+             middle name is male"
+        );
+        // The priority-based detection still picks the middle name
+        assert_eq!(
+            detect_gender(Some("Иванова"), Some("Олег"), Some("Петрович")),
+            Gender::Male
+        );
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_agree_with_androgynous_parts() {
+        assert_eq!(
+            detect_gender_checked(Some("Иванов"), Some("Саша"), None),
+            Ok(Gender::Male)
+        );
+        assert_eq!(
+            detect_gender_checked(None, Some("Саша"), None),
+            Ok(Gender::Androgynous)
+        );
+        assert_eq!(
+            detect_gender_checked(Some("Blabla"), None, None),
+            Ok(Gender::Unknown)
+        );
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_ignore_latin_words() {
+        assert_eq!(
+            detect_gender_strict(None, Some("Анна-Marie"), None),
+            Some(Gender::Female)
+        );
+        assert_eq!(
+            detect_gender_strict(Some("von Грин"), None, None),
+            Some(Gender::Androgynous)
+        );
+        assert_eq!(
+            detect_gender_strict(Some("Smith"), Some("John"), None),
+            None
+        );
+        assert_eq!(
+            detect_gender_scored(Some("Мюллер-Smith"), None, None),
+            detect_gender_scored(Some("Мюллер"), None, None)
+        );
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_trust_patronymic_particles_over_first_name() {
+        assert_eq!(
+            detect_gender(None, Some("Анна"), Some("Ибрагим оглы")),
+            Gender::Male
+        );
+        assert_eq!(
+            detect_gender(None, Some("Олег"), Some("Зульфия кызы")),
+            Gender::Female
+        );
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_detect_patronymic_particles() {
+        assert_eq!(
+            detect_gender(None, Some("Саша"), Some("Ибрагим оглу")),
+            Gender::Male
+        );
+        assert_eq!(
+            detect_gender(None, Some("Саша"), Some("Зульфия-Кызы")),
+            Gender::Female
+        );
+        assert_eq!(
+            detect_gender_strict(None, None, Some("ГАСАН ГЫЗЫ")),
+            Some(Gender::Female)
+        );
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_score_reliable_parts_higher() {
+        let middlename = detect_gender_scored(None, None, Some("Петрович"));
+        assert_eq!(middlename.gender, Gender::Male);
+        assert!(middlename.confidence > 0.85);
+
+        let lastname = detect_gender_scored(Some("Иванова"), None, None);
+        assert_eq!(lastname.gender, Gender::Female);
+        assert!(lastname.confidence > 0.8);
+
+        let firstname = detect_gender_scored(None, Some("Марина"), None);
+        assert_eq!(firstname.gender, Gender::Female);
+        assert!(firstname.confidence < 0.8);
+
+        let exception = detect_gender_scored(None, Some("Лука"), None);
+        assert_eq!(exception.gender, Gender::Male);
+        assert!(exception.confidence > firstname.confidence);
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_lower_score_when_parts_disagree() {
+        let agree = detect_gender_scored(Some("Иванов"), Some("Олег"), None);
+        let disagree = detect_gender_scored(Some("Иванова"), Some("Олег"), None);
+        assert_eq!(agree.gender, Gender::Male);
+        assert!(agree.confidence > 0.8);
+        assert!(disagree.confidence < 0.8);
+
+        let certain = detect_gender_scored(Some("Иванова"), Some("Олег"), Some("Петрович"));
+        assert_eq!(certain.gender, Gender::Male);
+        assert!(certain.confidence < detect_gender_scored(None, None, Some("Петрович")).confidence);
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_score_unknown_and_androgynous() {
+        assert_eq!(
+            detect_gender_scored(None, None, None),
+            GenderScore {
+                gender: Gender::Unknown,
+                confidence: 0.0
+            }
+        );
+        let androgynous = detect_gender_scored(Some("Осипчук"), Some("Саша"), None);
+        assert_eq!(androgynous.gender, Gender::Androgynous);
+        assert!(androgynous.confidence > 0.0);
+    }
+}
@@ -0,0 +1,136 @@
+//! Full first names for diminutives like "Саша" or "Дима".
+
+use super::rules::fold_yo_char;
+use super::{nfc, Gender};
+
+// Lowercase forms with ё folded to е, the full names they belong to and their genders,
+// sorted by the form; full names are listed as forms of themselves
+#[cfg(feature = "generate-rules")]
+const DIMINUTIVES: &[(&str, &str, Gender)] = include!(concat!(env!("OUT_DIR"), "/diminutives.inc"));
+#[cfg(not(feature = "generate-rules"))]
+const DIMINUTIVES: &[(&str, &str, Gender)] = include!("generated/diminutives.inc");
+
+/// Full first name a diminutive may stand for, see `canonical_firstname`
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct CanonicalCandidate {
+    /// Full name, as in "Александр"
+    pub name: &'static str,
+    /// Gender of the full name
+    pub gender: Gender,
+}
+
+fn candidates(key: &str) -> &'static [(&'static str, &'static str, Gender)] {
+    let start = DIMINUTIVES.partition_point(|&(form, _, _)| form < key);
+    let end = start + DIMINUTIVES[start..].partition_point(|&(form, _, _)| form == key);
+    &DIMINUTIVES[start..end]
+}
+
+fn lookup_key(name: &str) -> String {
+    nfc(name.trim())
+        .to_lowercase()
+        .chars()
+        .map(fold_yo_char)
+        .collect()
+}
+
+/// Full first names the name may be a diminutive of, in alphabetical order
+///
+/// Full names give themselves. Forms shared by male and female names, like "Саша", give
+/// both unless the gender hint is male or female. An empty list means the name is not
+/// in the dictionary.
+///
+/// ```
+/// use petrovich::{canonical_firstname, Gender};
+///
+/// assert_eq!(canonical_firstname("Лёша", None)[0].name, "Алексей");
+/// let names: Vec<_> = canonical_firstname("Саша", None)
+///     .into_iter()
+///     .map(|candidate| candidate.name)
+///     .collect();
+/// assert_eq!(names, ["Александр", "Александра"]);
+/// assert_eq!(
+///     canonical_firstname("Саша", Some(Gender::Female))[0].name,
+///     "Александра"
+/// );
+/// ```
+pub fn canonical_firstname(name: &str, gender_hint: Option<Gender>) -> Vec<CanonicalCandidate> {
+    let candidates: Vec<_> = candidates(&lookup_key(name))
+        .iter()
+        .map(|&(_, name, gender)| CanonicalCandidate { name, gender })
+        .collect();
+    match gender_hint {
+        Some(hint @ (Gender::Male | Gender::Female))
+            if candidates.iter().any(|candidate| candidate.gender == hint) =>
+        {
+            candidates
+                .into_iter()
+                .filter(|candidate| candidate.gender == hint)
+                .collect()
+        }
+        _ => candidates,
+    }
+}
+
+/// Gender of the full names the lowercase name is a diminutive of, when they agree
+pub(crate) fn diminutive_gender(key: &str) -> Option<Gender> {
+    let key: String = key.chars().map(fold_yo_char).collect();
+    let (first, rest) = candidates(&key).split_first()?;
+    let gender = first.2;
+    rest.iter()
+        .all(|&(_, _, other)| other == gender)
+        .then_some(gender)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(name: &str, gender_hint: Option<Gender>) -> Vec<&'static str> {
+        canonical_firstname(name, gender_hint)
+            .into_iter()
+            .map(|candidate| candidate.name)
+            .collect()
+    }
+
+    #[test]
+    fn should_be_sorted() {
+        assert!(DIMINUTIVES
+            .windows(2)
+            .all(|pair| (pair[0].0, pair[0].1) < (pair[1].0, pair[1].1)));
+    }
+
+    #[test]
+    fn should_find_full_names() {
+        assert_eq!(names("Лёша", None), ["Алексей"]);
+        assert_eq!(names("леша", None), ["Алексей"]);
+        assert_eq!(names("ДИМА", None), ["Дмитрий"]);
+        assert_eq!(names("Наташа", None), ["Наталья"]);
+        assert_eq!(names("Наталья", None), ["Наталья"]);
+        assert_eq!(names("Слава", None).len(), 6);
+        assert!(names("Иваныч", None).is_empty());
+    }
+
+    #[test]
+    fn should_resolve_unisex_forms_by_hint() {
+        assert_eq!(names("Женя", None), ["Евгений", "Евгения"]);
+        assert_eq!(names("Женя", Some(Gender::Male)), ["Евгений"]);
+        assert_eq!(names("Женя", Some(Gender::Female)), ["Евгения"]);
+        assert_eq!(names("Женя", Some(Gender::Androgynous)).len(), 2);
+        // A hint contradicting every candidate is ignored
+        assert_eq!(names("Дима", Some(Gender::Female)), ["Дмитрий"]);
+    }
+
+    #[test]
+    fn should_give_gender_of_diminutives() {
+        assert_eq!(diminutive_gender("дима"), Some(Gender::Male));
+        assert_eq!(diminutive_gender("лёша"), Some(Gender::Male));
+        assert_eq!(diminutive_gender("саша"), None);
+        assert_eq!(diminutive_gender("иваныч"), None);
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_detect_gender_of_diminutives() {
+        assert_eq!(crate::detect_gender(None, Some("Дима"), None), Gender::Male);
+    }
+}
@@ -0,0 +1,141 @@
+//! Inflected names formatted lazily.
+
+use std::fmt;
+
+use super::{inflect_into, Case, Gender, NamePart};
+
+/// Name inflected as it is formatted, see `inflect_display`
+///
+/// Nothing is inflected until the value is displayed, and the kept part of the name and
+/// the ending are written straight into the formatter. Width and alignment flags are
+/// ignored.
+#[derive(Clone, Copy, Debug)]
+pub struct Inflected<'a> {
+    part: NamePart,
+    gender: Gender,
+    name: &'a str,
+    case: Case,
+}
+
+impl<'a> fmt::Display for Inflected<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        inflect_into(self.part, self.gender, self.name, self.case, f)
+    }
+}
+
+// Checks written pieces against the start of a string, failing at the first mismatch
+struct Compare<'s> {
+    rest: &'s str,
+}
+
+impl<'s> fmt::Write for Compare<'s> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.rest = self.rest.strip_prefix(s).ok_or(fmt::Error)?;
+        Ok(())
+    }
+}
+
+/// Compares the inflected name without allocating it
+impl<'a> PartialEq<str> for Inflected<'a> {
+    fn eq(&self, other: &str) -> bool {
+        let mut compare = Compare { rest: other };
+        inflect_into(self.part, self.gender, self.name, self.case, &mut compare).is_ok()
+            && compare.rest.is_empty()
+    }
+}
+
+impl<'a, 'b> PartialEq<&'b str> for Inflected<'a> {
+    fn eq(&self, other: &&'b str) -> bool {
+        *self == **other
+    }
+}
+
+/// Name inflected using the rules for the given part once it's formatted, without
+/// allocating an intermediate `String`
+///
+/// ```
+/// # #[cfg(feature = "lastname-rules")]
+/// # {
+/// use petrovich::{inflect_display, Case, Gender, NamePart};
+///
+/// let name = inflect_display(NamePart::Last, Gender::Male, "Иванов", Case::Genitive);
+/// assert_eq!(format!("Заявление от {}", name), "Заявление от Иванова");
+/// assert_eq!(name, "Иванова");
+/// # }
+/// ```
+pub fn inflect_display(part: NamePart, gender: Gender, name: &str, case: Case) -> Inflected<'_> {
+    Inflected {
+        part,
+        gender,
+        name,
+        case,
+    }
+}
+
+/// First name inflected once it's formatted, see `inflect_display`
+#[cfg(feature = "firstname-rules")]
+pub fn firstname_display(gender: Gender, name: &str, case: Case) -> Inflected<'_> {
+    inflect_display(NamePart::First, gender, name, case)
+}
+
+/// Last name inflected once it's formatted, see `inflect_display`
+#[cfg(feature = "lastname-rules")]
+pub fn lastname_display(gender: Gender, name: &str, case: Case) -> Inflected<'_> {
+    inflect_display(NamePart::Last, gender, name, case)
+}
+
+/// Middle name inflected once it's formatted, see `inflect_display`
+#[cfg(feature = "middlename-rules")]
+pub fn middlename_display(gender: Gender, name: &str, case: Case) -> Inflected<'_> {
+    inflect_display(NamePart::Middle, gender, name, case)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inflect;
+
+    #[cfg(all(
+        feature = "firstname-rules",
+        feature = "lastname-rules",
+        feature = "middlename-rules"
+    ))]
+    #[test]
+    fn should_display_inflected_name() {
+        let name = lastname_display(Gender::Male, "Иванов", Case::Genitive);
+        assert_eq!(format!("Заявление от {}", name), "Заявление от Иванова");
+        let name = firstname_display(Gender::Female, "Анна-Мария", Case::Dative);
+        assert_eq!(name.to_string(), "Анне-Марии");
+        let name = middlename_display(Gender::Male, "Сергеевич", Case::Instrumental);
+        assert_eq!(name.to_string(), "Сергеевичем");
+    }
+
+    #[test]
+    fn should_match_inflect() {
+        for &(part, gender, name) in &[
+            (NamePart::Last, Gender::Female, "Станкевич"),
+            (NamePart::Last, Gender::Male, "ПЕТРОВ-ВОДКИН"),
+            (NamePart::First, Gender::Male, "Пётр"),
+        ] {
+            for case in Case::iter() {
+                let expected = inflect(part, gender, name, case);
+                assert_eq!(
+                    inflect_display(part, gender, name, case).to_string(),
+                    expected
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_compare_with_strings() {
+        let name = lastname_display(Gender::Male, "Иванов", Case::Dative);
+        assert_eq!(name, "Иванову");
+        assert!(name == *"Иванову");
+        assert!(name != "Иванов");
+        assert!(name != "Иванову ");
+        assert!(name != "Иванова");
+        assert!(name != "");
+    }
+}
@@ -0,0 +1,77 @@
+//! Errors reported by the fallible inflection API.
+
+use std::fmt;
+
+/// Error returned when a name can't be inflected
+#[derive(Eq, PartialEq, Clone, Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Name contains a character that is not allowed, e.g. a digit or an underscore.
+    /// `position` is the byte offset of the character in the name
+    InvalidCharacter { character: char, position: usize },
+    /// Grammeme tag has no counterpart in this crate
+    UnsupportedTag { tag: String },
+    /// String doesn't name a grammatical case
+    UnknownCase(String),
+    /// String doesn't name a gender or names several of them
+    UnknownGender(String),
+    /// String doesn't name a part of a full name
+    UnknownNamePart(String),
+    /// Integer doesn't correspond to any case
+    CaseIndexOutOfRange(usize),
+    /// Rule data failed to parse or validate; `section` names the offending rule
+    InvalidRules { section: String, message: String },
+    /// Name is empty or consists of whitespace only
+    EmptyName,
+    /// Name has no Cyrillic letters, so the rules can't apply to it
+    NonCyrillicInput(String),
+    /// No rule matched any segment of the name
+    NoRuleMatched(String),
+    /// Full name has fewer than one or more than three whitespace-separated parts
+    UnexpectedPartCount(usize),
+    /// Parts of a full name can be assigned in several ways with equal likelihood
+    AmbiguousName(String),
+    /// Name template is malformed; holds the description of the problem
+    InvalidFormat(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidCharacter {
+                character,
+                position,
+            } => write!(
+                f,
+                "invalid character {:?} at position {} in name",
+                character, position
+            ),
+            Error::UnsupportedTag { tag } => write!(f, "unsupported grammeme tag {:?}", tag),
+            Error::UnknownCase(name) => write!(f, "unknown grammatical case {:?}", name),
+            Error::UnknownGender(name) => write!(f, "unknown gender {:?}", name),
+            Error::UnknownNamePart(name) => write!(f, "unknown name part {:?}", name),
+            Error::CaseIndexOutOfRange(index) => write!(
+                f,
+                "case index {} is out of range 0..{}",
+                index,
+                crate::Case::ALL.len()
+            ),
+            Error::InvalidRules { section, message } if section.is_empty() => {
+                write!(f, "invalid rules: {}", message)
+            }
+            Error::InvalidRules { section, message } => {
+                write!(f, "invalid rules in {}: {}", section, message)
+            }
+            Error::EmptyName => f.write_str("name is empty"),
+            Error::NonCyrillicInput(name) => write!(f, "name {:?} has no Cyrillic letters", name),
+            Error::NoRuleMatched(name) => write!(f, "no rule matched name {:?}", name),
+            Error::UnexpectedPartCount(count) => {
+                write!(f, "expected 1 to 3 name parts, found {}", count)
+            }
+            Error::AmbiguousName(name) => write!(f, "can't tell the parts of {:?} apart", name),
+            Error::InvalidFormat(message) => write!(f, "invalid name format: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,163 @@
+//! Explanations of which rules produced an inflection.
+
+use std::ptr;
+
+use super::rules::{match_key, NoCustomTags, RULES};
+use super::{match_name, nfc, Case, Gender, InflectOptions, MatchKind, NamePart, ENDING_RESERVE};
+
+/// Rule applied to a single segment of a name
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct RuleApplication {
+    /// Whether the segment matched an exception or a suffix rule
+    pub kind: MatchKind,
+    /// Test string of the rule that matched, in lowercase
+    pub test: String,
+    /// Gender the rule is defined for
+    pub gender: Gender,
+    /// Position of the rule in the exception or suffix list of the part, telling apart
+    /// rules with the same test. Of several suffix rules the one with the longest match
+    /// wins, then the one for the exact gender, then the one listed first
+    pub index: usize,
+    /// Number of characters removed from the end of the segment, `None` when the rule
+    /// keeps the segment as is in this case
+    pub removed: Option<usize>,
+    /// Ending appended after removing characters
+    pub ending: String,
+}
+
+/// How a single segment of a name was inflected
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct SegmentExplanation {
+    /// Index of the segment within the name
+    pub index: usize,
+    /// Segment as it appears in the input
+    pub segment: String,
+    /// Applied rule, `None` when the segment was passed through
+    pub rule: Option<RuleApplication>,
+}
+
+/// How a name was inflected, segment by segment
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Explanation {
+    /// Inflected name, the same as returned by the inflection functions
+    pub result: String,
+    /// Explanations for every word and hyphen-separated part
+    pub segments: Vec<SegmentExplanation>,
+}
+
+/// Explains how a name is inflected with the rules for the given part
+pub fn explain(part: NamePart, gender: Gender, name: &str, case: Case) -> Explanation {
+    let name = &*nfc(name);
+    let rule_list = RULES.list(part);
+    let segments = match_name(
+        gender,
+        name,
+        rule_list,
+        &NoCustomTags,
+        &InflectOptions::DEFAULT,
+    )
+    .unwrap_or_default();
+    let mut result = String::with_capacity(name.len() + ENDING_RESERVE);
+    for segment in &segments {
+        segment.inflect_into(case, &mut result);
+    }
+    let segments = segments
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| SegmentExplanation {
+            index,
+            segment: segment.segment.to_owned(),
+            rule: segment.rule.map(|(kind, rule)| {
+                let key = match_key(segment.segment);
+                let fold_yo = InflectOptions::DEFAULT.normalize_yo;
+                let (test, list) = match kind {
+                    MatchKind::Exception => {
+                        (rule.matched_exception(&key, fold_yo), &rule_list.exceptions)
+                    }
+                    MatchKind::Suffix => (rule.matched_suffix(&key, fold_yo), &rule_list.suffixes),
+                };
+                let modifier = rule.modifier(case);
+                RuleApplication {
+                    kind,
+                    test: test.unwrap_or_default().to_owned(),
+                    gender: rule.gender,
+                    index: list
+                        .iter()
+                        .position(|listed| ptr::eq(listed, rule))
+                        .unwrap_or_default(),
+                    removed: modifier.map(|(skip, _)| skip),
+                    ending: modifier.map_or_else(String::new, |(_, ending)| ending.to_owned()),
+                }
+            }),
+        })
+        .collect();
+    Explanation { result, segments }
+}
+
+/// Explains how a first name is inflected
+#[cfg(feature = "firstname-rules")]
+pub fn explain_firstname(gender: Gender, name: &str, case: Case) -> Explanation {
+    explain(NamePart::First, gender, name, case)
+}
+
+/// Explains how a last name is inflected
+#[cfg(feature = "lastname-rules")]
+pub fn explain_lastname(gender: Gender, name: &str, case: Case) -> Explanation {
+    explain(NamePart::Last, gender, name, case)
+}
+
+/// Explains how a middle name is inflected
+#[cfg(feature = "middlename-rules")]
+pub fn explain_middlename(gender: Gender, name: &str, case: Case) -> Explanation {
+    explain(NamePart::Middle, gender, name, case)
+}
+
+#[cfg(all(test, feature = "lastname-rules"))]
+mod tests {
+    use super::*;
+    use crate::lastname;
+
+    #[test]
+    fn should_explain_suffix_and_exception() {
+        let explanation = explain_lastname(Gender::Male, "Кравец-Цой", Case::Dative);
+        assert_eq!(
+            explanation.result,
+            lastname(Gender::Male, "Кравец-Цой", Case::Dative)
+        );
+        let suffix = explanation.segments[0].rule.as_ref().unwrap();
+        assert_eq!(suffix.kind, MatchKind::Suffix);
+        assert!("кравец".ends_with(&suffix.test));
+        assert_eq!(suffix.removed, Some(2));
+        assert_eq!(suffix.ending, "цу");
+
+        let exception = explanation.segments[1].rule.as_ref().unwrap();
+        assert_eq!(exception.kind, MatchKind::Exception);
+        assert_eq!(exception.test, "цой");
+        assert_eq!(explanation.segments[1].index, 1);
+    }
+
+    #[cfg(feature = "firstname-rules")]
+    #[test]
+    fn should_report_winning_rule() {
+        let explanation = explain_firstname(Gender::Male, "Серж", Case::Instrumental);
+        let rule = explanation.segments[0].rule.as_ref().unwrap();
+        assert_eq!(rule.test, "ж");
+        assert_eq!(rule.ending, "ем");
+        let list = crate::Rules::embedded().list(NamePart::First);
+        assert!(list.suffixes()[rule.index].tests().any(|test| test == "ш"));
+        // Rules written with ё are reported for names spelled with е
+        let explanation = explain_lastname(Gender::Male, "Мотылек", Case::Dative);
+        assert_eq!(explanation.result, "Мотыльку");
+        assert_eq!(explanation.segments[0].rule.as_ref().unwrap().test, "лёк");
+    }
+
+    #[cfg(feature = "firstname-rules")]
+    #[test]
+    fn should_explain_pass_through() {
+        let explanation = explain_firstname(Gender::Male, "Blabla", Case::Dative);
+        assert_eq!(explanation.result, "Blabla");
+        assert_eq!(explanation.segments[0].rule, None);
+        let explanation = explain_lastname(Gender::Female, "Станкевич", Case::Dative);
+        assert_eq!(explanation.segments[0].rule.as_ref().unwrap().removed, None);
+    }
+}
@@ -0,0 +1,100 @@
+//! Extension trait for inflecting string slices in place of the free functions.
+//!
+//! Not re-exported from the crate root, so `use petrovich::*` leaves `str` untouched;
+//! import it explicitly with `use petrovich::ext::InflectExt`.
+
+use super::{inflect, Case, Gender, NamePart};
+
+/// Inflection methods on strings, mirroring the free functions
+///
+/// ```
+/// # #[cfg(all(feature = "firstname-rules", feature = "lastname-rules"))]
+/// # {
+/// use petrovich::ext::InflectExt;
+/// use petrovich::{Case, Gender};
+///
+/// assert_eq!("Иванов".inflect_lastname(Gender::Male, Case::Dative), "Иванову");
+/// assert_eq!("Анна".to_genitive_firstname(Gender::Female), "Анны");
+/// # }
+/// ```
+pub trait InflectExt {
+    /// Inflects the string using the rules for the given part
+    fn inflect_as(&self, part: NamePart, gender: Gender, case: Case) -> String;
+
+    /// Inflects the string as a first name
+    fn inflect_firstname(&self, gender: Gender, case: Case) -> String {
+        self.inflect_as(NamePart::First, gender, case)
+    }
+
+    /// Inflects the string as a last name
+    fn inflect_lastname(&self, gender: Gender, case: Case) -> String {
+        self.inflect_as(NamePart::Last, gender, case)
+    }
+
+    /// Inflects the string as a middle name
+    fn inflect_middlename(&self, gender: Gender, case: Case) -> String {
+        self.inflect_as(NamePart::Middle, gender, case)
+    }
+
+    /// First name in the genitive case
+    fn to_genitive_firstname(&self, gender: Gender) -> String {
+        self.inflect_firstname(gender, Case::Genitive)
+    }
+
+    /// Last name in the genitive case
+    fn to_genitive_lastname(&self, gender: Gender) -> String {
+        self.inflect_lastname(gender, Case::Genitive)
+    }
+
+    /// Middle name in the genitive case
+    fn to_genitive_middlename(&self, gender: Gender) -> String {
+        self.inflect_middlename(gender, Case::Genitive)
+    }
+
+    /// First name in the dative case
+    fn to_dative_firstname(&self, gender: Gender) -> String {
+        self.inflect_firstname(gender, Case::Dative)
+    }
+
+    /// Last name in the dative case
+    fn to_dative_lastname(&self, gender: Gender) -> String {
+        self.inflect_lastname(gender, Case::Dative)
+    }
+
+    /// Middle name in the dative case
+    fn to_dative_middlename(&self, gender: Gender) -> String {
+        self.inflect_middlename(gender, Case::Dative)
+    }
+}
+
+impl InflectExt for str {
+    fn inflect_as(&self, part: NamePart, gender: Gender, case: Case) -> String {
+        inflect(part, gender, self, case)
+    }
+}
+
+impl InflectExt for String {
+    fn inflect_as(&self, part: NamePart, gender: Gender, case: Case) -> String {
+        inflect(part, gender, self, case)
+    }
+}
+
+#[cfg(all(test, feature = "lastname-rules", feature = "middlename-rules"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_inflect_strings() {
+        assert_eq!(
+            "Иванов".inflect_lastname(Gender::Male, Case::Instrumental),
+            "Ивановым"
+        );
+        assert_eq!("Иванова".to_dative_lastname(Gender::Female), "Ивановой");
+        let name = String::from("Сергеевич");
+        assert_eq!(name.to_genitive_middlename(Gender::Male), "Сергеевича");
+        assert_eq!(
+            name.inflect_as(NamePart::Middle, Gender::Male, Case::Nominative),
+            name
+        );
+    }
+}
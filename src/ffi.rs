@@ -0,0 +1,429 @@
+//! C interface for linking the crate as a shared library.
+//!
+//! Build it with `cargo rustc --release --features ffi --crate-type cdylib`; the matching
+//! header is `include/petrovich.h`, generated from this module with `cbindgen`.
+//!
+//! Genders and cases are passed as the integer codes below. Every function
+//! returns `PETROVICH_OK` or a negative error code and never unwinds into the caller.
+//! Strings are NUL-terminated UTF-8.
+
+// The helpers are shared by the inflection functions, which need at least one rule set
+#![cfg_attr(
+    not(any(
+        feature = "firstname-rules",
+        feature = "lastname-rules",
+        feature = "middlename-rules"
+    )),
+    allow(dead_code)
+)]
+
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::panic::{self, UnwindSafe};
+use std::ptr;
+
+use super::{Case, Gender, NamePart};
+
+/// Call succeeded
+pub const PETROVICH_OK: c_int = 0;
+/// Required pointer argument is null
+pub const PETROVICH_ERROR_NULL_POINTER: c_int = -1;
+/// Name is not valid UTF-8
+pub const PETROVICH_ERROR_INVALID_UTF8: c_int = -2;
+/// Gender code is out of range
+pub const PETROVICH_ERROR_INVALID_GENDER: c_int = -3;
+/// Case code is out of range
+pub const PETROVICH_ERROR_INVALID_CASE: c_int = -4;
+/// Output buffer can't hold the result and its terminating NUL
+pub const PETROVICH_ERROR_BUFFER_TOO_SMALL: c_int = -5;
+/// Name contains a NUL character, so the result can't be returned as a C string
+pub const PETROVICH_ERROR_INTERIOR_NUL: c_int = -6;
+/// Inflection panicked; this is a bug in the crate
+pub const PETROVICH_ERROR_INTERNAL: c_int = -7;
+
+/// `Gender::Male`
+pub const PETROVICH_GENDER_MALE: c_int = 0;
+/// `Gender::Female`
+pub const PETROVICH_GENDER_FEMALE: c_int = 1;
+/// `Gender::Androgynous`
+pub const PETROVICH_GENDER_ANDROGYNOUS: c_int = 2;
+/// `Gender::Unknown`
+pub const PETROVICH_GENDER_UNKNOWN: c_int = 3;
+
+/// `Case::Nominative`
+pub const PETROVICH_CASE_NOMINATIVE: c_int = 0;
+/// `Case::Genitive`
+pub const PETROVICH_CASE_GENITIVE: c_int = 1;
+/// `Case::Dative`
+pub const PETROVICH_CASE_DATIVE: c_int = 2;
+/// `Case::Accusative`
+pub const PETROVICH_CASE_ACCUSATIVE: c_int = 3;
+/// `Case::Instrumental`
+pub const PETROVICH_CASE_INSTRUMENTAL: c_int = 4;
+/// `Case::Prepositional`
+pub const PETROVICH_CASE_PREPOSITIONAL: c_int = 5;
+
+fn gender_from_code(code: c_int) -> Result<Gender, c_int> {
+    match code {
+        PETROVICH_GENDER_MALE => Ok(Gender::Male),
+        PETROVICH_GENDER_FEMALE => Ok(Gender::Female),
+        PETROVICH_GENDER_ANDROGYNOUS => Ok(Gender::Androgynous),
+        PETROVICH_GENDER_UNKNOWN => Ok(Gender::Unknown),
+        _ => Err(PETROVICH_ERROR_INVALID_GENDER),
+    }
+}
+
+#[cfg(feature = "gender-detection")]
+fn gender_code(gender: Gender) -> c_int {
+    match gender {
+        Gender::Male => PETROVICH_GENDER_MALE,
+        Gender::Female => PETROVICH_GENDER_FEMALE,
+        Gender::Androgynous => PETROVICH_GENDER_ANDROGYNOUS,
+        Gender::Unknown => PETROVICH_GENDER_UNKNOWN,
+    }
+}
+
+fn case_from_code(code: c_int) -> Result<Case, c_int> {
+    match code {
+        PETROVICH_CASE_NOMINATIVE => Ok(Case::Nominative),
+        PETROVICH_CASE_GENITIVE => Ok(Case::Genitive),
+        PETROVICH_CASE_DATIVE => Ok(Case::Dative),
+        PETROVICH_CASE_ACCUSATIVE => Ok(Case::Accusative),
+        PETROVICH_CASE_INSTRUMENTAL => Ok(Case::Instrumental),
+        PETROVICH_CASE_PREPOSITIONAL => Ok(Case::Prepositional),
+        _ => Err(PETROVICH_ERROR_INVALID_CASE),
+    }
+}
+
+// Runs `f` and turns its error or a panic into the return code
+fn guard(f: impl FnOnce() -> Result<c_int, c_int> + UnwindSafe) -> c_int {
+    match panic::catch_unwind(f) {
+        Ok(Ok(code)) | Ok(Err(code)) => code,
+        Err(_) => PETROVICH_ERROR_INTERNAL,
+    }
+}
+
+// Reads a string argument; null stands for a missing one
+unsafe fn optional_str<'a>(s: *const c_char) -> Result<Option<&'a str>, c_int> {
+    if s.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map(Some)
+        .map_err(|_| PETROVICH_ERROR_INVALID_UTF8)
+}
+
+unsafe fn required_str<'a>(s: *const c_char) -> Result<&'a str, c_int> {
+    optional_str(s)?.ok_or(PETROVICH_ERROR_NULL_POINTER)
+}
+
+unsafe fn inflect_c(
+    part: NamePart,
+    gender: c_int,
+    name: *const c_char,
+    case: c_int,
+) -> Result<String, c_int> {
+    let gender = gender_from_code(gender)?;
+    let case = case_from_code(case)?;
+    Ok(super::inflect(part, gender, required_str(name)?, case))
+}
+
+// Copies the result with a terminating NUL into the caller's buffer
+unsafe fn write_to_buffer(result: &str, out: *mut c_char, out_len: usize) -> Result<c_int, c_int> {
+    if out.is_null() {
+        return Err(PETROVICH_ERROR_NULL_POINTER);
+    }
+    if result.len() >= out_len {
+        return Err(PETROVICH_ERROR_BUFFER_TOO_SMALL);
+    }
+    ptr::copy_nonoverlapping(result.as_ptr().cast::<c_char>(), out, result.len());
+    *out.add(result.len()) = 0;
+    Ok(PETROVICH_OK)
+}
+
+// Hands the result over to the caller, who releases it with `petrovich_free`
+unsafe fn write_allocated(result: String, out: *mut *mut c_char) -> Result<c_int, c_int> {
+    if out.is_null() {
+        return Err(PETROVICH_ERROR_NULL_POINTER);
+    }
+    let result = CString::new(result).map_err(|_| PETROVICH_ERROR_INTERIOR_NUL)?;
+    *out = result.into_raw();
+    Ok(PETROVICH_OK)
+}
+
+/// Inflects first name into the buffer `out` of `out_len` bytes
+///
+/// # Safety
+///
+/// `name` must be a NUL-terminated string and `out` must be valid for writes of `out_len`
+/// bytes.
+#[cfg(feature = "firstname-rules")]
+#[no_mangle]
+pub unsafe extern "C" fn petrovich_firstname(
+    gender: c_int,
+    name: *const c_char,
+    case: c_int,
+    out: *mut c_char,
+    out_len: usize,
+) -> c_int {
+    guard(|| {
+        write_to_buffer(
+            &inflect_c(NamePart::First, gender, name, case)?,
+            out,
+            out_len,
+        )
+    })
+}
+
+/// Inflects first name into a new string stored in `*out`, see `petrovich_firstname`
+///
+/// The string must be released with `petrovich_free`. `*out` is left untouched on error.
+///
+/// # Safety
+///
+/// `name` must be a NUL-terminated string and `out` must be valid for writes.
+#[cfg(feature = "firstname-rules")]
+#[no_mangle]
+pub unsafe extern "C" fn petrovich_firstname_alloc(
+    gender: c_int,
+    name: *const c_char,
+    case: c_int,
+    out: *mut *mut c_char,
+) -> c_int {
+    guard(|| write_allocated(inflect_c(NamePart::First, gender, name, case)?, out))
+}
+
+/// Inflects last name into the buffer `out` of `out_len` bytes
+///
+/// # Safety
+///
+/// `name` must be a NUL-terminated string and `out` must be valid for writes of `out_len`
+/// bytes.
+#[cfg(feature = "lastname-rules")]
+#[no_mangle]
+pub unsafe extern "C" fn petrovich_lastname(
+    gender: c_int,
+    name: *const c_char,
+    case: c_int,
+    out: *mut c_char,
+    out_len: usize,
+) -> c_int {
+    guard(|| {
+        write_to_buffer(
+            &inflect_c(NamePart::Last, gender, name, case)?,
+            out,
+            out_len,
+        )
+    })
+}
+
+/// Inflects last name into a new string stored in `*out`, see `petrovich_lastname`
+///
+/// The string must be released with `petrovich_free`. `*out` is left untouched on error.
+///
+/// # Safety
+///
+/// `name` must be a NUL-terminated string and `out` must be valid for writes.
+#[cfg(feature = "lastname-rules")]
+#[no_mangle]
+pub unsafe extern "C" fn petrovich_lastname_alloc(
+    gender: c_int,
+    name: *const c_char,
+    case: c_int,
+    out: *mut *mut c_char,
+) -> c_int {
+    guard(|| write_allocated(inflect_c(NamePart::Last, gender, name, case)?, out))
+}
+
+/// Inflects middle name into the buffer `out` of `out_len` bytes
+///
+/// # Safety
+///
+/// `name` must be a NUL-terminated string and `out` must be valid for writes of `out_len`
+/// bytes.
+#[cfg(feature = "middlename-rules")]
+#[no_mangle]
+pub unsafe extern "C" fn petrovich_middlename(
+    gender: c_int,
+    name: *const c_char,
+    case: c_int,
+    out: *mut c_char,
+    out_len: usize,
+) -> c_int {
+    guard(|| {
+        write_to_buffer(
+            &inflect_c(NamePart::Middle, gender, name, case)?,
+            out,
+            out_len,
+        )
+    })
+}
+
+/// Inflects middle name into a new string stored in `*out`, see `petrovich_middlename`
+///
+/// The string must be released with `petrovich_free`. `*out` is left untouched on error.
+///
+/// # Safety
+///
+/// `name` must be a NUL-terminated string and `out` must be valid for writes.
+#[cfg(feature = "middlename-rules")]
+#[no_mangle]
+pub unsafe extern "C" fn petrovich_middlename_alloc(
+    gender: c_int,
+    name: *const c_char,
+    case: c_int,
+    out: *mut *mut c_char,
+) -> c_int {
+    guard(|| write_allocated(inflect_c(NamePart::Middle, gender, name, case)?, out))
+}
+
+/// Detects gender from the parts given and stores its code in `*out`
+///
+/// Missing parts are passed as null.
+///
+/// # Safety
+///
+/// Every part must be null or a NUL-terminated string, and `out` must be valid for
+/// writes.
+#[cfg(feature = "gender-detection")]
+#[no_mangle]
+pub unsafe extern "C" fn petrovich_detect_gender(
+    lastname: *const c_char,
+    firstname: *const c_char,
+    middlename: *const c_char,
+    out: *mut c_int,
+) -> c_int {
+    guard(|| {
+        if out.is_null() {
+            return Err(PETROVICH_ERROR_NULL_POINTER);
+        }
+        let gender = super::detect_gender(
+            optional_str(lastname)?,
+            optional_str(firstname)?,
+            optional_str(middlename)?,
+        );
+        *out = gender_code(gender);
+        Ok(PETROVICH_OK)
+    })
+}
+
+/// Releases a string returned by one of the `*_alloc` functions; null is ignored
+///
+/// # Safety
+///
+/// `s` must be null or come from a `*_alloc` function and not be released already.
+#[no_mangle]
+pub unsafe extern "C" fn petrovich_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn c(s: &str) -> CString {
+        CString::new(s).unwrap()
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_inflect_into_buffer() {
+        let name = c("Иванов");
+        let mut out = [0 as c_char; 32];
+        let code = unsafe {
+            petrovich_lastname(
+                PETROVICH_GENDER_MALE,
+                name.as_ptr(),
+                PETROVICH_CASE_DATIVE,
+                out.as_mut_ptr(),
+                out.len(),
+            )
+        };
+        assert_eq!(code, PETROVICH_OK);
+        let result = unsafe { CStr::from_ptr(out.as_ptr()) };
+        assert_eq!(result.to_str(), Ok("Иванову"));
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_report_small_buffer() {
+        let name = c("Иванов");
+        // "Иванову" takes 14 bytes, so 14 leave no room for the NUL
+        let mut out = [0 as c_char; 14];
+        let code = unsafe {
+            petrovich_lastname(
+                PETROVICH_GENDER_MALE,
+                name.as_ptr(),
+                PETROVICH_CASE_DATIVE,
+                out.as_mut_ptr(),
+                out.len(),
+            )
+        };
+        assert_eq!(code, PETROVICH_ERROR_BUFFER_TOO_SMALL);
+    }
+
+    #[cfg(feature = "firstname-rules")]
+    #[test]
+    fn should_report_bad_arguments() {
+        let name = c("Анна");
+        let invalid = b"\xd0\0";
+        let mut out = [0 as c_char; 32];
+        let mut call = |gender, name, case| unsafe {
+            petrovich_firstname(gender, name, case, out.as_mut_ptr(), 32)
+        };
+        assert_eq!(
+            call(PETROVICH_GENDER_FEMALE, invalid.as_ptr().cast(), 0),
+            PETROVICH_ERROR_INVALID_UTF8
+        );
+        assert_eq!(
+            call(PETROVICH_GENDER_FEMALE, ptr::null(), 0),
+            PETROVICH_ERROR_NULL_POINTER
+        );
+        assert_eq!(call(4, name.as_ptr(), 0), PETROVICH_ERROR_INVALID_GENDER);
+        assert_eq!(
+            call(PETROVICH_GENDER_FEMALE, name.as_ptr(), 6),
+            PETROVICH_ERROR_INVALID_CASE
+        );
+    }
+
+    #[cfg(feature = "middlename-rules")]
+    #[test]
+    fn should_allocate_result() {
+        let name = c("Сергеевич");
+        let mut out = ptr::null_mut();
+        let code = unsafe {
+            petrovich_middlename_alloc(
+                PETROVICH_GENDER_MALE,
+                name.as_ptr(),
+                PETROVICH_CASE_INSTRUMENTAL,
+                &mut out,
+            )
+        };
+        assert_eq!(code, PETROVICH_OK);
+        let result = unsafe { CStr::from_ptr(out) }.to_str().unwrap().to_owned();
+        unsafe { petrovich_free(out) };
+        assert_eq!(result, "Сергеевичем");
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_detect_gender() {
+        let middlename = c("Петровна");
+        let mut gender = -1;
+        let code = unsafe {
+            petrovich_detect_gender(ptr::null(), ptr::null(), middlename.as_ptr(), &mut gender)
+        };
+        assert_eq!(code, PETROVICH_OK);
+        assert_eq!(gender, PETROVICH_GENDER_FEMALE);
+    }
+
+    #[test]
+    fn should_match_case_order() {
+        for (code, &case) in Case::ALL.iter().enumerate() {
+            assert_eq!(case_from_code(code as c_int), Ok(case));
+        }
+    }
+}
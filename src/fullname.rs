@@ -0,0 +1,418 @@
+//! Inflection of a full name made of several parts.
+
+use std::fmt;
+
+#[cfg(feature = "gender-detection")]
+use super::detect::looks_like_lastname;
+#[cfg(feature = "gender-detection")]
+use super::normalize::PATRONYMIC_PARTICLES;
+#[cfg(feature = "gender-detection")]
+use super::{detect_gender, Error};
+use super::{inflect, Case, Gender, NamePart};
+
+/// Inflects the given parts of a full name, detecting gender from them
+///
+/// Parts are joined in the "Фамилия Имя Отчество" order; missing parts are omitted.
+#[cfg(feature = "gender-detection")]
+pub fn fullname(
+    last: Option<&str>,
+    first: Option<&str>,
+    middle: Option<&str>,
+    case: Case,
+) -> String {
+    let gender = detect_gender(last, first, middle);
+    fullname_with_gender(gender, last, first, middle, case)
+}
+
+/// Inflects the given parts of a full name using an explicit gender
+pub fn fullname_with_gender(
+    gender: Gender,
+    last: Option<&str>,
+    first: Option<&str>,
+    middle: Option<&str>,
+    case: Case,
+) -> String {
+    let parts = [
+        last.map(|name| inflect(NamePart::Last, gender, name, case)),
+        first.map(|name| inflect(NamePart::First, gender, name, case)),
+        middle.map(|name| inflect(NamePart::Middle, gender, name, case)),
+    ];
+    parts
+        .iter()
+        .flatten()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Owned full name with a known or detected gender
+#[derive(Eq, PartialEq, Clone, Debug, Hash)]
+pub struct FullName {
+    last: Option<String>,
+    first: Option<String>,
+    middle: Option<String>,
+    gender: Gender,
+}
+
+/// Builder for `FullName`
+#[derive(Clone, Debug, Default)]
+pub struct FullNameBuilder {
+    last: Option<String>,
+    first: Option<String>,
+    middle: Option<String>,
+    gender: Option<Gender>,
+}
+
+impl FullNameBuilder {
+    /// Sets last name
+    pub fn last(mut self, last: impl Into<String>) -> FullNameBuilder {
+        self.last = Some(last.into());
+        self
+    }
+
+    /// Sets first name
+    pub fn first(mut self, first: impl Into<String>) -> FullNameBuilder {
+        self.first = Some(first.into());
+        self
+    }
+
+    /// Sets middle name
+    pub fn middle(mut self, middle: impl Into<String>) -> FullNameBuilder {
+        self.middle = Some(middle.into());
+        self
+    }
+
+    /// Sets gender instead of detecting it from the name parts
+    pub fn gender(mut self, gender: Gender) -> FullNameBuilder {
+        self.gender = Some(gender);
+        self
+    }
+
+    /// Builds the name, detecting gender when it wasn't set
+    ///
+    /// Without the `gender-detection` feature the gender defaults to `Gender::Unknown`.
+    pub fn build(self) -> FullName {
+        #[cfg(feature = "gender-detection")]
+        let gender = self.gender.unwrap_or_else(|| {
+            detect_gender(
+                self.last.as_deref(),
+                self.first.as_deref(),
+                self.middle.as_deref(),
+            )
+        });
+        #[cfg(not(feature = "gender-detection"))]
+        let gender = self.gender.unwrap_or(Gender::Unknown);
+        FullName {
+            last: self.last,
+            first: self.first,
+            middle: self.middle,
+            gender,
+        }
+    }
+}
+
+impl FullName {
+    /// Starts building a full name
+    pub fn builder() -> FullNameBuilder {
+        FullNameBuilder::default()
+    }
+
+    /// Last name
+    pub fn last(&self) -> Option<&str> {
+        self.last.as_deref()
+    }
+
+    /// First name
+    pub fn first(&self) -> Option<&str> {
+        self.first.as_deref()
+    }
+
+    /// Middle name
+    pub fn middle(&self) -> Option<&str> {
+        self.middle.as_deref()
+    }
+
+    /// Gender used for inflection
+    pub fn gender(&self) -> Gender {
+        self.gender
+    }
+
+    /// Inflects every part of the name
+    pub fn to_case(&self, case: Case) -> FullName {
+        let gender = self.gender;
+        FullName {
+            last: self
+                .last()
+                .map(|name| inflect(NamePart::Last, gender, name, case)),
+            first: self
+                .first()
+                .map(|name| inflect(NamePart::First, gender, name, case)),
+            middle: self
+                .middle()
+                .map(|name| inflect(NamePart::Middle, gender, name, case)),
+            gender,
+        }
+    }
+
+    /// Formats the name in the given case
+    pub fn display_in(&self, case: Case) -> DisplayIn<'_> {
+        DisplayIn { name: self, case }
+    }
+}
+
+/// Prints the parts in the "Фамилия Имя Отчество" order, separated by spaces
+impl fmt::Display for FullName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.display_in(Case::Nominative).fmt(f)
+    }
+}
+
+/// Full name formatted in a given case, see `FullName::display_in`
+#[derive(Clone, Copy, Debug)]
+pub struct DisplayIn<'a> {
+    name: &'a FullName,
+    case: Case,
+}
+
+impl<'a> fmt::Display for DisplayIn<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = self.name;
+        f.write_str(&fullname_with_gender(
+            name.gender,
+            name.last(),
+            name.first(),
+            name.middle(),
+            self.case,
+        ))
+    }
+}
+
+#[cfg(feature = "gender-detection")]
+const PATRONYMIC_SUFFIXES: &[&str] = &["ич", "вна", "чна", "оглы", "улы", "кызы", "гызы"];
+
+#[cfg(feature = "gender-detection")]
+fn looks_like_middlename(name: &str) -> bool {
+    let name = name.to_lowercase();
+    PATRONYMIC_SUFFIXES
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+}
+
+// Whitespace separated parts of the name, with particles like "оглы" kept together with the
+// name before them as one middle name
+#[cfg(feature = "gender-detection")]
+fn split_parts(name: &str) -> Vec<String> {
+    let mut parts: Vec<String> = Vec::new();
+    for word in name.split_whitespace() {
+        let is_particle = PATRONYMIC_PARTICLES.contains(&word.to_lowercase().as_str());
+        match parts.last_mut() {
+            Some(last) if is_particle => {
+                last.push(' ');
+                last.push_str(word);
+            }
+            _ => parts.push(word.to_owned()),
+        }
+    }
+    parts
+}
+
+/// Splits a full name into parts, telling the "Фамилия Имя Отчество" and
+/// "Имя Отчество Фамилия" orders apart
+///
+/// Parts are separated by whitespace, so hyphenated names stay whole, and a name followed by
+/// a particle like "оглы" or "кызы" is one middle name. Patronymic endings like -вич or -вна
+/// mark the middle name, last name suffixes like -ов or -ская mark the last name. When both orders are equally likely `Error::AmbiguousName` is returned
+/// instead of a guess. A single part becomes a middle, last or first name in this order
+/// of preference. Gender is detected from the parts.
+#[cfg(feature = "gender-detection")]
+pub fn parse_fullname(name: &str) -> Result<FullName, Error> {
+    let parts = split_parts(name);
+    let parts: Vec<&str> = parts.iter().map(String::as_str).collect();
+    let builder = FullName::builder();
+    let builder = match *parts.as_slice() {
+        [] => return Err(Error::EmptyName),
+        [single] if looks_like_middlename(single) => builder.middle(single),
+        [single] if looks_like_lastname(single) => builder.last(single),
+        [single] => builder.first(single),
+        [first, middle] if looks_like_middlename(middle) && !looks_like_middlename(first) => {
+            builder.first(first).middle(middle)
+        }
+        [middle, last] if looks_like_middlename(middle) && looks_like_lastname(last) => {
+            builder.middle(middle).last(last)
+        }
+        [a, b] => match (looks_like_lastname(a), looks_like_lastname(b)) {
+            (true, false) => builder.last(a).first(b),
+            (false, true) => builder.first(a).last(b),
+            _ => return Err(Error::AmbiguousName(name.to_owned())),
+        },
+        [a, b, c] => {
+            let score = |last: &str, first: &str, middle: &str| {
+                2 * i32::from(looks_like_middlename(middle)) + i32::from(looks_like_lastname(last))
+                    - 2 * i32::from(looks_like_middlename(first))
+            };
+            let (surname_first, surname_last) = (score(a, b, c), score(c, a, b));
+            if surname_first > surname_last {
+                builder.last(a).first(b).middle(c)
+            } else if surname_last > surname_first {
+                builder.first(a).middle(b).last(c)
+            } else {
+                return Err(Error::AmbiguousName(name.to_owned()));
+            }
+        }
+        _ => return Err(Error::UnexpectedPartCount(parts.len())),
+    };
+    Ok(builder.build())
+}
+
+#[cfg(all(test, feature = "lastname-rules"))]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[cfg(all(
+        feature = "firstname-rules",
+        feature = "middlename-rules",
+        feature = "gender-detection"
+    ))]
+    #[test]
+    fn should_inflect_fullname() {
+        assert_eq!(
+            fullname(
+                Some("Иванова-Петрова"),
+                Some("Анна"),
+                Some("Сергеевна"),
+                Case::Dative
+            ),
+            "Ивановой-Петровой Анне Сергеевне"
+        );
+        assert_eq!(
+            fullname(
+                Some("Пушкин"),
+                Some("Александр"),
+                Some("Сергеевич"),
+                Case::Genitive
+            ),
+            "Пушкина Александра Сергеевича"
+        );
+    }
+
+    #[cfg(all(feature = "firstname-rules", feature = "gender-detection"))]
+    #[test]
+    fn should_omit_missing_parts() {
+        assert_eq!(
+            fullname(Some("Иванова"), None, None, Case::Dative),
+            "Ивановой"
+        );
+        assert_eq!(
+            fullname(None, Some("Олег"), None, Case::Instrumental),
+            "Олегом"
+        );
+        assert_eq!(fullname(None, None, None, Case::Dative), "");
+        assert_eq!(
+            fullname_with_gender(Gender::Male, Some("Сирота"), None, None, Case::Dative),
+            "Сироте"
+        );
+    }
+
+    #[cfg(all(feature = "firstname-rules", feature = "middlename-rules"))]
+    #[test]
+    fn should_build_fullname() {
+        let name = FullName::builder().last("Иванова").first("Анна").build();
+        assert_eq!(name.gender(), Gender::Female);
+        assert_eq!(name.middle(), None);
+        assert_eq!(name.to_string(), "Иванова Анна");
+        assert_eq!(name.display_in(Case::Dative).to_string(), "Ивановой Анне");
+
+        let dative = name.to_case(Case::Dative);
+        assert_eq!(dative.last(), Some("Ивановой"));
+        assert_eq!(dative.gender(), Gender::Female);
+    }
+
+    #[test]
+    fn should_keep_explicit_gender() {
+        let name = FullName::builder()
+            .last("Сирота")
+            .gender(Gender::Male)
+            .build();
+        assert_eq!(name.display_in(Case::Dative).to_string(), "Сироте");
+
+        let mut counts = HashMap::new();
+        *counts.entry(name.clone()).or_insert(0) += 1;
+        *counts.entry(name).or_insert(0) += 1;
+        assert_eq!(counts.len(), 1);
+    }
+
+    #[cfg(feature = "gender-detection")]
+    fn parts(name: &FullName) -> (Option<&str>, Option<&str>, Option<&str>) {
+        (name.last(), name.first(), name.middle())
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_parse_both_orders() {
+        let expected = (Some("Иванов"), Some("Иван"), Some("Иванович"));
+        let name = parse_fullname("Иванов Иван Иванович").unwrap();
+        assert_eq!(parts(&name), expected);
+        assert_eq!(name.gender(), Gender::Male);
+        let name = parse_fullname(" Иван  Иванович\tИванов ").unwrap();
+        assert_eq!(parts(&name), expected);
+
+        let name = parse_fullname("Мария-Луиза Петровна Бонч-Бруевич").unwrap();
+        assert_eq!(
+            parts(&name),
+            (Some("Бонч-Бруевич"), Some("Мария-Луиза"), Some("Петровна"))
+        );
+        assert_eq!(name.gender(), Gender::Female);
+        let name = parse_fullname("Станкевич Иван Петрович").unwrap();
+        assert_eq!(
+            parts(&name),
+            (Some("Станкевич"), Some("Иван"), Some("Петрович"))
+        );
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_parse_two_parts() {
+        let name = parse_fullname("Иванова Анна").unwrap();
+        assert_eq!(parts(&name), (Some("Иванова"), Some("Анна"), None));
+        assert_eq!(name.gender(), Gender::Female);
+        let name = parse_fullname("Анна Иванова").unwrap();
+        assert_eq!(parts(&name), (Some("Иванова"), Some("Анна"), None));
+        let name = parse_fullname("Анна Сергеевна").unwrap();
+        assert_eq!(parts(&name), (None, Some("Анна"), Some("Сергеевна")));
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_keep_patronymic_particles_with_the_middle_name() {
+        let expected = (Some("Алиев"), Some("Ильхам"), Some("Гейдар оглы"));
+        let name = parse_fullname("Алиев Ильхам Гейдар оглы").unwrap();
+        assert_eq!(parts(&name), expected);
+        assert_eq!(name.gender(), Gender::Male);
+        let name = parse_fullname("Ильхам Гейдар оглы Алиев").unwrap();
+        assert_eq!(parts(&name), expected);
+        let name = parse_fullname("Гейдар оглы Алиев").unwrap();
+        assert_eq!(parts(&name), (Some("Алиев"), None, Some("Гейдар оглы")));
+        let name = parse_fullname("Лейла Ильхам кызы").unwrap();
+        assert_eq!(parts(&name), (None, Some("Лейла"), Some("Ильхам кызы")));
+        assert_eq!(name.gender(), Gender::Female);
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_report_unparsable_names() {
+        assert_eq!(parse_fullname("  "), Err(Error::EmptyName));
+        assert_eq!(
+            parse_fullname("Иван Иванович Иванов Младший"),
+            Err(Error::UnexpectedPartCount(4))
+        );
+        assert_eq!(
+            parse_fullname("Марина Иванова"),
+            Err(Error::AmbiguousName("Марина Иванова".to_owned()))
+        );
+        assert_eq!(
+            parse_fullname("Блабла Блабла Блабла"),
+            Err(Error::AmbiguousName("Блабла Блабла Блабла".to_owned()))
+        );
+    }
+}
@@ -1,78 +1,132 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::Error;
+
 /// Возможные рода
-#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+#[non_exhaustive]
 pub enum Gender {
     /// Мужской род
+    #[cfg_attr(feature = "serde", serde(alias = "мужской"))]
     Male,
     /// Женский род
+    #[cfg_attr(feature = "serde", serde(alias = "женский"))]
     Female,
     /// Средний род
+    #[cfg_attr(feature = "serde", serde(alias = "средний"))]
     Androgynous,
+    /// Род не определён; при склонении равнозначен `Androgynous`
+    #[cfg_attr(feature = "serde", serde(alias = "неизвестный"))]
+    Unknown,
 }
 
-struct GenderMapping {
-    androgynous: &'static [&'static str],
-    male: &'static [&'static str],
-    female: &'static [&'static str],
-}
+impl Gender {
+    /// English identifier of the gender, e.g. "male"
+    pub fn name(self) -> &'static str {
+        match self {
+            Gender::Male => "male",
+            Gender::Female => "female",
+            Gender::Androgynous => "androgynous",
+            Gender::Unknown => "unknown",
+        }
+    }
 
-struct GenderHeuristic {
-    exceptions: Option<GenderMapping>,
-    suffixes: GenderMapping,
+    /// Russian name of the gender, e.g. "мужской"
+    pub fn russian_name(self) -> &'static str {
+        match self {
+            Gender::Male => "мужской",
+            Gender::Female => "женский",
+            Gender::Androgynous => "средний",
+            Gender::Unknown => "неизвестный",
+        }
+    }
 }
 
-impl GenderHeuristic {
-    fn detect_gender(&self, name: &str) -> Option<Gender> {
-        let find_exception = |exceptions: &[&str]| exceptions.contains(&name);
-        let find_suffix = |suffixes: &[&str]| suffixes.iter().any(|&suffix| name.ends_with(suffix));
-        self.exceptions
-            .as_ref()
-            .and_then(|mapping| {
-                if find_exception(mapping.androgynous) {
-                    None
-                } else if find_exception(mapping.female) {
-                    Some(Gender::Female)
-                } else if find_exception(mapping.male) {
-                    Some(Gender::Male)
-                } else {
-                    None
-                }
-            })
-            .or_else(|| {
-                if find_suffix(self.suffixes.androgynous) {
-                    None
-                } else if find_suffix(self.suffixes.female) {
-                    Some(Gender::Female)
-                } else if find_suffix(self.suffixes.male) {
-                    Some(Gender::Male)
-                } else {
-                    None
-                }
-            })
+/// Prints the English identifier, or the Russian name with the alternate flag (`{:#}`)
+impl fmt::Display for Gender {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.write_str(self.russian_name())
+        } else {
+            f.write_str(self.name())
+        }
     }
 }
 
-struct GenderHeuristics {
-    lastname: GenderHeuristic,
-    firstname: GenderHeuristic,
-    middlename: GenderHeuristic,
+/// Parses English and Russian names and common abbreviations, ignoring letter case
+impl FromStr for Gender {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Gender, Error> {
+        let lowercase = s.trim().to_lowercase();
+        match lowercase.trim_end_matches('.') {
+            "male" | "m" | "мужской" | "муж" | "м" => Ok(Gender::Male),
+            "female" | "f" | "женский" | "жен" | "ж" => Ok(Gender::Female),
+            "androgynous" | "средний" | "общий" | "ср" => Ok(Gender::Androgynous),
+            "unknown" | "неизвестный" => Ok(Gender::Unknown),
+            _ => Err(Error::UnknownGender(s.to_owned())),
+        }
+    }
 }
 
-const GENDER: GenderHeuristics = include!(concat!(env!("OUT_DIR"), "/gender.inc"));
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-/// Detects gender of a middlename, fallbacks to `Gender::Androgynous`
-pub fn detect_gender(
-    lastname: Option<&str>,
-    firstname: Option<&str>,
-    middlename: Option<&str>,
-) -> Gender {
-    middlename
-        .and_then(|middlename| GENDER.middlename.detect_gender(&middlename.to_lowercase()))
-        .or_else(|| {
-            firstname
-                .and_then(|firstname| GENDER.firstname.detect_gender(&firstname.to_lowercase()))
-        })
-        .or_else(|| {
-            lastname.and_then(|lastname| GENDER.lastname.detect_gender(&lastname.to_lowercase()))
-        })
-        .unwrap_or(Gender::Androgynous)
+    #[test]
+    fn should_round_trip_display() {
+        for &gender in &[
+            Gender::Male,
+            Gender::Female,
+            Gender::Androgynous,
+            Gender::Unknown,
+        ] {
+            assert_eq!(gender.to_string().parse(), Ok(gender));
+            assert_eq!(format!("{:#}", gender).parse(), Ok(gender));
+        }
+        assert_eq!(format!("{:#}", Gender::Female), "женский");
+    }
+
+    #[test]
+    fn should_parse_abbreviations() {
+        assert_eq!(" Муж ".parse(), Ok(Gender::Male));
+        assert_eq!("M".parse(), Ok(Gender::Male));
+        assert_eq!("\tж\n".parse(), Ok(Gender::Female));
+        assert_eq!("ЖЕН.".parse(), Ok(Gender::Female));
+        assert_eq!("Female".parse(), Ok(Gender::Female));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_serialize_as_lowercase_string() {
+        for &gender in &[
+            Gender::Male,
+            Gender::Female,
+            Gender::Androgynous,
+            Gender::Unknown,
+        ] {
+            let json = serde_json::to_string(&gender).unwrap();
+            assert_eq!(json, format!("\"{}\"", gender));
+            assert_eq!(serde_json::from_str::<Gender>(&json).unwrap(), gender);
+            let russian = format!("\"{:#}\"", gender);
+            assert_eq!(serde_json::from_str::<Gender>(&russian).unwrap(), gender);
+        }
+        assert!(serde_json::from_str::<Gender>("\"neuter\"").is_err());
+    }
+
+    #[test]
+    fn should_reject_unknown_gender() {
+        assert_eq!(
+            "м/ж".parse::<Gender>(),
+            Err(Error::UnknownGender("м/ж".to_owned()))
+        );
+        assert!("".parse::<Gender>().is_err());
+        assert!("x".parse::<Gender>().is_err());
+    }
 }
@@ -0,0 +1,126 @@
+//! Salutations like "Уважаемый Иван Иванович" for letters and envelopes.
+
+#[cfg(feature = "gender-detection")]
+use super::detect_gender;
+use super::{inflect, Case, Gender, NamePart};
+
+/// Kind of salutation `greeting` produces
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+#[non_exhaustive]
+pub enum GreetingStyle {
+    /// Уважаемый Иван Иванович | Уважаемая Анна Петровна
+    Formal,
+    /// Здравствуйте, Иван Иванович
+    Neutral,
+    /// Уважаемому Ивану Ивановичу, as addressed on an envelope
+    Dative,
+}
+
+/// Salutation with the first name and the optional middle name, without trailing
+/// punctuation
+///
+/// Without a gender it is detected from the names, or taken as `Gender::Unknown` without
+/// the `gender-detection` feature. Unless the gender is male or female, the formal style
+/// falls back to the neutral one and the dative style leaves out "Уважаемому", so
+/// nothing is guessed.
+///
+/// ```
+/// # #[cfg(all(feature = "firstname-rules", feature = "middlename-rules", feature = "gender-detection"))]
+/// # {
+/// use petrovich::{greeting, Gender, GreetingStyle};
+///
+/// assert_eq!(
+///     greeting("Анна", Some("Петровна"), None, GreetingStyle::Formal),
+///     "Уважаемая Анна Петровна"
+/// );
+/// assert_eq!(
+///     greeting("Иван", Some("Иванович"), Some(Gender::Male), GreetingStyle::Dative),
+///     "Уважаемому Ивану Ивановичу"
+/// );
+/// assert_eq!(
+///     greeting("Иван", None, None, GreetingStyle::Neutral),
+///     "Здравствуйте, Иван"
+/// );
+/// # }
+/// ```
+pub fn greeting(
+    first: &str,
+    middle: Option<&str>,
+    gender: Option<Gender>,
+    style: GreetingStyle,
+) -> String {
+    #[cfg(feature = "gender-detection")]
+    let gender = gender.unwrap_or_else(|| detect_gender(None, Some(first), middle));
+    #[cfg(not(feature = "gender-detection"))]
+    let gender = gender.unwrap_or(Gender::Unknown);
+    let name = |case| {
+        let mut name = inflect(NamePart::First, gender, first.trim(), case);
+        if let Some(middle) = middle {
+            name.push(' ');
+            name.push_str(&inflect(NamePart::Middle, gender, middle.trim(), case));
+        }
+        name
+    };
+    match (style, gender) {
+        (GreetingStyle::Formal, Gender::Male) => format!("Уважаемый {}", name(Case::Nominative)),
+        (GreetingStyle::Formal, Gender::Female) => {
+            format!("Уважаемая {}", name(Case::Nominative))
+        }
+        (GreetingStyle::Dative, Gender::Male) => format!("Уважаемому {}", name(Case::Dative)),
+        (GreetingStyle::Dative, Gender::Female) => format!("Уважаемой {}", name(Case::Dative)),
+        (GreetingStyle::Dative, _) => name(Case::Dative),
+        _ => format!("Здравствуйте, {}", name(Case::Nominative)),
+    }
+}
+
+#[cfg(all(test, feature = "firstname-rules", feature = "gender-detection"))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "middlename-rules")]
+    #[test]
+    fn should_agree_with_gender() {
+        assert_eq!(
+            greeting("Иван", Some("Иванович"), None, GreetingStyle::Formal),
+            "Уважаемый Иван Иванович"
+        );
+        assert_eq!(
+            greeting("Анна", Some("Петровна"), None, GreetingStyle::Dative),
+            "Уважаемой Анне Петровне"
+        );
+        assert_eq!(
+            greeting("Анна", Some("Петровна"), None, GreetingStyle::Neutral),
+            "Здравствуйте, Анна Петровна"
+        );
+    }
+
+    #[cfg(feature = "middlename-rules")]
+    #[test]
+    fn should_disambiguate_androgynous_names_by_middle_name() {
+        assert_eq!(
+            greeting("Саша", Some("Игоревна"), None, GreetingStyle::Formal),
+            "Уважаемая Саша Игоревна"
+        );
+        assert_eq!(
+            greeting("Саша", Some("Игоревич"), None, GreetingStyle::Dative),
+            "Уважаемому Саше Игоревичу"
+        );
+        assert_eq!(
+            greeting("Женя", Some("Петровна"), None, GreetingStyle::Dative),
+            "Уважаемой Жене Петровне"
+        );
+    }
+
+    #[test]
+    fn should_not_guess_unknown_gender() {
+        assert_eq!(
+            greeting("Саша", None, None, GreetingStyle::Formal),
+            "Здравствуйте, Саша"
+        );
+        assert_eq!(greeting("Саша", None, None, GreetingStyle::Dative), "Саше");
+        assert_eq!(
+            greeting("Иван", None, Some(Gender::Unknown), GreetingStyle::Formal),
+            "Здравствуйте, Иван"
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! Honorifics like "господин" or "доктор" inflected along with the last name.
+
+#[cfg(feature = "gender-detection")]
+use super::detect_gender;
+use super::{inflect, Case, Gender, NamePart};
+
+/// Word put before a last name, declined in the same case
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+#[non_exhaustive]
+pub enum Honorific {
+    /// Господин, for men
+    Gospodin,
+    /// Госпожа, for women
+    Gospozha,
+    /// Доктор
+    Doctor,
+    /// Профессор
+    Professor,
+    /// Any other honorific given by its forms in the order of `Case::ALL`
+    Custom(&'static [&'static str; 6]),
+}
+
+const GOSPODIN: [&str; 6] = [
+    "господин",
+    "господина",
+    "господину",
+    "господина",
+    "господином",
+    "господине",
+];
+const GOSPOZHA: [&str; 6] = [
+    "госпожа",
+    "госпожи",
+    "госпоже",
+    "госпожу",
+    "госпожой",
+    "госпоже",
+];
+const DOCTOR: [&str; 6] = [
+    "доктор",
+    "доктора",
+    "доктору",
+    "доктора",
+    "доктором",
+    "докторе",
+];
+const PROFESSOR: [&str; 6] = [
+    "профессор",
+    "профессора",
+    "профессору",
+    "профессора",
+    "профессором",
+    "профессоре",
+];
+
+impl Honorific {
+    /// Господин or госпожа for a male or a female name, `None` for other genders
+    pub fn polite(gender: Gender) -> Option<Honorific> {
+        match gender {
+            Gender::Male => Some(Honorific::Gospodin),
+            Gender::Female => Some(Honorific::Gospozha),
+            _ => None,
+        }
+    }
+
+    /// Lowercase form of the honorific in the case, e.g. "господину" for the dative
+    pub fn form(self, case: Case) -> &'static str {
+        let forms = match self {
+            Honorific::Gospodin => &GOSPODIN,
+            Honorific::Gospozha => &GOSPOZHA,
+            Honorific::Doctor => &DOCTOR,
+            Honorific::Professor => &PROFESSOR,
+            Honorific::Custom(forms) => forms,
+        };
+        forms[usize::from(case)]
+    }
+
+    // Господин and госпожа swapped to match a male or a female name
+    fn agreeing(self, gender: Gender) -> Honorific {
+        match self {
+            Honorific::Gospodin | Honorific::Gospozha => Honorific::polite(gender).unwrap_or(self),
+            _ => self,
+        }
+    }
+}
+
+/// Inflects a last name together with the honorific in front of it
+///
+/// Господин and госпожа are swapped to agree with a male or a female gender, so
+/// `Honorific::Gospodin` can be used for anyone; with other genders the given one is kept.
+///
+/// ```
+/// # #[cfg(feature = "lastname-rules")]
+/// # {
+/// use petrovich::{with_honorific, Case, Gender, Honorific};
+///
+/// assert_eq!(
+///     with_honorific(Honorific::Gospodin, Gender::Male, "Иванов", Case::Dative),
+///     "господину Иванову"
+/// );
+/// assert_eq!(
+///     with_honorific(Honorific::Gospodin, Gender::Female, "Петрова", Case::Dative),
+///     "госпоже Петровой"
+/// );
+/// assert_eq!(
+///     with_honorific(Honorific::Doctor, Gender::Male, "Сидоров", Case::Dative),
+///     "доктору Сидорову"
+/// );
+/// # }
+/// ```
+pub fn with_honorific(honorific: Honorific, gender: Gender, lastname: &str, case: Case) -> String {
+    format!(
+        "{} {}",
+        honorific.agreeing(gender).form(case),
+        inflect(NamePart::Last, gender, lastname, case)
+    )
+}
+
+/// Inflects a last name together with the honorific, detecting gender from the name
+///
+/// Last names like "Шевченко" say nothing about gender; they keep the given honorific and
+/// are inflected with androgynous rules. See `lastname_auto`.
+#[cfg(feature = "gender-detection")]
+pub fn with_honorific_auto(honorific: Honorific, lastname: &str, case: Case) -> String {
+    with_honorific(
+        honorific,
+        detect_gender(Some(lastname), None, None),
+        lastname,
+        case,
+    )
+}
+
+#[cfg(all(test, feature = "lastname-rules"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_decline_honorifics() {
+        assert_eq!(
+            with_honorific(
+                Honorific::Professor,
+                Gender::Male,
+                "Сидоров",
+                Case::Instrumental
+            ),
+            "профессором Сидоровым"
+        );
+        assert_eq!(
+            with_honorific(Honorific::Doctor, Gender::Female, "Петрова", Case::Genitive),
+            "доктора Петровой"
+        );
+        assert_eq!(
+            with_honorific(
+                Honorific::Gospozha,
+                Gender::Female,
+                "Петрова",
+                Case::Accusative
+            ),
+            "госпожу Петрову"
+        );
+        let forms = &["сэр", "сэра", "сэру", "сэра", "сэром", "сэре"];
+        assert_eq!(
+            with_honorific(
+                Honorific::Custom(forms),
+                Gender::Male,
+                "Иванов",
+                Case::Dative
+            ),
+            "сэру Иванову"
+        );
+    }
+
+    #[test]
+    fn should_agree_with_gender() {
+        assert_eq!(
+            with_honorific(Honorific::Gospozha, Gender::Male, "Иванов", Case::Genitive),
+            "господина Иванова"
+        );
+        assert_eq!(
+            with_honorific(
+                Honorific::Gospozha,
+                Gender::Androgynous,
+                "Шевченко",
+                Case::Dative
+            ),
+            "госпоже Шевченко"
+        );
+        assert_eq!(Honorific::polite(Gender::Unknown), None);
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_detect_gender() {
+        assert_eq!(
+            with_honorific_auto(Honorific::Gospodin, "Петрова", Case::Dative),
+            "госпоже Петровой"
+        );
+        assert_eq!(
+            with_honorific_auto(Honorific::Gospozha, "Иванов", Case::Prepositional),
+            "господине Иванове"
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! Trait for user types holding the parts of a full name.
+
+#[cfg(feature = "gender-detection")]
+use super::detect_gender;
+use super::{fullname_with_gender, inflect, Case, FullName, Gender, NamePart};
+
+/// Type holding some of the parts of a full name, usually implemented with
+/// `#[derive(Inflectable)]`
+///
+/// Only the accessors of the parts are required; the inflection methods are built on
+/// them.
+///
+/// ```
+/// # #[cfg(all(feature = "firstname-rules", feature = "lastname-rules"))]
+/// # {
+/// use petrovich::{Case, Gender, Inflectable};
+///
+/// struct Employee {
+///     last: String,
+///     first: String,
+/// }
+///
+/// impl Inflectable for Employee {
+///     fn last(&self) -> Option<&str> {
+///         Some(&self.last)
+///     }
+///
+///     fn first(&self) -> Option<&str> {
+///         Some(&self.first)
+///     }
+///
+///     fn gender(&self) -> Gender {
+///         Gender::Female
+///     }
+/// }
+///
+/// let employee = Employee {
+///     last: "Иванова".to_owned(),
+///     first: "Анна".to_owned(),
+/// };
+/// assert_eq!(employee.inflected(Case::Dative), "Ивановой Анне");
+/// # }
+/// ```
+pub trait Inflectable {
+    /// Last name, if any
+    fn last(&self) -> Option<&str> {
+        None
+    }
+
+    /// First name, if any
+    fn first(&self) -> Option<&str> {
+        None
+    }
+
+    /// Middle name, if any
+    fn middle(&self) -> Option<&str> {
+        None
+    }
+
+    /// Gender the parts are inflected with
+    ///
+    /// Detected from the parts by default, or `Gender::Unknown` without the
+    /// `gender-detection` feature.
+    fn gender(&self) -> Gender {
+        #[cfg(feature = "gender-detection")]
+        let gender = detect_gender(self.last(), self.first(), self.middle());
+        #[cfg(not(feature = "gender-detection"))]
+        let gender = Gender::Unknown;
+        gender
+    }
+
+    /// Parts inflected into the case, in the "Фамилия Имя Отчество" order
+    fn inflected(&self, case: Case) -> String {
+        fullname_with_gender(
+            self.gender(),
+            self.last(),
+            self.first(),
+            self.middle(),
+            case,
+        )
+    }
+
+    /// Last name inflected into the case
+    fn inflected_last(&self, case: Case) -> Option<String> {
+        let name = self.last()?;
+        Some(inflect(NamePart::Last, self.gender(), name, case))
+    }
+
+    /// First name inflected into the case
+    fn inflected_first(&self, case: Case) -> Option<String> {
+        let name = self.first()?;
+        Some(inflect(NamePart::First, self.gender(), name, case))
+    }
+
+    /// Middle name inflected into the case
+    fn inflected_middle(&self, case: Case) -> Option<String> {
+        let name = self.middle()?;
+        Some(inflect(NamePart::Middle, self.gender(), name, case))
+    }
+}
+
+impl Inflectable for FullName {
+    fn last(&self) -> Option<&str> {
+        FullName::last(self)
+    }
+
+    fn first(&self) -> Option<&str> {
+        FullName::first(self)
+    }
+
+    fn middle(&self) -> Option<&str> {
+        FullName::middle(self)
+    }
+
+    fn gender(&self) -> Gender {
+        FullName::gender(self)
+    }
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod tests {
+    use crate::{Case, Gender, Inflectable};
+
+    #[derive(Inflectable)]
+    struct Employee {
+        #[petrovich(last)]
+        last: String,
+        #[petrovich(first)]
+        first: String,
+        #[petrovich(middle)]
+        middle: Option<String>,
+        #[petrovich(gender)]
+        gender: Gender,
+        #[allow(dead_code)]
+        position: String,
+    }
+
+    #[derive(Inflectable)]
+    struct Signature<'a>(
+        #[petrovich(first)] &'a str,
+        #[petrovich(last)] Option<&'a str>,
+    );
+
+    #[test]
+    fn should_inflect_annotated_fields() {
+        let employee = Employee {
+            last: "Иванова".to_owned(),
+            first: "Анна".to_owned(),
+            middle: Some("Сергеевна".to_owned()),
+            gender: Gender::Female,
+            position: "бухгалтер".to_owned(),
+        };
+        assert_eq!(employee.inflected(Case::Dative), "Ивановой Анне Сергеевне");
+        assert_eq!(
+            employee.inflected_last(Case::Genitive).as_deref(),
+            Some("Ивановой")
+        );
+        assert_eq!(employee.gender(), Gender::Female);
+
+        let employee = Employee {
+            middle: None,
+            ..employee
+        };
+        assert_eq!(employee.inflected(Case::Dative), "Ивановой Анне");
+        assert_eq!(employee.inflected_middle(Case::Dative), None);
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_detect_gender_without_gender_field() {
+        let signature = Signature("Пётр", Some("Смирнов"));
+        assert_eq!(signature.gender(), Gender::Male);
+        assert_eq!(signature.inflected(Case::Instrumental), "Смирновым Петром");
+        let signature = Signature("Пётр", None);
+        assert_eq!(
+            signature.inflected_first(Case::Dative).as_deref(),
+            Some("Петру")
+        );
+        assert_eq!(signature.last(), None);
+    }
+}
@@ -0,0 +1,684 @@
+//! Inflection engine with a configurable rule set.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use super::declensions::declensions_with;
+#[cfg(feature = "gender-detection")]
+use super::detect_gender;
+#[cfg(feature = "old-orthography")]
+use super::orthography::restore_old_spelling;
+use super::rules::{MatchContext, Rules, TagFilter};
+#[cfg(feature = "yofication")]
+use super::yoficate;
+use super::{
+    fix_homoglyphs, inflect_name, nfc, normalize_name, particle_start, titlecase_name, Case,
+    Declensions, Error, Gender, InflectOptions, NamePart, Overrides,
+};
+#[cfg(feature = "old-orthography")]
+use super::{normalize_old_orthography, OldOrthography};
+
+type TagPredicate = dyn Fn(&MatchContext) -> bool + Send + Sync;
+
+struct TagPredicates(HashMap<String, Box<TagPredicate>>);
+
+impl TagFilter for TagPredicates {
+    fn allows(&self, tag: &str, context: &MatchContext) -> bool {
+        self.0.get(tag).is_some_and(|predicate| predicate(context))
+    }
+}
+
+/// Inflects names using a rule set and predicates for custom rule tags
+///
+/// Rules tagged with a `RuleTag::Custom` only apply when a predicate is registered for
+/// the tag and returns `true` for the segment being matched.
+///
+/// ```
+/// use std::borrow::Cow;
+///
+/// use petrovich::{Case, Gender, Inflector, Rule, RuleList, RuleTag, Rules};
+///
+/// let rule = Rule::new(Gender::Male, vec!["ко"], ["-а", "-у", "-а", "-ом", "-е"])
+///     .with_tag(RuleTag::Custom(Cow::Borrowed("polysyllabic")));
+/// let rules = Rules::new(
+///     RuleList::new(vec![], vec![rule]),
+///     RuleList::new(vec![], vec![]),
+///     RuleList::new(vec![], vec![]),
+/// );
+/// let inflector = Inflector::new(rules).with_tag_predicate("polysyllabic", |context| {
+///     context.segment().chars().filter(|&c| "аеёиоуыэюя".contains(c)).count() >= 3
+/// });
+/// assert_eq!(inflector.lastname(Gender::Male, "Головко", Case::Dative), "Головку");
+/// assert_eq!(inflector.lastname(Gender::Male, "Пушко", Case::Dative), "Пушко");
+/// ```
+pub struct Inflector {
+    rules: Rules,
+    predicates: TagPredicates,
+    options: InflectOptions,
+}
+
+impl Default for Inflector {
+    fn default() -> Inflector {
+        Inflector::new(Rules::builtin())
+    }
+}
+
+/// Builder of an `Inflector`, created by `Inflector::builder()`
+pub struct InflectorBuilder {
+    inflector: Inflector,
+}
+
+impl InflectorBuilder {
+    /// Replaces the built-in rules
+    pub fn rules(mut self, rules: Rules) -> InflectorBuilder {
+        self.inflector.rules = rules;
+        self
+    }
+
+    /// Sets options controlling inflection
+    pub fn options(mut self, options: InflectOptions) -> InflectorBuilder {
+        self.inflector.options = options;
+        self
+    }
+
+    /// Registers a predicate for a custom rule tag
+    pub fn tag_predicate<F>(self, tag: impl Into<String>, predicate: F) -> InflectorBuilder
+    where
+        F: Fn(&MatchContext) -> bool + Send + Sync + 'static,
+    {
+        InflectorBuilder {
+            inflector: self.inflector.with_tag_predicate(tag, predicate),
+        }
+    }
+
+    /// Puts explicit forms of particular names in front of the rules set so far
+    pub fn overrides(self, overrides: &Overrides) -> InflectorBuilder {
+        InflectorBuilder {
+            inflector: self.inflector.with_overrides(overrides),
+        }
+    }
+
+    /// Creates the inflector
+    pub fn build(self) -> Inflector {
+        self.inflector
+    }
+}
+
+impl Inflector {
+    /// Starts building an inflector with the built-in rules and default options
+    pub fn builder() -> InflectorBuilder {
+        InflectorBuilder {
+            inflector: Inflector::default(),
+        }
+    }
+
+    /// Creates an inflector using the given rules
+    pub fn new(rules: Rules) -> Inflector {
+        Inflector {
+            rules,
+            predicates: TagPredicates(HashMap::new()),
+            options: InflectOptions::default(),
+        }
+    }
+
+    /// Registers a predicate for a custom rule tag
+    pub fn with_tag_predicate<F>(mut self, tag: impl Into<String>, predicate: F) -> Inflector
+    where
+        F: Fn(&MatchContext) -> bool + Send + Sync + 'static,
+    {
+        self.predicates.0.insert(tag.into(), Box::new(predicate));
+        self
+    }
+
+    /// Sets options controlling inflection
+    pub fn with_options(mut self, options: InflectOptions) -> Inflector {
+        self.options = options;
+        self
+    }
+
+    /// Puts explicit forms of particular names in front of the rules
+    pub fn with_overrides(self, overrides: &Overrides) -> Inflector {
+        Inflector {
+            rules: self.rules.merge(overrides.to_rules()),
+            ..self
+        }
+    }
+
+    /// Options controlling inflection
+    pub fn options(&self) -> &InflectOptions {
+        &self.options
+    }
+
+    /// Rules used by the inflector, including merged overrides
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    // Name the rules run against, normalized, with homoglyphs fixed, in the modern
+    // orthography, with ё restored and title-cased when the options ask for it
+    fn prepare<'n>(&self, name: &'n str, part: NamePart) -> Cow<'n, str> {
+        let name = if self.options.normalize {
+            normalize_name(name)
+        } else {
+            Cow::Borrowed(name)
+        };
+        let name = match name {
+            name if !self.options.fix_homoglyphs => name,
+            Cow::Borrowed(name) => fix_homoglyphs(name),
+            Cow::Owned(name) => Cow::Owned(fix_homoglyphs(&name).into_owned()),
+        };
+        #[cfg(feature = "old-orthography")]
+        let name = match self.options.old_orthography {
+            OldOrthography::Off => name,
+            _ => Cow::Owned(normalize_old_orthography(&name)),
+        };
+        #[cfg(feature = "yofication")]
+        let name = match name {
+            name if !self.options.yoficate => name,
+            Cow::Borrowed(name) => yoficate(part, name),
+            Cow::Owned(name) => Cow::Owned(yoficate(part, &name).into_owned()),
+        };
+        if self.options.titlecase {
+            return Cow::Owned(titlecase_name(part, &name));
+        }
+        name
+    }
+
+    // Inflected form of the name as given, with the old spelling put back when the options
+    // ask for it
+    #[cfg_attr(not(feature = "old-orthography"), allow(unused_variables))]
+    fn finish(&self, name: &str, output: String) -> String {
+        #[cfg(feature = "old-orthography")]
+        if self.options.old_orthography == OldOrthography::KeepSpelling {
+            return restore_old_spelling(name, &output);
+        }
+        output
+    }
+
+    fn inflect_with(
+        &self,
+        gender: Gender,
+        name: &str,
+        case: Case,
+        part: NamePart,
+    ) -> Result<String, Error> {
+        let output = self.inflect_prepared(gender, &self.prepare(name, part), case, part)?;
+        Ok(self.finish(name, output))
+    }
+
+    fn inflect_prepared(
+        &self,
+        gender: Gender,
+        name: &str,
+        case: Case,
+        part: NamePart,
+    ) -> Result<String, Error> {
+        let rule_list = self.rules.list(part);
+        if part == NamePart::Middle && self.options.decline_before_particle {
+            let name = &*nfc(name);
+            if let Some(start) =
+                particle_start(gender, name, rule_list, &self.predicates, &self.options)
+            {
+                let (given, particle) = name.split_at(start);
+                // The particle tells the gender when it isn't known
+                let gender = match gender {
+                    Gender::Male | Gender::Female => gender,
+                    #[cfg(feature = "gender-detection")]
+                    _ => detect_gender(None, None, Some(particle)),
+                    #[cfg(not(feature = "gender-detection"))]
+                    _ => gender,
+                };
+                let mut output = self.inflect_with(gender, given, case, NamePart::First)?;
+                output.push_str(&inflect_name(
+                    gender,
+                    particle,
+                    case,
+                    rule_list,
+                    &self.predicates,
+                    &self.options,
+                )?);
+                return Ok(output);
+            }
+        }
+        inflect_name(
+            gender,
+            name,
+            case,
+            rule_list,
+            &self.predicates,
+            &self.options,
+        )
+    }
+
+    /// Inflects a name using the rules for the given part, returning it unchanged when
+    /// the options reject it
+    pub fn inflect(&self, part: NamePart, gender: Gender, name: &str, case: Case) -> String {
+        self.inflect_with(gender, name, case, part)
+            .unwrap_or_else(|_| name.to_owned())
+    }
+
+    /// Inflects first name, returning it unchanged when the options reject it
+    pub fn firstname(&self, gender: Gender, name: &str, case: Case) -> String {
+        self.inflect_with(gender, name, case, NamePart::First)
+            .unwrap_or_else(|_| name.to_owned())
+    }
+
+    /// Inflects last name, returning it unchanged when the options reject it
+    pub fn lastname(&self, gender: Gender, name: &str, case: Case) -> String {
+        self.inflect_with(gender, name, case, NamePart::Last)
+            .unwrap_or_else(|_| name.to_owned())
+    }
+
+    /// Inflects middle name, returning it unchanged when the options reject it
+    pub fn middlename(&self, gender: Gender, name: &str, case: Case) -> String {
+        self.inflect_with(gender, name, case, NamePart::Middle)
+            .unwrap_or_else(|_| name.to_owned())
+    }
+
+    /// Inflects first name, reporting names rejected by the options
+    pub fn firstname_checked(
+        &self,
+        gender: Gender,
+        name: &str,
+        case: Case,
+    ) -> Result<String, Error> {
+        self.inflect_with(gender, name, case, NamePart::First)
+    }
+
+    /// Inflects last name, reporting names rejected by the options
+    pub fn lastname_checked(
+        &self,
+        gender: Gender,
+        name: &str,
+        case: Case,
+    ) -> Result<String, Error> {
+        self.inflect_with(gender, name, case, NamePart::Last)
+    }
+
+    /// Inflects middle name, reporting names rejected by the options
+    pub fn middlename_checked(
+        &self,
+        gender: Gender,
+        name: &str,
+        case: Case,
+    ) -> Result<String, Error> {
+        self.inflect_with(gender, name, case, NamePart::Middle)
+    }
+
+    /// Inflects a name into every case at once, reporting names rejected by the options
+    pub fn declensions(
+        &self,
+        gender: Gender,
+        name: &str,
+        part: NamePart,
+    ) -> Result<Declensions, Error> {
+        let declensions = declensions_with(
+            gender,
+            &self.prepare(name, part),
+            self.rules.list(part),
+            &self.predicates,
+            &self.options,
+        )?;
+        #[cfg(feature = "old-orthography")]
+        if self.options.old_orthography == OldOrthography::KeepSpelling {
+            return Ok(declensions.map_forms(|form| restore_old_spelling(name, form)));
+        }
+        Ok(declensions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "lastname-rules")]
+    use crate::JunkHandling;
+    use crate::{Rule, RuleList, RuleTag};
+
+    fn syllables(segment: &str) -> usize {
+        segment
+            .chars()
+            .filter(|c| "аеёиоуыэюя".contains(c.to_lowercase().next().unwrap()))
+            .count()
+    }
+
+    fn synthetic_rules() -> Rules {
+        let rule = Rule::new(Gender::Male, vec!["ко"], ["-а", "-у", "-а", "-ом", "-е"])
+            .with_tag(RuleTag::Custom("polysyllabic".into()));
+        Rules::new(
+            RuleList::new(vec![], vec![rule]),
+            RuleList::new(vec![], vec![]),
+            RuleList::new(vec![], vec![]),
+        )
+    }
+
+    #[test]
+    fn should_skip_custom_tag_without_predicate() {
+        let inflector = Inflector::new(synthetic_rules());
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Головко", Case::Dative),
+            "Головко"
+        );
+    }
+
+    #[test]
+    fn should_apply_custom_tag_predicate() {
+        let inflector = Inflector::new(synthetic_rules())
+            .with_tag_predicate("polysyllabic", |context| syllables(context.segment()) >= 3);
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Головко", Case::Dative),
+            "Головку"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Пушко", Case::Dative),
+            "Пушко"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Пушко-Головко", Case::Dative),
+            "Пушко-Головку"
+        );
+    }
+
+    #[test]
+    fn should_pass_match_context() {
+        let inflector =
+            Inflector::new(synthetic_rules()).with_tag_predicate("polysyllabic", |context| {
+                context.name() == "Пушко-Головко"
+                    && context.segment_count() == 2
+                    && context.index() == 0
+            });
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Пушко-Головко", Case::Dative),
+            "Пушку-Головко"
+        );
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_detach_junk() {
+        let inflector = Inflector::default().with_options(InflectOptions {
+            junk: JunkHandling::Detach,
+            ..InflectOptions::DEFAULT
+        });
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Иванов2", Case::Dative),
+            "Иванову2"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Иванов_42!", Case::Genitive),
+            "Иванова_42!"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "test_Петров", Case::Dative),
+            "test_Петрову"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "2Иванов-Сидоров3", Case::Dative),
+            "2Иванову-Сидорову3"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "_123_", Case::Dative),
+            "_123_"
+        );
+    }
+
+    #[test]
+    fn should_keep_junk_by_default() {
+        let inflector = Inflector::default();
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Иванов2", Case::Dative),
+            "Иванов2"
+        );
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_reject_junk() {
+        let inflector = Inflector::default().with_options(InflectOptions {
+            junk: JunkHandling::Reject,
+            ..InflectOptions::DEFAULT
+        });
+        assert_eq!(
+            inflector.lastname_checked(Gender::Male, "Иванов2", Case::Dative),
+            Err(Error::InvalidCharacter {
+                character: '2',
+                position: 12
+            })
+        );
+        assert_eq!(
+            inflector.lastname_checked(Gender::Male, "test_Петров", Case::Dative),
+            Err(Error::InvalidCharacter {
+                character: '_',
+                position: 4
+            })
+        );
+        assert_eq!(
+            inflector.lastname_checked(Gender::Male, "123", Case::Dative),
+            Err(Error::InvalidCharacter {
+                character: '1',
+                position: 0
+            })
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Иванов2", Case::Dative),
+            "Иванов2"
+        );
+        assert_eq!(
+            inflector.lastname_checked(Gender::Male, "Петров Водкин", Case::Dative),
+            Ok("Петрову Водкину".to_owned())
+        );
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_apply_builder_options() {
+        let inflector = Inflector::builder()
+            .options(InflectOptions {
+                preserve_caps: true,
+                normalize_yo: true,
+                strict: true,
+                ..InflectOptions::DEFAULT
+            })
+            .build();
+        assert_eq!(
+            inflector.lastname(Gender::Male, "ИВАНОВ-Сидоров", Case::Dative),
+            "ИВАНОВУ-Сидорову"
+        );
+        assert_eq!(
+            inflector.lastname_checked(Gender::Male, "Blabla", Case::Dative),
+            Err(Error::NoRuleMatched("Blabla".to_owned()))
+        );
+    }
+
+    #[cfg(feature = "firstname-rules")]
+    #[test]
+    fn should_match_yo_as_ye() {
+        let overrides = Rules::new(
+            RuleList::new(vec![], vec![]),
+            RuleList::new(
+                vec![Rule::new(
+                    Gender::Male,
+                    vec!["сёма"],
+                    [".", ".", ".", ".", "."],
+                )],
+                vec![],
+            ),
+            RuleList::new(vec![], vec![]),
+        );
+        let rules = Rules::builtin().merge(overrides);
+        let inflector = Inflector::builder()
+            .rules(rules.clone())
+            .options(InflectOptions {
+                normalize_yo: false,
+                ..InflectOptions::DEFAULT
+            })
+            .build();
+        assert_eq!(
+            inflector.firstname(Gender::Male, "Сема", Case::Genitive),
+            "Семы"
+        );
+        let inflector = Inflector::builder().rules(rules).build();
+        assert_eq!(
+            inflector.firstname(Gender::Male, "Сема", Case::Genitive),
+            "Сема"
+        );
+        assert_eq!(
+            inflector.firstname(Gender::Male, "Сёма", Case::Genitive),
+            "Сёма"
+        );
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_match_whole_name_without_splitting() {
+        let inflector = Inflector::builder()
+            .options(InflectOptions {
+                split_hyphens: false,
+                ..InflectOptions::DEFAULT
+            })
+            .build();
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Иванов-Сидоров", Case::Dative),
+            "Иванов-Сидорову"
+        );
+    }
+
+    #[cfg(all(feature = "firstname-rules", feature = "middlename-rules"))]
+    #[test]
+    fn should_decline_name_before_particle() {
+        let inflector = Inflector::builder()
+            .options(InflectOptions {
+                decline_before_particle: true,
+                ..InflectOptions::DEFAULT
+            })
+            .build();
+        assert_eq!(
+            inflector.middlename(Gender::Male, "Ибрагим оглы", Case::Genitive),
+            "Ибрагима оглы"
+        );
+        // The particle tells the gender of the name before it
+        assert_eq!(
+            inflector.middlename(Gender::Unknown, "Полина-кызы", Case::Dative),
+            "Полине-кызы"
+        );
+        assert_eq!(
+            inflector.middlename(Gender::Male, "Ибрагимович", Case::Genitive),
+            "Ибрагимовича"
+        );
+        assert_eq!(
+            inflector.firstname(Gender::Male, "Ибрагим оглы", Case::Genitive),
+            "Ибрагима оглы"
+        );
+    }
+
+    #[cfg(all(feature = "firstname-rules", feature = "lastname-rules"))]
+    #[test]
+    fn should_normalize_input() {
+        let inflector = Inflector::builder()
+            .options(InflectOptions {
+                normalize: true,
+                ..InflectOptions::DEFAULT
+            })
+            .build();
+        assert_eq!(
+            inflector.lastname(Gender::Male, " Иванов  — Сидоров ", Case::Dative),
+            "Иванову-Сидорову"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Петров\u{a0}\u{a0}Водкин", Case::Genitive),
+            "Петрова Водкина"
+        );
+        assert_eq!(
+            inflector
+                .declensions(Gender::Female, "Анна – Мария", NamePart::First)
+                .map(|declensions| declensions.get(Case::Genitive).to_owned()),
+            Ok("Анны-Марии".to_owned())
+        );
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_fix_homoglyphs() {
+        let inflector = Inflector::default().with_options(InflectOptions {
+            fix_homoglyphs: true,
+            ..InflectOptions::DEFAULT
+        });
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Иванoв", Case::Dative),
+            "Иванову"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Smith", Case::Dative),
+            "Smith"
+        );
+        assert_eq!(
+            Inflector::default().lastname(Gender::Male, "Иванoв", Case::Dative),
+            "Иванoв"
+        );
+    }
+
+    #[cfg(feature = "yofication")]
+    #[test]
+    fn should_yoficate() {
+        let inflector = Inflector::default().with_options(InflectOptions {
+            yoficate: true,
+            ..InflectOptions::DEFAULT
+        });
+        assert_eq!(
+            inflector.firstname(Gender::Male, "Семен", Case::Dative),
+            "Семёну"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Female, "Королева", Case::Genitive),
+            "Королёвой"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Чебышев", Case::Dative),
+            "Чебышеву"
+        );
+        assert_eq!(
+            Inflector::default().firstname(Gender::Male, "Семен", Case::Dative),
+            "Семену"
+        );
+    }
+
+    #[cfg(all(feature = "firstname-rules", feature = "lastname-rules"))]
+    #[test]
+    fn should_titlecase() {
+        let inflector = Inflector::default().with_options(InflectOptions {
+            titlecase: true,
+            ..InflectOptions::DEFAULT
+        });
+        assert_eq!(
+            inflector.lastname(Gender::Male, "ИВАНОВ", Case::Dative),
+            "Иванову"
+        );
+        assert_eq!(
+            inflector.firstname(Gender::Female, "анна-мария", Case::Genitive),
+            "Анны-Марии"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "ФОН БРАУН", Case::Dative),
+            "фон Брауну"
+        );
+        assert_eq!(
+            inflector
+                .declensions(Gender::Male, "МакГрегор", NamePart::Last)
+                .unwrap()[Case::Genitive],
+            *"МакГрегора"
+        );
+        assert_eq!(
+            Inflector::default().lastname(Gender::Male, "ИВАНОВ", Case::Dative),
+            "ИВАНОВУ"
+        );
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_match_builtin_rules_by_default() {
+        let inflector = Inflector::default();
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Бонч-Бруевич", Case::Dative),
+            "Бонч-Бруевичу"
+        );
+    }
+}
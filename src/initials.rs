@@ -0,0 +1,118 @@
+//! Inflection of surnames written together with initials.
+
+use super::{inflect, Case, Gender, NamePart};
+
+/// Whether the token is one or more initials like "И.", "И.И." or "А.-М."; the last dot
+/// may be omitted after a capital letter, as in "И" or "А.С"
+pub(crate) fn is_initials(token: &str) -> bool {
+    let mut chars = token.chars().peekable();
+    let mut letters = 0;
+    while let Some(c) = chars.next() {
+        if !c.is_alphabetic() {
+            return false;
+        }
+        letters += 1;
+        match chars.next() {
+            // A hyphen must be followed by another initial
+            Some('.') if chars.peek() == Some(&'-') => {
+                chars.next();
+                if chars.peek().is_none() {
+                    return false;
+                }
+            }
+            Some('.') => {}
+            None => return c.is_uppercase(),
+            Some(_) => return false,
+        }
+    }
+    letters > 0
+}
+
+// Byte range of the first run of whitespace-separated tokens that aren't initials
+fn surname_span(name: &str) -> Option<(usize, usize)> {
+    let mut span: Option<(usize, usize)> = None;
+    for token in name.split_whitespace() {
+        let start = token.as_ptr() as usize - name.as_ptr() as usize;
+        if is_initials(token) {
+            if span.is_some() {
+                break;
+            }
+        } else {
+            let end = start + token.len();
+            span = Some(span.map_or((start, end), |(first, _)| (first, end)));
+        }
+    }
+    span
+}
+
+/// Inflects a surname written together with initials, like "Иванов И. И." or "А.С. Петрова"
+///
+/// Initials are kept untouched wherever they are, and so is the spacing. The first run of
+/// tokens that aren't initials is inflected as a last name; a string of initials only is
+/// returned unchanged.
+///
+/// ```
+/// # #[cfg(feature = "lastname-rules")]
+/// # {
+/// use petrovich::{inflect_with_initials, Case, Gender};
+///
+/// assert_eq!(
+///     inflect_with_initials(Gender::Male, "Иванов И. И.", Case::Dative),
+///     "Иванову И. И."
+/// );
+/// assert_eq!(
+///     inflect_with_initials(Gender::Female, "А.С.  Петрова", Case::Genitive),
+///     "А.С.  Петровой"
+/// );
+/// # }
+/// ```
+pub fn inflect_with_initials(gender: Gender, name: &str, case: Case) -> String {
+    match surname_span(name) {
+        Some((start, end)) => {
+            name[..start].to_owned()
+                + &inflect(NamePart::Last, gender, &name[start..end], case)
+                + &name[end..]
+        }
+        None => name.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_recognize_initials() {
+        for &token in &["И.", "И.И.", "А.-М.", "и.", "И", "А.С"] {
+            assert!(is_initials(token), "{}", token);
+        }
+        for &token in &["Иванов", "Ив.", "И.Иванов", "и", ".", "", "Ж.-"] {
+            assert!(!is_initials(token), "{}", token);
+        }
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_keep_initials_around_surname() {
+        assert_eq!(
+            inflect_with_initials(Gender::Male, "И.И. Иванов", Case::Instrumental),
+            "И.И. Ивановым"
+        );
+        assert_eq!(
+            inflect_with_initials(Gender::Female, "Петрова А.С.", Case::Dative),
+            "Петровой А.С."
+        );
+        assert_eq!(
+            inflect_with_initials(Gender::Male, "Петров Водкин К. С.", Case::Dative),
+            "Петрову Водкину К. С."
+        );
+        assert_eq!(
+            inflect_with_initials(Gender::Male, " И. И. ", Case::Dative),
+            " И. И. "
+        );
+        assert_eq!(
+            inflect_with_initials(Gender::Male, "Иванов", Case::Genitive),
+            "Иванова"
+        );
+    }
+}
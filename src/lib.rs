@@ -20,186 +20,901 @@
 //! use petrovich::*;
 //!
 //! fn main() {
+//!     # #[cfg(feature = "firstname-rules")]
 //!     assert_eq!(firstname(Gender::Male, "Саша", Case::Dative), "Саше");
+//!     # #[cfg(feature = "firstname-rules")]
 //!     assert_eq!(firstname(Gender::Female, "Изабель", Case::Genitive), "Изабель");
 //!
+//!     # #[cfg(feature = "lastname-rules")]
 //!     assert_eq!(lastname(Gender::Male, "Станкевич", Case::Prepositional), "Станкевиче");
+//!     # #[cfg(feature = "lastname-rules")]
 //!     assert_eq!(lastname(Gender::Female, "Станкевич", Case::Prepositional), "Станкевич");
 //!
+//!     # #[cfg(feature = "middlename-rules")]
 //!     assert_eq!(middlename(Gender::Male, "Сергеич", Case::Instrumental), "Сергеичем");
+//!     # #[cfg(feature = "middlename-rules")]
 //!     assert_eq!(middlename(Gender::Female, "Прокопьевна", Case::Accusative), "Прокопьевну");
 //! }
 //! ```
 
+use std::borrow::Cow;
+use std::fmt;
+
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+mod case;
+pub use case::Case;
+
+mod part;
+pub use part::NamePart;
+
 mod gender;
-pub use gender::{detect_gender, Gender};
+pub use gender::Gender;
+
+#[cfg(feature = "gender-detection")]
+mod detect;
+#[cfg(feature = "gender-detection")]
+pub use detect::{
+    detect_gender, detect_gender_checked, detect_gender_scored, detect_gender_strict,
+    GenderConflict, GenderScore,
+};
 
 pub mod deprecated;
 pub use deprecated::*;
 
-type Modifier = Option<(usize, &'static str)>;
+mod rule_index;
+mod rules;
+#[doc(hidden)]
+pub use rules::RuleParts;
+use rules::{match_key, NoCustomTags, TagFilter, RULES};
+pub use rules::{MatchContext, Rule, RuleList, RuleTag, Rules};
 
-#[derive(Eq, PartialEq, Copy, Clone)]
-enum RuleTag {
-    FirstWord,
-}
+#[cfg(feature = "macros")]
+pub use petrovich_macros::include_rules;
+
+#[cfg(feature = "derive")]
+pub use petrovich_derive::Inflectable;
+
+// Lets the macro expansion refer to `::petrovich` in this crate's own tests
+#[cfg(all(test, any(feature = "macros", feature = "derive")))]
+extern crate self as petrovich;
+
+mod inflector;
+pub use inflector::{Inflector, InflectorBuilder};
+
+mod overrides;
+pub use overrides::Overrides;
+
+#[cfg(feature = "cache")]
+mod cache;
+#[cfg(feature = "cache")]
+pub use cache::{CacheStats, CachedInflector, SyncCachedInflector};
+
+mod options;
+#[cfg(feature = "old-orthography")]
+pub use options::OldOrthography;
+use options::{is_apostrophe, is_cyrillic_word, is_junk};
+pub use options::{InflectOptions, JunkHandling};
+
+mod error;
+pub use error::Error;
+
+#[cfg(any(feature = "toml", feature = "yaml", feature = "json"))]
+mod loader;
+
+mod opencorpora;
+
+mod plural;
+pub use plural::lastname_plural;
+
+#[cfg(feature = "firstname-rules")]
+mod vocative;
+#[cfg(feature = "firstname-rules")]
+pub use vocative::firstname_vocative;
+
+mod coverage;
+pub use coverage::{
+    coverage_report, coverage_report_with_limit, CoverageReport, DEFAULT_PASS_THROUGH_LIMIT,
+};
+
+mod declensions;
+pub use declensions::{declensions, matches_any_case, matching_case, Declensions};
+
+mod strict;
+pub use strict::Matched;
+#[cfg(feature = "firstname-rules")]
+pub use strict::{firstname_checked, try_firstname};
+#[cfg(feature = "lastname-rules")]
+pub use strict::{lastname_checked, try_lastname};
+#[cfg(feature = "middlename-rules")]
+pub use strict::{middlename_checked, try_middlename};
+
+mod explain;
+#[cfg(feature = "firstname-rules")]
+pub use explain::explain_firstname;
+#[cfg(feature = "lastname-rules")]
+pub use explain::explain_lastname;
+#[cfg(feature = "middlename-rules")]
+pub use explain::explain_middlename;
+pub use explain::{explain, Explanation, RuleApplication, SegmentExplanation};
+
+pub mod ext;
+
+mod fullname;
+#[cfg(feature = "gender-detection")]
+pub use fullname::{fullname, parse_fullname};
+pub use fullname::{fullname_with_gender, DisplayIn, FullName, FullNameBuilder};
+
+mod matching;
+pub use matching::{FieldMatch, MatchGrade, MatchOptions, MatchReason, MatchResult};
+
+mod surname_forms;
+pub use surname_forms::lastname_to_gender;
+
+mod patronymic;
+pub use patronymic::{check_patronymic, patronymic, patronymic_matches, PatronymicMatch};
+
+mod reverse;
+#[cfg(feature = "firstname-rules")]
+pub use reverse::firstname_to_nominative;
+#[cfg(feature = "lastname-rules")]
+pub use reverse::lastname_to_nominative;
+#[cfg(feature = "middlename-rules")]
+pub use reverse::middlename_to_nominative;
+pub use reverse::{
+    detect_case, detect_cases, detect_fullname_cases, to_nominative, NominativeCandidate,
+};
+
+mod inflectable;
+pub use inflectable::Inflectable;
+
+mod display;
+#[cfg(feature = "firstname-rules")]
+pub use display::firstname_display;
+#[cfg(feature = "lastname-rules")]
+pub use display::lastname_display;
+#[cfg(feature = "middlename-rules")]
+pub use display::middlename_display;
+pub use display::{inflect_display, Inflected};
+
+mod initials;
+pub use initials::inflect_with_initials;
+
+mod greeting;
+pub use greeting::{greeting, GreetingStyle};
+
+mod honorific;
+#[cfg(feature = "gender-detection")]
+pub use honorific::with_honorific_auto;
+pub use honorific::{with_honorific, Honorific};
+
+mod preposition;
+pub use preposition::{with_preposition, with_preposition_in, Preposition, PrepositionalPhrase};
+
+mod template;
+pub use template::NameFormat;
+
+mod batch;
+#[cfg(all(feature = "rayon", feature = "gender-detection"))]
+pub use batch::par_detect_gender_many;
+#[cfg(feature = "rayon")]
+pub use batch::par_inflect_many;
+pub use batch::{inflect_each, inflect_many};
+
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(all(feature = "wasm", feature = "gender-detection"))]
+pub use wasm::detect_gender_js;
+#[cfg(all(feature = "wasm", feature = "firstname-rules"))]
+pub use wasm::firstname_js;
+#[cfg(feature = "wasm")]
+pub use wasm::inflect_many_js;
+#[cfg(all(feature = "wasm", feature = "lastname-rules"))]
+pub use wasm::lastname_js;
+#[cfg(all(feature = "wasm", feature = "middlename-rules"))]
+pub use wasm::middlename_js;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+#[cfg(feature = "python")]
+mod python;
+
+#[cfg(feature = "translit")]
+mod translit;
+#[cfg(all(feature = "translit", feature = "firstname-rules"))]
+pub use translit::firstname_latin;
+#[cfg(all(feature = "translit", feature = "lastname-rules"))]
+pub use translit::lastname_latin;
+#[cfg(all(feature = "translit", feature = "middlename-rules"))]
+pub use translit::middlename_latin;
+#[cfg(feature = "translit")]
+pub use translit::{
+    from_latin, inflect_latin, to_cyrillic, to_latin, LatinInflection, TranslitStandard,
+};
+
+#[cfg(feature = "lang-uk")]
+pub mod uk;
+
+#[cfg(feature = "diminutives")]
+mod diminutives;
+#[cfg(feature = "diminutives")]
+pub use diminutives::{canonical_firstname, CanonicalCandidate};
+
+#[cfg(feature = "old-orthography")]
+mod orthography;
+#[cfg(feature = "old-orthography")]
+pub use orthography::normalize_old_orthography;
+
+#[cfg(feature = "yofication")]
+mod yofication;
+#[cfg(feature = "yofication")]
+pub use yofication::{is_yo_ambiguous, yoficate};
+
+mod validate;
+pub use validate::{validate_name, NameIssue, NameIssueKind};
+
+mod normalize;
+pub use normalize::{fix_homoglyphs, homoglyphs, normalize_name, titlecase_name, Homoglyph};
 
-use RuleTag::*;
+mod stem;
+pub use stem::{stem, stem_matches};
 
-struct Rule {
+mod neutral;
+#[cfg(feature = "lastname-rules")]
+pub use neutral::lastname_neutral;
+pub use neutral::{inflect_neutral, inflect_neutral_with, merge_neutral, BracketStyle};
+
+/// Kind of rule that matched a name segment
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+pub enum MatchKind {
+    /// Whole segment matched an exception
+    Exception,
+    /// Segment ending matched a suffix rule
+    Suffix,
+}
+
+fn find_rule<'r>(
     gender: Gender,
-    test: &'static [&'static str],
-    mods: [Modifier; 5],
-    tags: &'static [RuleTag],
+    rule_list: &'r RuleList,
+    context: &MatchContext,
+    filter: &impl TagFilter,
+    options: &InflectOptions,
+) -> Option<(MatchKind, &'r Rule)> {
+    let lowercase_name = match_key(context.segment);
+    // First let's check for exceptions
+    rule_list
+        .find_exception(
+            &lowercase_name,
+            gender,
+            context,
+            filter,
+            options.normalize_yo,
+        )
+        .map(|rule| (MatchKind::Exception, rule))
+        // Then check for suffixes
+        .or_else(|| {
+            rule_list
+                .find_suffix(
+                    &lowercase_name,
+                    gender,
+                    context,
+                    filter,
+                    options.normalize_yo,
+                )
+                .map(|rule| (MatchKind::Suffix, rule))
+        })
+}
+
+// Split segment into the leading characters, the last run of letters and the trailing
+// characters; apostrophes inside the run are kept, as in "О’Нил"
+fn detach_junk(segment: &str) -> Option<(&str, &str, &str)> {
+    let end = segment
+        .char_indices()
+        .rev()
+        .find(|&(_, c)| c.is_alphabetic())
+        .map(|(i, c)| i + c.len_utf8())?;
+    let start = segment[..end]
+        .char_indices()
+        .rev()
+        .find(|&(_, c)| !(c.is_alphabetic() || is_apostrophe(c)))
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    Some((&segment[..start], &segment[start..end], &segment[end..]))
+}
+
+/// Rule matched for a single segment of a name
+pub(crate) struct SegmentMatch<'n, 'r> {
+    /// Separator preceding the segment, empty for the first one
+    pub(crate) separator: &'n str,
+    /// Characters kept untouched before the segment
+    pub(crate) head: &'n str,
+    pub(crate) segment: &'n str,
+    /// Characters kept untouched after the segment
+    pub(crate) tail: &'n str,
+    pub(crate) rule: Option<(MatchKind, &'r Rule)>,
+    /// Capitalization the appended ending follows, `Casing::Lower` when
+    /// `InflectOptions::preserve_caps` is off
+    pub(crate) casing: Casing,
 }
 
-impl Rule {
-    fn modifier(&self, case: Case) -> Modifier {
-        self.mods[case as usize]
+impl<'n, 'r> SegmentMatch<'n, 'r> {
+    /// Writes the segment inflected into the case, without allocating
+    fn write_into(&self, case: Case, output: &mut impl fmt::Write) -> fmt::Result {
+        self.write_slot(usize::from(case), output)
     }
 
-    fn has_tag(&self, tag: RuleTag) -> bool {
-        self.tags.contains(&tag)
+    /// Writes the segment with the modifier in the slot of the rule, see `Rule::modifier_at`
+    pub(crate) fn write_slot(&self, slot: usize, output: &mut impl fmt::Write) -> fmt::Result {
+        output.write_str(self.separator)?;
+        output.write_str(self.head)?;
+        match self.rule.and_then(|(_, rule)| rule.modifier_at(slot)) {
+            Some((skip, postfix)) => {
+                output.write_str(&self.segment[..self.kept_len(skip)])?;
+                if self.casing == Casing::Upper {
+                    for c in postfix.chars().flat_map(char::to_uppercase) {
+                        output.write_char(c)?;
+                    }
+                } else {
+                    output.write_str(postfix)?;
+                }
+            }
+            None => output.write_str(self.segment)?,
+        }
+        output.write_str(self.tail)
     }
 
-    fn fully_matches(&self, name: &str) -> bool {
-        self.test.iter().any(|&test| test == name)
+    // Byte length of the segment left after cutting `skip` characters off its end; a
+    // modifier cutting more characters than the segment has leaves nothing
+    fn kept_len(&self, skip: usize) -> usize {
+        match skip {
+            0 => self.segment.len(),
+            skip => self
+                .segment
+                .char_indices()
+                .nth_back(skip - 1)
+                .map_or(0, |(i, _)| i),
+        }
     }
 
-    fn suffix_matches(&self, name: &str) -> bool {
-        self.test.iter().any(|&test| name.ends_with(test))
+    /// Whether inflecting into the case changes the segment
+    fn changes(&self, case: Case) -> bool {
+        match self.rule.and_then(|(_, rule)| rule.modifier(case)) {
+            Some((skip, postfix)) => {
+                let ending = &self.segment[self.kept_len(skip)..];
+                if self.casing == Casing::Upper {
+                    !ending
+                        .chars()
+                        .eq(postfix.chars().flat_map(char::to_uppercase))
+                } else {
+                    ending != postfix
+                }
+            }
+            None => false,
+        }
     }
 
-    fn gender_matches(&self, gender: Gender) -> bool {
-        self.gender == gender || self.gender == Gender::Androgynous
+    pub(crate) fn inflect_into(&self, case: Case, output: &mut String) {
+        // Writing to a String never fails
+        let _ = self.write_into(case, output);
+    }
+
+    /// Characters of the segment inflected into the case, without allocating
+    fn inflected_chars(&self, case: Case) -> impl Iterator<Item = char> + '_ {
+        let (kept, postfix) = match self.rule.and_then(|(_, rule)| rule.modifier(case)) {
+            Some((skip, postfix)) => (&self.segment[..self.kept_len(skip)], postfix),
+            None => (self.segment, ""),
+        };
+        self.separator
+            .chars()
+            .chain(self.head.chars())
+            .chain(kept.chars())
+            .chain(postfix.chars())
+            .chain(self.tail.chars())
     }
 }
 
-struct RuleList {
-    exceptions: &'static [Rule],
-    suffixes: &'static [Rule],
+// Composes names written in NFD, as some systems produce them, so that rules and the
+// character counts of modifiers apply to whole letters like "й" and "ё"
+pub(crate) fn nfc(name: &str) -> Cow<'_, str> {
+    if is_nfc(name) {
+        Cow::Borrowed(name)
+    } else {
+        Cow::Owned(name.nfc().collect())
+    }
 }
 
-struct Rules {
-    lastname: RuleList,
-    firstname: RuleList,
-    middlename: RuleList,
+/// Capitalization of a segment, deciding how endings appended to it are written
+///
+/// Characters written in the input are never recased, whatever the class.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub(crate) enum Casing {
+    /// "иванов", or no letters at all
+    Lower,
+    /// "Иванов"
+    Title,
+    /// "ИВАНОВ", getting endings in capitals
+    Upper,
+    /// "МакГрегор" or "иВАНОВ", getting lowercase endings
+    Mixed,
 }
 
-const RULES: Rules = include!(concat!(env!("OUT_DIR"), "/rules.inc"));
+impl Casing {
+    pub(crate) fn of(segment: &str) -> Casing {
+        let mut letters = segment.chars().filter(|c| c.is_alphabetic());
+        let first = match letters.next() {
+            Some(first) => first,
+            None => return Casing::Lower,
+        };
+        let (mut upper, mut lower) = (false, false);
+        for c in letters {
+            upper |= c.is_uppercase();
+            lower |= c.is_lowercase();
+        }
+        match (first.is_uppercase(), upper, lower) {
+            (true, _, false) => Casing::Upper,
+            (true, false, true) => Casing::Title,
+            (false, false, _) => Casing::Lower,
+            _ => Casing::Mixed,
+        }
+    }
 
-/// Возможные падежи
-#[derive(Eq, PartialEq, Clone, Copy, Debug)]
-pub enum Case {
-    /// Родительный  | _Кого? Чего?_
-    Genitive,
-    /// Дательный    | _Кому? Чему?_
-    Dative,
-    /// Винительный  | _Кого? Что?_
-    Accusative,
-    /// Творительный | _Кем? Чем?_
-    Instrumental,
-    /// Предложный   | _О ком? О чём?_
-    Prepositional,
-}
-
-// Find exception by name and gender
-fn find_exception<'a>(
-    exceptions: &'a [Rule],
-    name: &str,
+    /// Ending written in the case of the segment
+    pub(crate) fn apply(self, ending: &str) -> Cow<'_, str> {
+        match self {
+            Casing::Upper => Cow::Owned(ending.to_uppercase()),
+            Casing::Lower | Casing::Title | Casing::Mixed => Cow::Borrowed(ending),
+        }
+    }
+}
+
+// Rejects names with junk when the options say so
+fn check_junk(name: &str, options: &InflectOptions) -> Result<(), Error> {
+    if options.junk == JunkHandling::Reject {
+        if let Some((position, character)) =
+            name.char_indices().find(|&(_, c)| c != '-' && is_junk(c))
+        {
+            return Err(Error::InvalidCharacter {
+                character,
+                position,
+            });
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn match_name<'n, 'r>(
     gender: Gender,
-    is_last: bool,
-) -> Option<&'a Rule> {
-    // Search exceptions with matching name and gender
-    exceptions.iter().find(|&exception| {
-        exception.fully_matches(name)
-            && exception.gender_matches(gender)
-            && (!exception.has_tag(FirstWord) || !is_last)
-    })
-}
-
-// Find suffix by name and gender
-fn find_suffix<'a>(suffixes: &'a [Rule], name: &str, gender: Gender) -> Option<&'a Rule> {
-    suffixes
-        .iter()
-        .filter(|&suffix| suffix.suffix_matches(name) && suffix.gender_matches(gender))
-        .max_by_key(|&rule| {
-            // Find longest match
-            rule.test
-                .iter()
-                .filter(|&&test| name.ends_with(test))
-                .max_by_key(|&&test| test.len())
-                .unwrap()
-                .len()
+    name: &'n str,
+    rule_list: &'r RuleList,
+    filter: &impl TagFilter,
+    options: &InflectOptions,
+) -> Result<Vec<SegmentMatch<'n, 'r>>, Error> {
+    check_junk(name, options)?;
+    let matches = segment_matches(gender, name, rule_list, filter, options).collect::<Vec<_>>();
+    if options.strict && matches.iter().all(|segment| segment.rule.is_none()) {
+        return Err(Error::NoRuleMatched(name.to_owned()));
+    }
+    Ok(matches)
+}
+
+// Words like "Петров Водкин" are always inflected separately, hyphenated ones by default
+fn is_separator(c: char, options: &InflectOptions) -> bool {
+    c.is_whitespace() || (options.split_hyphens && c == '-')
+}
+
+/// Segments of the name as the rules see them with the default options, each with the
+/// separator following it, empty for the last one
+pub(crate) fn split_segments(name: &str) -> impl Iterator<Item = (&str, &str)> {
+    let is_separator = |c| is_separator(c, &InflectOptions::DEFAULT);
+    name.split_inclusive(is_separator)
+        .map(move |piece| match piece.char_indices().last() {
+            Some((i, c)) if is_separator(c) => piece.split_at(i),
+            _ => (piece, ""),
         })
 }
 
-fn inflect(name: &str, rule: &Rule, case: Case) -> String {
-    // Get inflection by case
-    if let Some((skip, postfix)) = rule.modifier(case) {
-        name.chars()
-            .take(name.chars().count() - skip)
-            .collect::<String>()
-            + postfix
+// Matches the `index`-th of `count` segments of the name
+#[allow(clippy::too_many_arguments)]
+fn match_segment<'n, 'r>(
+    gender: Gender,
+    name: &'n str,
+    name_part: &'n str,
+    index: usize,
+    count: usize,
+    rule_list: &'r RuleList,
+    filter: &impl TagFilter,
+    options: &InflectOptions,
+) -> SegmentMatch<'n, 'r> {
+    // The separator is the single character before the segment, kept as written
+    let start = name_part.as_ptr() as usize - name.as_ptr() as usize;
+    let separator = name[..start]
+        .chars()
+        .next_back()
+        .map_or("", |c| &name[start - c.len_utf8()..start]);
+    // Pure junk segments are left as is
+    let split = if options.junk == JunkHandling::Detach && name_part.contains(is_junk) {
+        detach_junk(name_part)
     } else {
-        name.to_owned()
+        Some(("", name_part, ""))
+    };
+    let (head, segment, tail) = split.unwrap_or(("", name_part, ""));
+    let context = MatchContext {
+        segment,
+        index,
+        count,
+        name,
+    };
+    // Latin words, as in "Мюллер-Smith", are passed through
+    let rule = split
+        .filter(|_| is_cyrillic_word(segment))
+        .and_then(|_| find_rule(gender, rule_list, &context, filter, options));
+    SegmentMatch {
+        separator,
+        head,
+        segment,
+        tail,
+        rule,
+        casing: if options.preserve_caps {
+            Casing::of(segment)
+        } else {
+            Casing::Lower
+        },
     }
 }
 
-fn inflect_name_part(
+// Byte offset of the separator before the last word when it is a particle like "оглы"
+pub(crate) fn particle_start(
     gender: Gender,
     name: &str,
-    case: Case,
     rule_list: &RuleList,
-    is_last: bool,
-) -> Option<String> {
-    let lowercase_name = name.to_lowercase();
-    // First let's check for exceptions
-    find_exception(rule_list.exceptions, &lowercase_name, gender, is_last)
-        // Then check for suffixes
-        .or(find_suffix(rule_list.suffixes, &lowercase_name, gender))
-        // Then inflect name using matched rule
-        .map(|rule| inflect(name, rule, case))
+    filter: &impl TagFilter,
+    options: &InflectOptions,
+) -> Option<usize> {
+    let (start, separator) = name
+        .char_indices()
+        .rev()
+        .find(|&(_, c)| is_separator(c, options))?;
+    let count = name.matches(|c| is_separator(c, options)).count() + 1;
+    let last = &name[start + separator.len_utf8()..];
+    match_segment(
+        gender,
+        name,
+        last,
+        count - 1,
+        count,
+        rule_list,
+        filter,
+        options,
+    )
+    .rule
+    .filter(|(_, rule)| rule.has_tag(&RuleTag::Particle))
+    .map(|_| start)
 }
 
-fn inflect_name(gender: Gender, name: &str, case: Case, rule_list: &RuleList) -> String {
-    let name_parts: Vec<&str> = name.split('-').collect();
-    name_parts
-        .iter()
+// Matches the segments of the name one at a time, leaving the junk and strict checks of
+// `match_name` to the caller
+fn segment_matches<'a, 'n: 'a, 'r: 'a>(
+    gender: Gender,
+    name: &'n str,
+    rule_list: &'r RuleList,
+    filter: &'a impl TagFilter,
+    options: &'a InflectOptions,
+) -> impl Iterator<Item = SegmentMatch<'n, 'r>> + 'a {
+    let count = name.matches(|c| is_separator(c, options)).count() + 1;
+    // Words before a particle, as in "Ибрагим оглы", keep their nominative form
+    let before_particle = particle_start(gender, name, rule_list, filter, options).is_some();
+    name.split(move |c| is_separator(c, options))
         .enumerate()
-        .map(|(i, &name_part)| {
-            inflect_name_part(
-                gender,
-                name_part,
-                case,
-                rule_list,
-                i == name_parts.len() - 1,
-            )
-            .unwrap_or(name_part.to_owned())
+        .map(move |(i, name_part)| {
+            let mut segment = match_segment(
+                gender, name, name_part, i, count, rule_list, filter, options,
+            );
+            if before_particle && i + 1 < count {
+                segment.rule = None;
+            }
+            segment
         })
-        .collect::<Vec<_>>()
-        .join("-")
+}
+
+// Bytes reserved for endings, enough for the longest one of the embedded rules; names
+// with several inflected segments may still grow the output
+pub(crate) const ENDING_RESERVE: usize = 10;
+
+pub(crate) fn inflect_name(
+    gender: Gender,
+    name: &str,
+    case: Case,
+    rule_list: &RuleList,
+    filter: &impl TagFilter,
+    options: &InflectOptions,
+) -> Result<String, Error> {
+    let name = &*nfc(name);
+    check_junk(name, options)?;
+    let mut output = String::with_capacity(name.len() + ENDING_RESERVE);
+    let mut matched = false;
+    for segment in segment_matches(gender, name, rule_list, filter, options) {
+        matched |= segment.rule.is_some();
+        segment.inflect_into(case, &mut output);
+    }
+    if options.strict && !matched {
+        return Err(Error::NoRuleMatched(name.to_owned()));
+    }
+    Ok(output)
+}
+
+fn inflect_builtin(gender: Gender, name: &str, case: Case, rule_list: &RuleList) -> String {
+    inflect_name(
+        gender,
+        name,
+        case,
+        rule_list,
+        &NoCustomTags,
+        &InflectOptions::DEFAULT,
+    )
+    .unwrap_or_else(|_| name.to_owned())
+}
+
+/// Inflects a name using the rules for the given part
+///
+/// Equivalent to `Inflector::default().inflect(part, gender, name, case)`.
+pub fn inflect(part: NamePart, gender: Gender, name: &str, case: Case) -> String {
+    inflect_cow(part, gender, name, case).into_owned()
+}
+
+/// Inflects a name using the rules for the given part, borrowing it when the inflected
+/// form is the same
+///
+/// Indeclinable names, names no rule matches and forms equal to the nominative are
+/// returned as `Cow::Borrowed` without allocating.
+///
+/// ```
+/// # #[cfg(feature = "lastname-rules")]
+/// # {
+/// use std::borrow::Cow;
+/// use petrovich::{inflect_cow, Case, Gender, NamePart};
+///
+/// let name = inflect_cow(NamePart::Last, Gender::Female, "Станкевич", Case::Dative);
+/// assert!(matches!(name, Cow::Borrowed("Станкевич")));
+/// let name = inflect_cow(NamePart::Last, Gender::Male, "Станкевич", Case::Dative);
+/// assert_eq!(name, "Станкевичу");
+/// # }
+/// ```
+pub fn inflect_cow(part: NamePart, gender: Gender, name: &str, case: Case) -> Cow<'_, str> {
+    let name = match nfc(name) {
+        Cow::Borrowed(name) => name,
+        Cow::Owned(name) => {
+            let mut output = String::with_capacity(name.len() + ENDING_RESERVE);
+            // Writing to a String never fails
+            let _ = inflect_into(part, gender, &name, case, &mut output);
+            return Cow::Owned(output);
+        }
+    };
+    let mut output: Option<String> = None;
+    for segment in segment_matches(
+        gender,
+        name,
+        RULES.list(part),
+        &NoCustomTags,
+        &InflectOptions::DEFAULT,
+    ) {
+        match &mut output {
+            Some(output) => segment.inflect_into(case, output),
+            None if segment.changes(case) => {
+                // Segments up to this one are copied as they are
+                let start = segment.segment.as_ptr() as usize
+                    - name.as_ptr() as usize
+                    - segment.head.len()
+                    - segment.separator.len();
+                let mut inflected = String::with_capacity(name.len() + ENDING_RESERVE);
+                inflected.push_str(&name[..start]);
+                segment.inflect_into(case, &mut inflected);
+                output = Some(inflected);
+            }
+            None => {}
+        }
+    }
+    output.map_or(Cow::Borrowed(name), Cow::Owned)
+}
+
+/// Inflects first name, borrowing it when unchanged, see `inflect_cow`
+#[cfg(feature = "firstname-rules")]
+pub fn firstname_cow(gender: Gender, name: &str, case: Case) -> Cow<'_, str> {
+    inflect_cow(NamePart::First, gender, name, case)
+}
+
+/// Inflects last name, borrowing it when unchanged, see `inflect_cow`
+#[cfg(feature = "lastname-rules")]
+pub fn lastname_cow(gender: Gender, name: &str, case: Case) -> Cow<'_, str> {
+    inflect_cow(NamePart::Last, gender, name, case)
+}
+
+/// Inflects middle name, borrowing it when unchanged, see `inflect_cow`
+#[cfg(feature = "middlename-rules")]
+pub fn middlename_cow(gender: Gender, name: &str, case: Case) -> Cow<'_, str> {
+    inflect_cow(NamePart::Middle, gender, name, case)
 }
 
 /// Inflects first name
+///
+/// Double first names like "Анна Мария" or "Жан-Поль" decline word by word, so
+/// indeclinable words like "Жозе" stay as they are: "Анне Марии", "Марии Жозе".
+/// Equivalent to `Inflector::default().firstname(gender, name, case)`.
+#[cfg(feature = "firstname-rules")]
 pub fn firstname(gender: Gender, name: &str, case: Case) -> String {
-    inflect_name(gender, name, case, &RULES.firstname)
+    inflect(NamePart::First, gender, name, case)
 }
 
 /// Inflects last name
+///
+/// Equivalent to `Inflector::default().lastname(gender, name, case)`.
+#[cfg(feature = "lastname-rules")]
 pub fn lastname(gender: Gender, name: &str, case: Case) -> String {
-    inflect_name(gender, name, case, &RULES.lastname)
+    inflect(NamePart::Last, gender, name, case)
 }
 
 /// Inflects middle name
+///
+/// Equivalent to `Inflector::default().middlename(gender, name, case)`.
+#[cfg(feature = "middlename-rules")]
 pub fn middlename(gender: Gender, name: &str, case: Case) -> String {
-    inflect_name(gender, name, case, &RULES.middlename)
+    inflect(NamePart::Middle, gender, name, case)
+}
+
+/// Whether the rules for the given part change the name in at least one case
+///
+/// Invariant surnames like "Черных", "Дурново" or "Живаго", female surnames ending in a
+/// consonant and names no rule matches are not declinable. A hyphenated name is declinable
+/// when any of its parts is.
+///
+/// ```
+/// # #[cfg(feature = "lastname-rules")]
+/// # {
+/// use petrovich::{is_declinable, Gender, NamePart};
+///
+/// assert!(is_declinable(NamePart::Last, Gender::Male, "Иванов"));
+/// assert!(!is_declinable(NamePart::Last, Gender::Male, "Черных"));
+/// assert!(!is_declinable(NamePart::Last, Gender::Female, "Станкевич"));
+/// # }
+/// ```
+pub fn is_declinable(part: NamePart, gender: Gender, name: &str) -> bool {
+    let name = nfc(name);
+    let declinable = segment_matches(
+        gender,
+        &name,
+        RULES.list(part),
+        &NoCustomTags,
+        &InflectOptions::DEFAULT,
+    )
+    .any(|segment| Case::iter().any(|case| segment.changes(case)));
+    declinable
+}
+
+/// Writes a name inflected using the rules for the given part, without allocating
+/// anything but the lowercase copies used to look up rules
+///
+/// ```
+/// # #[cfg(feature = "firstname-rules")]
+/// # {
+/// use petrovich::{inflect_into, Case, Gender, NamePart};
+///
+/// let mut greeting = String::from("Дорогой ");
+/// inflect_into(NamePart::First, Gender::Male, "Лёша", Case::Dative, &mut greeting).unwrap();
+/// assert_eq!(greeting, "Дорогой Лёше");
+/// # }
+/// ```
+pub fn inflect_into(
+    part: NamePart,
+    gender: Gender,
+    name: &str,
+    case: Case,
+    output: &mut impl fmt::Write,
+) -> fmt::Result {
+    let name = &*nfc(name);
+    for segment in segment_matches(
+        gender,
+        name,
+        RULES.list(part),
+        &NoCustomTags,
+        &InflectOptions::DEFAULT,
+    ) {
+        segment.write_into(case, output)?;
+    }
+    Ok(())
+}
+
+/// Writes inflected first name, see `inflect_into`
+#[cfg(feature = "firstname-rules")]
+pub fn firstname_into(
+    gender: Gender,
+    name: &str,
+    case: Case,
+    output: &mut impl fmt::Write,
+) -> fmt::Result {
+    inflect_into(NamePart::First, gender, name, case, output)
+}
+
+/// Writes inflected last name, see `inflect_into`
+#[cfg(feature = "lastname-rules")]
+pub fn lastname_into(
+    gender: Gender,
+    name: &str,
+    case: Case,
+    output: &mut impl fmt::Write,
+) -> fmt::Result {
+    inflect_into(NamePart::Last, gender, name, case, output)
+}
+
+/// Writes inflected middle name, see `inflect_into`
+#[cfg(feature = "middlename-rules")]
+pub fn middlename_into(
+    gender: Gender,
+    name: &str,
+    case: Case,
+    output: &mut impl fmt::Write,
+) -> fmt::Result {
+    inflect_into(NamePart::Middle, gender, name, case, output)
+}
+
+/// Inflects first name, detecting gender from the name itself
+///
+/// Names the heuristics don't know are inflected with androgynous rules only. Detection
+/// from a single part is weaker than from a full name, see `detect_gender`.
+#[cfg(all(feature = "firstname-rules", feature = "gender-detection"))]
+pub fn firstname_auto(name: &str, case: Case) -> String {
+    firstname(detect_gender(None, Some(name), None), name, case)
+}
+
+/// Inflects last name, detecting gender from the name itself
+///
+/// Last name suffixes are a much weaker hint than a middle name: "Цой" or "Шевченко"
+/// say nothing about gender. Prefer `fullname` when other parts are available.
+#[cfg(all(feature = "lastname-rules", feature = "gender-detection"))]
+pub fn lastname_auto(name: &str, case: Case) -> String {
+    lastname(detect_gender(Some(name), None, None), name, case)
+}
+
+/// Inflects middle name, detecting gender from the name itself
+#[cfg(all(feature = "middlename-rules", feature = "gender-detection"))]
+pub fn middlename_auto(name: &str, case: Case) -> String {
+    middlename(detect_gender(None, None, Some(name)), name, case)
+}
+
+/// Inflects a name whose part is unknown
+///
+/// Middle name rules are tried first, as patronymic suffixes are the most distinctive,
+/// then last name and first name rules. Returns the inflected name together with the part
+/// whose rules matched; a name no rule matches is returned unchanged with `NamePart::Last`.
+pub fn inflect_any(gender: Gender, name: &str, case: Case) -> (String, NamePart) {
+    let name = &*nfc(name);
+    for &part in &[NamePart::Middle, NamePart::Last, NamePart::First] {
+        if let Ok(segments) = match_name(
+            gender,
+            name,
+            RULES.list(part),
+            &NoCustomTags,
+            &InflectOptions::DEFAULT,
+        ) {
+            if segments.iter().any(|segment| segment.rule.is_some()) {
+                let mut output = String::with_capacity(name.len() + ENDING_RESERVE);
+                for segment in segments {
+                    segment.inflect_into(case, &mut output);
+                }
+                return (output, part);
+            }
+        }
+    }
+    (name.to_owned(), NamePart::Last)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(all(
+        feature = "firstname-rules",
+        feature = "lastname-rules",
+        feature = "middlename-rules"
+    ))]
     #[test]
     fn should_pass_through_uninflected() {
         assert_eq!(firstname(Gender::Male, "Blabla", Case::Genitive), "Blabla");
@@ -207,6 +922,7 @@ mod tests {
         assert_eq!(lastname(Gender::Male, "Blabla", Case::Genitive), "Blabla");
     }
 
+    #[cfg(feature = "firstname-rules")]
     #[test]
     fn should_inflect_first_names() {
         assert_eq!(firstname(Gender::Male, "Лёша", Case::Genitive), "Лёши");
@@ -226,6 +942,502 @@ mod tests {
         );
     }
 
+    #[cfg(all(feature = "firstname-rules", feature = "gender-detection"))]
+    #[test]
+    fn should_drop_fleeting_vowels() {
+        for &(name, forms) in &[
+            ("Лев", ["Льва", "Льву", "Льва", "Львом", "Льве"]),
+            ("Павел", ["Павла", "Павлу", "Павла", "Павлом", "Павле"]),
+            ("Пётр", ["Петра", "Петру", "Петра", "Петром", "Петре"]),
+            ("Семён", ["Семёна", "Семёну", "Семёна", "Семёном", "Семёне"]),
+        ] {
+            let cases = [
+                Case::Genitive,
+                Case::Dative,
+                Case::Accusative,
+                Case::Instrumental,
+                Case::Prepositional,
+            ];
+            for (&case, &form) in cases.iter().zip(forms.iter()) {
+                assert_eq!(firstname(Gender::Male, name, case), form);
+            }
+            assert_eq!(detect_gender(None, Some(name), None), Gender::Male);
+        }
+        assert_eq!(firstname(Gender::Male, "ЛЕВ", Case::Dative), "ЛЬВУ");
+        assert_eq!(
+            firstname(Gender::Male, "Пётр-Павел", Case::Genitive),
+            "Петра-Павла"
+        );
+    }
+
+    #[cfg(feature = "firstname-rules")]
+    #[test]
+    fn should_keep_borrowed_names_with_stressed_endings() {
+        for &name in &["Николя", "Франсуа", "Луи", "Рене", "Тома"] {
+            for &case in &[Case::Genitive, Case::Dative, Case::Instrumental] {
+                assert_eq!(firstname(Gender::Male, name, case), name);
+            }
+        }
+        assert_eq!(
+            firstname(Gender::Male, "Жан-Николя", Case::Dative),
+            "Жану-Николя"
+        );
+    }
+
+    #[cfg(feature = "firstname-rules")]
+    #[test]
+    fn should_keep_unlisted_names_with_stressed_endings() {
+        for &name in &["Анри", "Амеде", "Пьеру", "Мэтью", "Жюли"] {
+            for case in Case::iter() {
+                assert_eq!(firstname(Gender::Male, name, case), name);
+                assert_eq!(firstname(Gender::Female, name, case), name);
+            }
+        }
+        assert_eq!(
+            firstname(Gender::Male, "Анри-Николя", Case::Instrumental),
+            "Анри-Николя"
+        );
+    }
+
+    #[cfg(feature = "firstname-rules")]
+    #[test]
+    fn should_decline_native_names_with_vowel_endings() {
+        assert_eq!(firstname(Gender::Male, "Илья", Case::Dative), "Илье");
+        assert_eq!(firstname(Gender::Male, "Илья", Case::Instrumental), "Ильёй");
+        assert_eq!(firstname(Gender::Male, "Коля", Case::Dative), "Коле");
+        assert_eq!(firstname(Gender::Male, "Толя", Case::Genitive), "Толи");
+        assert_eq!(firstname(Gender::Male, "Добрыня", Case::Dative), "Добрыне");
+        assert_eq!(firstname(Gender::Female, "Тома", Case::Dative), "Томе");
+        assert_eq!(firstname(Gender::Female, "Оля", Case::Dative), "Оле");
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_drop_fleeting_vowels_in_lastnames() {
+        for &(name, genitive, instrumental) in &[
+            ("Починок", "Починка", "Починком"),
+            ("Конёк", "Конька", "Коньком"),
+            ("Королёк", "Королька", "Корольком"),
+            ("Заяц", "Зайца", "Зайцем"),
+            ("Боец", "Бойца", "Бойцем"),
+            ("Блок", "Блока", "Блоком"),
+            ("Сок", "Сока", "Соком"),
+        ] {
+            assert_eq!(lastname(Gender::Male, name, Case::Genitive), genitive);
+            assert_eq!(
+                lastname(Gender::Male, name, Case::Instrumental),
+                instrumental
+            );
+            assert_eq!(lastname(Gender::Female, name, Case::Genitive), name);
+        }
+    }
+
+    #[cfg(feature = "firstname-rules")]
+    #[test]
+    fn should_prefer_first_listed_suffix_rule() {
+        // "ш, ж" come before the general consonant rule that also lists "ж"
+        assert_eq!(
+            firstname(Gender::Male, "Серж", Case::Instrumental),
+            "Сержем"
+        );
+    }
+
+    #[cfg(all(
+        feature = "firstname-rules",
+        feature = "lastname-rules",
+        feature = "middlename-rules"
+    ))]
+    #[test]
+    fn should_keep_invariant_lastnames() {
+        for &name in &["Черных", "Долгих", "Седых", "Дурново", "Хитрово", "Живаго"]
+        {
+            for &gender in &[Gender::Male, Gender::Female] {
+                for case in Case::iter() {
+                    assert_eq!(lastname(gender, name, case), name);
+                }
+                assert!(!is_declinable(NamePart::Last, gender, name));
+            }
+        }
+        assert!(is_declinable(NamePart::Last, Gender::Male, "Черных-Иванов"));
+        assert!(!is_declinable(NamePart::First, Gender::Male, "Blabla"));
+        assert!(is_declinable(NamePart::Middle, Gender::Female, "Сергеевна"));
+    }
+
+    #[cfg(all(feature = "lastname-rules", feature = "gender-detection"))]
+    #[test]
+    fn should_keep_foreign_lastnames_with_stressed_endings() {
+        for &name in &["Дюма", "Золя", "Ферма", "Моравиа"] {
+            for &gender in &[Gender::Male, Gender::Female] {
+                for case in Case::iter() {
+                    assert_eq!(lastname(gender, name, case), name);
+                }
+            }
+            assert_ne!(detect_gender(Some(name), None, None), Gender::Female);
+        }
+        assert_eq!(
+            detect_gender(Some("Золя"), Some("Эмиль"), None),
+            Gender::Male
+        );
+        assert_eq!(lastname(Gender::Male, "Сирота", Case::Dative), "Сироте");
+        assert_eq!(lastname(Gender::Female, "Сирота", Case::Genitive), "Сироты");
+        assert_eq!(
+            lastname(Gender::Male, "Окуджава", Case::Instrumental),
+            "Окуджавой"
+        );
+        assert_eq!(
+            lastname(Gender::Female, "Окуджава", Case::Dative),
+            "Окуджаве"
+        );
+    }
+
+    #[test]
+    fn should_cut_whole_segment_for_long_modifiers() {
+        let inflector = Inflector::new(Rules::new(
+            RuleList::new(
+                vec![],
+                vec![Rule::new(
+                    Gender::Male,
+                    vec!["ёв"],
+                    ["----а", "-у", "ым", "--", "."],
+                )],
+            ),
+            RuleList::new(vec![], vec![]),
+            RuleList::new(vec![], vec![]),
+        ));
+        assert_eq!(inflector.lastname(Gender::Male, "Ёв", Case::Genitive), "а");
+        assert_eq!(inflector.lastname(Gender::Male, "Ёв", Case::Dative), "Ёу");
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Лёв-Ёв", Case::Genitive),
+            "а-а"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Лёв-Ёв", Case::Accusative),
+            "Лёвым-Ёвым"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Лёв-Ёв", Case::Instrumental),
+            "Л-"
+        );
+    }
+
+    #[test]
+    fn should_classify_casing() {
+        assert_eq!(Casing::of("иванов"), Casing::Lower);
+        assert_eq!(Casing::of("Иванов"), Casing::Title);
+        assert_eq!(Casing::of("ИВАНОВ"), Casing::Upper);
+        assert_eq!(Casing::of("МакГрегор"), Casing::Mixed);
+        assert_eq!(Casing::of("иВАНОВ"), Casing::Mixed);
+        assert_eq!(Casing::of("О'Нил"), Casing::Mixed);
+        assert_eq!(Casing::of("123"), Casing::Lower);
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_keep_internal_capitals() {
+        assert_eq!(
+            lastname(Gender::Male, "МакГрегор", Case::Dative),
+            "МакГрегору"
+        );
+        assert_eq!(
+            lastname(Gender::Male, "МакГрегор", Case::Instrumental),
+            "МакГрегором"
+        );
+        assert_eq!(
+            lastname(Gender::Male, "МАКГРЕГОР", Case::Dative),
+            "МАКГРЕГОРУ"
+        );
+        assert_eq!(
+            lastname(Gender::Male, "Мак-Грегор", Case::Dative),
+            "Мак-Грегору"
+        );
+        assert_eq!(
+            lastname(Gender::Female, "Мак-Грегор", Case::Dative),
+            "Мак-Грегор"
+        );
+        assert_eq!(
+            lastname(Gender::Male, "МАК-ГРЕГОР", Case::Instrumental),
+            "МАК-ГРЕГОРОМ"
+        );
+        for &gender in &[Gender::Male, Gender::Female] {
+            for case in Case::iter() {
+                assert_eq!(lastname(gender, "ДиКаприо", case), "ДиКаприо");
+            }
+        }
+        let options = InflectOptions {
+            normalize: true,
+            ..InflectOptions::default()
+        };
+        assert_eq!(
+            Inflector::default().with_options(options).lastname(
+                Gender::Male,
+                " МакГрегор ",
+                Case::Dative
+            ),
+            "МакГрегору"
+        );
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_inflect_caucasian_lastnames() {
+        let cases = [
+            Case::Genitive,
+            Case::Dative,
+            Case::Accusative,
+            Case::Instrumental,
+            Case::Prepositional,
+        ];
+        let forms = [
+            "Петросяна",
+            "Петросяну",
+            "Петросяна",
+            "Петросяном",
+            "Петросяне",
+        ];
+        for (&case, &form) in cases.iter().zip(forms.iter()) {
+            assert_eq!(lastname(Gender::Male, "Петросян", case), form);
+        }
+        for &name in &["Джугашвили", "Кикабидзе"] {
+            for &gender in &[Gender::Male, Gender::Female] {
+                for case in Case::iter() {
+                    assert_eq!(lastname(gender, name, case), name);
+                }
+            }
+        }
+        for case in Case::iter() {
+            assert_eq!(lastname(Gender::Female, "Петросян", case), "Петросян");
+        }
+        assert_eq!(
+            lastname(Gender::Male, "Петросян-Кикабидзе", Case::Dative),
+            "Петросяну-Кикабидзе"
+        );
+        assert_eq!(
+            lastname(Gender::Female, "Иванова-Петросян", Case::Dative),
+            "Ивановой-Петросян"
+        );
+        assert_eq!(
+            lastname(Gender::Male, "Берия", Case::Instrumental),
+            "Берией"
+        );
+    }
+
+    #[cfg(all(feature = "lastname-rules", feature = "gender-detection"))]
+    #[test]
+    fn should_defer_to_first_name_for_caucasian_lastnames() {
+        for &name in &["Петросян", "Джугашвили", "Кикабидзе", "Данелия"]
+        {
+            assert_eq!(detect_gender(Some(name), None, None), Gender::Androgynous);
+            assert_eq!(
+                detect_gender(Some(name), Some("Анна"), None),
+                Gender::Female
+            );
+            assert_eq!(
+                detect_gender(Some(name), None, Some("Арменович")),
+                Gender::Male
+            );
+        }
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_inflect_space_separated_lastnames() {
+        assert_eq!(
+            lastname(Gender::Male, "Петров Водкин", Case::Genitive),
+            "Петрова Водкина"
+        );
+        assert_eq!(
+            lastname(Gender::Male, "Гарсиа Маркес", Case::Dative),
+            "Гарсиа Маркесу"
+        );
+        // Exceptions tagged first_word apply to the leading words only
+        assert_eq!(
+            lastname(Gender::Male, "Вар Иванов", Case::Instrumental),
+            "Вар Ивановым"
+        );
+        assert_eq!(
+            lastname(Gender::Male, "Фон Браун-Тер", Case::Genitive),
+            "Фон Брауна-Тера"
+        );
+        // Separators are kept as written
+        assert_eq!(
+            lastname(
+                Gender::Male,
+                "Петров\u{a0}Водкин  Бонч-Бруевич",
+                Case::Dative
+            ),
+            "Петрову\u{a0}Водкину  Бонч-Бруевичу"
+        );
+        assert_eq!(
+            lastname_cow(Gender::Female, "Петрова Водкина", Case::Nominative),
+            Cow::Borrowed("Петрова Водкина")
+        );
+    }
+
+    #[cfg(feature = "firstname-rules")]
+    #[test]
+    fn should_inflect_space_separated_firstnames() {
+        for (case, expected) in Case::iter().zip(&[
+            "Анна Мария",
+            "Анны Марии",
+            "Анне Марии",
+            "Анну Марию",
+            "Анной Марией",
+            "Анне Марии",
+        ]) {
+            assert_eq!(firstname(Gender::Female, "Анна Мария", case), *expected);
+        }
+        assert_eq!(
+            firstname(Gender::Male, "Жан Поль", Case::Genitive),
+            "Жана Поля"
+        );
+        assert_eq!(
+            firstname(Gender::Female, "Мария Жозе", Case::Genitive),
+            "Марии Жозе"
+        );
+        assert_eq!(
+            firstname(Gender::Female, "Анна\u{a0}Мария", Case::Genitive),
+            "Анны\u{a0}Марии"
+        );
+        // Single words are inflected as before
+        assert_eq!(firstname(Gender::Female, "Анна", Case::Dative), "Анне");
+        assert_eq!(
+            firstname_cow(Gender::Female, "Анна Мария", Case::Nominative),
+            Cow::Borrowed("Анна Мария")
+        );
+    }
+
+    #[cfg(all(feature = "gender-detection", feature = "firstname-rules"))]
+    #[test]
+    fn should_detect_gender_of_double_firstnames() {
+        assert_eq!(
+            detect_gender(None, Some("Анна Мария"), None),
+            Gender::Female
+        );
+        assert_eq!(detect_gender(None, Some("Жан Поль"), None), Gender::Male);
+        // "Жозе" tells nothing, the other word decides
+        assert_eq!(
+            detect_gender(None, Some("Мария Жозе"), None),
+            Gender::Female
+        );
+        // Words pointing to both genders cancel out
+        assert_eq!(detect_gender(None, Some("Жан Анна"), None), Gender::Unknown);
+    }
+
+    #[cfg(all(feature = "firstname-rules", feature = "lastname-rules"))]
+    #[test]
+    fn should_pass_latin_words_through() {
+        for &case in Case::ALL.iter() {
+            assert_eq!(lastname(Gender::Male, "Smith", case), "Smith");
+            assert_eq!(firstname(Gender::Female, "Anna", case), "Anna");
+        }
+        assert_eq!(
+            lastname(Gender::Male, "Мюллер-Smith", Case::Dative),
+            "Мюллеру-Smith"
+        );
+        assert_eq!(
+            firstname(Gender::Female, "Анна-Marie", Case::Genitive),
+            "Анны-Marie"
+        );
+        assert_eq!(
+            lastname(Gender::Male, "von Штиглиц", Case::Instrumental),
+            "von Штиглицем"
+        );
+        // A word mixing scripts isn't Russian either
+        assert_eq!(lastname(Gender::Male, "Ivanов", Case::Dative), "Ivanов");
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_keep_nobiliary_particles() {
+        assert_eq!(
+            lastname(Gender::Male, "фон Штиглиц", Case::Dative),
+            "фон Штиглицу"
+        );
+        assert_eq!(
+            lastname(Gender::Male, "ван дер Берг", Case::Instrumental),
+            "ван дер Бергом"
+        );
+        assert_eq!(lastname(Gender::Male, "Де Голль", Case::Dative), "Де Голлю");
+        assert_eq!(
+            lastname(Gender::Male, "Фон-Визин", Case::Genitive),
+            "Фон-Визина"
+        );
+        assert_eq!(
+            lastname(Gender::Male, "ДЕ ЛА ТУР", Case::Genitive),
+            "ДЕ ЛА ТУРА"
+        );
+    }
+
+    #[cfg(feature = "middlename-rules")]
+    #[test]
+    fn should_keep_patronymic_particles() {
+        for &case in Case::ALL.iter() {
+            for &name in &["Ибрагим оглы", "Гусейн-Оглу", "ПОЛИНА КЫЗЫ", "Зульфия гызы"]
+            {
+                assert_eq!(middlename(Gender::Male, name, case), name);
+                assert_eq!(middlename(Gender::Female, name, case), name);
+            }
+        }
+        assert!(!is_declinable(
+            NamePart::Middle,
+            Gender::Female,
+            "Полина кызы"
+        ));
+        // Only the particle is matched, the name before it is passed through
+        assert_eq!(
+            try_middlename(Gender::Male, "Ибрагим оглы", Case::Dative),
+            Some(Matched::Partial("Ибрагим оглы".to_owned()))
+        );
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_keep_apostrophes_inside_lastnames() {
+        for &apostrophe in &["’", "'", "`"] {
+            let dartagnan = format!("Д{}Артаньян", apostrophe);
+            let oneil = format!("О{}Нил", apostrophe);
+            assert_eq!(
+                lastname(Gender::Male, &dartagnan, Case::Dative),
+                format!("Д{}Артаньяну", apostrophe)
+            );
+            assert_eq!(
+                lastname(Gender::Male, &oneil, Case::Genitive),
+                format!("О{}Нила", apostrophe)
+            );
+            assert_eq!(lastname(Gender::Female, &oneil, Case::Genitive), oneil);
+        }
+        let inflector = Inflector::default().with_options(InflectOptions {
+            junk: JunkHandling::Reject,
+            ..InflectOptions::DEFAULT
+        });
+        assert_eq!(
+            inflector.lastname_checked(Gender::Male, "О`Нил", Case::Dative),
+            Ok("О`Нилу".to_owned())
+        );
+    }
+
+    #[test]
+    fn should_match_any_apostrophe_in_rules() {
+        let rules = Rules::new(
+            RuleList::new(
+                vec![Rule::new(
+                    Gender::Androgynous,
+                    vec!["д’арк"],
+                    [".", ".", ".", ".", "."],
+                )],
+                vec![],
+            ),
+            RuleList::new(vec![], vec![]),
+            RuleList::new(vec![], vec![]),
+        );
+        let inflector = Inflector::new(rules);
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Д'Арк", Case::Dative),
+            "Д'Арк"
+        );
+    }
+
+    #[cfg(feature = "lastname-rules")]
     #[test]
     fn should_inflect_complex_male_lastnames() {
         assert_eq!(lastname(Gender::Male, "Кваша", Case::Genitive), "Кваши");
@@ -268,7 +1480,7 @@ mod tests {
         );
         assert_eq!(
             lastname(Gender::Male, "Петров Водкин", Case::Dative),
-            "Петров Водкину"
+            "Петрову Водкину"
         );
         assert_eq!(lastname(Gender::Male, "Бонч", Case::Dative), "Бончу");
         assert_eq!(
@@ -277,6 +1489,7 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "middlename-rules")]
     #[test]
     fn should_inflect_middlenames() {
         assert_eq!(middlename(Gender::Male, "фон", Case::Genitive), "фон");
@@ -291,6 +1504,7 @@ mod tests {
         )
     }
 
+    #[cfg(feature = "lastname-rules")]
     #[test]
     fn should_inflect_complex_female_lastnames() {
         assert_eq!(lastname(Gender::Female, "Бильжо", Case::Dative), "Бильжо");
@@ -341,9 +1555,229 @@ mod tests {
         );
     }
 
+    #[cfg(all(feature = "firstname-rules", feature = "lastname-rules"))]
+    #[test]
+    fn should_inflect_unknown_as_androgynous() {
+        for &(name, case) in &[("Саша", Case::Dative), ("Иванов", Case::Genitive)] {
+            assert_eq!(
+                firstname(Gender::Unknown, name, case),
+                firstname(Gender::Androgynous, name, case)
+            );
+            assert_eq!(
+                lastname(Gender::Unknown, name, case),
+                lastname(Gender::Androgynous, name, case)
+            );
+        }
+    }
+
+    #[cfg(all(
+        feature = "firstname-rules",
+        feature = "lastname-rules",
+        feature = "middlename-rules"
+    ))]
+    #[test]
+    fn should_keep_nominative() {
+        assert_eq!(firstname(Gender::Male, "Саша", Case::Nominative), "Саша");
+        assert_eq!(
+            lastname(Gender::Female, "Иванова-Сидорова", Case::Nominative),
+            "Иванова-Сидорова"
+        );
+        assert_eq!(
+            middlename(Gender::Male, "Сергеич", Case::Nominative),
+            "Сергеич"
+        );
+    }
+
+    #[cfg(all(
+        feature = "firstname-rules",
+        feature = "lastname-rules",
+        feature = "middlename-rules"
+    ))]
+    #[test]
+    fn should_inflect_by_part() {
+        for &part in &[NamePart::First, NamePart::Last, NamePart::Middle] {
+            let name = "Петрович";
+            let expected = match part {
+                NamePart::First => firstname(Gender::Male, name, Case::Dative),
+                NamePart::Last => lastname(Gender::Male, name, Case::Dative),
+                NamePart::Middle => middlename(Gender::Male, name, Case::Dative),
+            };
+            assert_eq!(inflect(part, Gender::Male, name, Case::Dative), expected);
+        }
+        assert_eq!(
+            inflect(
+                "фамилия".parse().unwrap(),
+                Gender::Female,
+                "Иванова",
+                Case::Dative
+            ),
+            "Ивановой"
+        );
+    }
+
+    #[cfg(all(feature = "firstname-rules", feature = "lastname-rules"))]
+    #[test]
+    fn should_match_letter_case_of_endings() {
+        assert_eq!(lastname(Gender::Male, "ИВАНОВ", Case::Dative), "ИВАНОВУ");
+        assert_eq!(lastname(Gender::Male, "Иванов", Case::Dative), "Иванову");
+        assert_eq!(lastname(Gender::Male, "иванов", Case::Dative), "иванову");
+        assert_eq!(
+            lastname(Gender::Male, "ИВАНОВ-СИДОРОВ", Case::Dative),
+            "ИВАНОВУ-СИДОРОВУ"
+        );
+        assert_eq!(
+            firstname(Gender::Female, "АННА-МАРИЯ", Case::Genitive),
+            "АННЫ-МАРИИ"
+        );
+        assert_eq!(lastname(Gender::Male, "ИвАнОв", Case::Dative), "ИвАнОву");
+        assert!(matches!(
+            lastname_cow(Gender::Female, "ИВАНОВА", Case::Nominative),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[cfg(all(feature = "firstname-rules", feature = "lastname-rules"))]
+    #[test]
+    fn should_match_yo_as_ye() {
+        // Exception spelled "пётр"
+        assert_eq!(firstname(Gender::Male, "Петр", Case::Genitive), "Петра");
+        assert_eq!(firstname(Gender::Male, "Пётр", Case::Dative), "Петру");
+        // Exception spelled "маненок"
+        assert_eq!(lastname(Gender::Male, "Манёнок", Case::Genitive), "Манёнок");
+        // Suffix spelled "ен" keeps the ё of the stem
+        assert_eq!(
+            lastname(Gender::Male, "Зелён", Case::Instrumental),
+            "Зелёном"
+        );
+        // Endings without ё aren't affected
+        assert_eq!(firstname(Gender::Male, "Артем", Case::Genitive), "Артема");
+        assert_eq!(firstname(Gender::Male, "Артём", Case::Genitive), "Артёма");
+    }
+
+    #[cfg(all(
+        feature = "firstname-rules",
+        feature = "lastname-rules",
+        feature = "gender-detection"
+    ))]
+    #[test]
+    fn should_compose_decomposed_letters() {
+        let alyosha = "Але\u{308}ша";
+        let tchaikovsky = "Чаи\u{306}ковский";
+        assert_eq!(firstname(Gender::Male, alyosha, Case::Genitive), "Алёши");
+        assert_eq!(
+            lastname(Gender::Male, tchaikovsky, Case::Dative),
+            "Чайковскому"
+        );
+        assert_eq!(
+            lastname_cow(Gender::Male, tchaikovsky, Case::Nominative),
+            "Чайковский"
+        );
+        let mut output = String::new();
+        firstname_into(Gender::Male, alyosha, Case::Instrumental, &mut output).unwrap();
+        assert_eq!(output, "Алёшей");
+        assert_eq!(detect_gender(Some(tchaikovsky), None, None), Gender::Male);
+    }
+
+    #[cfg(all(
+        feature = "firstname-rules",
+        feature = "lastname-rules",
+        feature = "middlename-rules"
+    ))]
+    #[test]
+    fn should_write_into_buffer() {
+        let mut output = String::from("к ");
+        lastname_into(Gender::Male, "Бонч-Бруевич", Case::Dative, &mut output).unwrap();
+        output.push_str(", ");
+        firstname_into(Gender::Female, "Анна-Мария", Case::Dative, &mut output).unwrap();
+        output.push(' ');
+        middlename_into(Gender::Female, "Blabla", Case::Dative, &mut output).unwrap();
+        assert_eq!(output, "к Бонч-Бруевичу, Анне-Марии Blabla");
+
+        for &(gender, name) in &[(Gender::Male, "Лёша"), (Gender::Female, "Иванова-Сидорова")]
+        {
+            for case in Case::iter() {
+                let mut output = String::new();
+                inflect_into(NamePart::Last, gender, name, case, &mut output).unwrap();
+                assert_eq!(output, lastname(gender, name, case));
+            }
+        }
+    }
+
+    #[cfg(all(
+        feature = "firstname-rules",
+        feature = "lastname-rules",
+        feature = "middlename-rules"
+    ))]
+    #[test]
+    fn should_borrow_unchanged_names() {
+        assert!(matches!(
+            lastname_cow(Gender::Female, "Станкевич", Case::Genitive),
+            Cow::Borrowed("Станкевич")
+        ));
+        assert!(matches!(
+            firstname_cow(Gender::Male, "Blabla", Case::Dative),
+            Cow::Borrowed(_)
+        ));
+        assert!(matches!(
+            middlename_cow(Gender::Male, "Сергеевич", Case::Nominative),
+            Cow::Borrowed(_)
+        ));
+        assert_eq!(
+            lastname_cow(Gender::Female, "Станкевич-Иванова", Case::Genitive),
+            "Станкевич-Ивановой"
+        );
+        assert_eq!(
+            lastname_cow(Gender::Male, "Иванов-Blabla", Case::Genitive),
+            "Иванова-Blabla"
+        );
+        assert_eq!(
+            lastname_cow(Gender::Male, "Бонч-Бруевич", Case::Genitive),
+            "Бонч-Бруевича"
+        );
+    }
+
+    #[cfg(all(
+        feature = "firstname-rules",
+        feature = "lastname-rules",
+        feature = "middlename-rules",
+        feature = "gender-detection"
+    ))]
+    #[test]
+    fn should_detect_gender_automatically() {
+        assert_eq!(lastname_auto("Иванова", Case::Dative), "Ивановой");
+        assert_eq!(lastname_auto("Иванов", Case::Dative), "Иванову");
+        assert_eq!(lastname_auto("Шевченко", Case::Dative), "Шевченко");
+        assert_eq!(firstname_auto("Анна", Case::Genitive), "Анны");
+        assert_eq!(firstname_auto("Олег", Case::Genitive), "Олега");
+        assert_eq!(middlename_auto("Сергеевна", Case::Dative), "Сергеевне");
+        assert_eq!(middlename_auto("Сергеевич", Case::Dative), "Сергеевичу");
+    }
+
+    #[cfg(all(feature = "lastname-rules", feature = "middlename-rules"))]
+    #[test]
+    fn should_inflect_any_part() {
+        assert_eq!(
+            inflect_any(Gender::Male, "Петрович", Case::Dative),
+            ("Петровичу".to_owned(), NamePart::Middle)
+        );
+        assert_eq!(
+            inflect_any(Gender::Female, "Иванова", Case::Dative),
+            ("Ивановой".to_owned(), NamePart::Last)
+        );
+        assert_eq!(
+            inflect_any(Gender::Male, "Саша", Case::Dative),
+            ("Саше".to_owned(), NamePart::Last)
+        );
+        assert_eq!(
+            inflect_any(Gender::Male, "Qwerty", Case::Dative),
+            ("Qwerty".to_owned(), NamePart::Last)
+        );
+    }
+
+    #[cfg(feature = "gender-detection")]
     #[test]
     fn should_detect_gender() {
-        assert_eq!(detect_gender(None, None, None), Gender::Androgynous);
+        assert_eq!(detect_gender(None, None, None), Gender::Unknown);
         assert_eq!(detect_gender(None, Some("Александр"), None), Gender::Male);
         assert_eq!(
             detect_gender(Some("Склифасовский"), None, None),
@@ -378,26 +1812,36 @@ mod tests {
             detect_gender(None, Some("Саша"), Some("Олегович")),
             Gender::Male
         );
+        assert_eq!(detect_gender(Some("Осипчук"), None, None), Gender::Unknown);
         assert_eq!(
-            detect_gender(Some("Осипчук"), None, None),
+            detect_gender(Some("Осипчук"), Some("Саша"), None),
             Gender::Androgynous
         );
+        assert_eq!(
+            detect_gender(Some("Qwerty"), Some("Blabla"), None),
+            Gender::Unknown
+        );
         assert_eq!(detect_gender(None, None, Some("Олегович")), Gender::Male);
         assert_eq!(detect_gender(None, None, Some("Олеговна")), Gender::Female);
         assert_eq!(detect_gender(None, None, Some("Сергеевич")), Gender::Male);
         assert_eq!(detect_gender(None, None, Some("Степаныч")), Gender::Male);
         assert_eq!(detect_gender(None, None, Some("Петровна")), Gender::Female);
-        assert_eq!(detect_gender(None, None, Some("Оно")), Gender::Androgynous);
+        assert_eq!(detect_gender(None, None, Some("Оно")), Gender::Unknown);
     }
 
+    #[cfg(all(
+        feature = "lastname-rules",
+        feature = "middlename-rules",
+        feature = "gender-detection"
+    ))]
     #[test]
     #[allow(deprecated)]
     fn test_deprecated_apis() {
         let subject = Petrovich::new();
         assert_eq!(Petrovich::detect_gender("Валентиновна"), Gender::Female);
         assert_eq!(
-            subject.firstname(Gender::Male, "", Case::Genitive).unwrap(),
-            ""
+            subject.firstname(Gender::Male, "", Case::Genitive),
+            Err("name is empty")
         );
         assert_eq!(
             subject
@@ -406,10 +1850,8 @@ mod tests {
             "Андрея"
         );
         assert_eq!(
-            subject
-                .middlename(Gender::Male, "", Case::Genitive)
-                .unwrap(),
-            ""
+            subject.middlename(Gender::Male, "", Case::Genitive),
+            Err("name is empty")
         );
         assert_eq!(
             subject
@@ -418,8 +1860,8 @@ mod tests {
             "Порфирьевича"
         );
         assert_eq!(
-            subject.lastname(Gender::Male, "", Case::Genitive).unwrap(),
-            ""
+            subject.lastname(Gender::Male, "Blabla", Case::Genitive),
+            Err("name has no Cyrillic letters")
         );
         assert_eq!(
             subject
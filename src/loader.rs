@@ -0,0 +1,307 @@
+//! Loading rules from configuration files.
+
+use std::borrow::Cow;
+
+use serde::Deserialize;
+#[cfg(feature = "json")]
+use serde::Serialize;
+
+use super::rules::{match_key, parse_mods, Rule, RuleList, RuleParts, RuleTag, Rules};
+#[cfg(feature = "json")]
+use super::{Case, NamePart};
+use super::{Error, Gender};
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+#[serde(rename_all = "lowercase")]
+enum RawGender {
+    Male,
+    Female,
+    Androgynous,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+struct RawRule {
+    gender: RawGender,
+    test: Vec<String>,
+    mods: Vec<String>,
+    #[serde(default = "Vec::new")]
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+struct RawRuleList {
+    #[serde(default = "Vec::new")]
+    exceptions: Vec<RawRule>,
+    #[serde(default = "Vec::new")]
+    suffixes: Vec<RawRule>,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+pub(crate) struct RawRules {
+    #[serde(default)]
+    lastname: RawRuleList,
+    #[serde(default)]
+    firstname: RawRuleList,
+    #[serde(default)]
+    middlename: RawRuleList,
+}
+
+fn invalid(section: &str, index: usize, message: impl Into<String>) -> Error {
+    Error::InvalidRules {
+        section: format!("{}[{}]", section, index),
+        message: message.into(),
+    }
+}
+
+fn validate_rule(section: &str, index: usize, raw: RawRule) -> Result<Rule, Error> {
+    if raw.test.is_empty() {
+        return Err(invalid(section, index, "rule has no test strings"));
+    }
+    if let Some(test) = raw.test.iter().find(|test| test.is_empty()) {
+        return Err(invalid(
+            section,
+            index,
+            format!("empty test string {:?}", test),
+        ));
+    }
+    let mods = match raw.mods.as_slice() {
+        [genitive, dative, accusative, instrumental, prepositional] => {
+            parse_mods([genitive, dative, accusative, instrumental, prepositional])
+        }
+        _ => {
+            return Err(invalid(
+                section,
+                index,
+                format!("expected 5 mods, found {}", raw.mods.len()),
+            ))
+        }
+    };
+    let gender = match raw.gender {
+        RawGender::Male => Gender::Male,
+        RawGender::Female => Gender::Female,
+        RawGender::Androgynous => Gender::Androgynous,
+    };
+    Ok(Rule::from_parts(
+        gender,
+        RuleParts {
+            test: raw
+                .test
+                .into_iter()
+                .map(|test| Cow::Owned(match_key(&test).into_owned()))
+                .collect(),
+            mods,
+            tags: raw
+                .tags
+                .into_iter()
+                .map(|tag| match tag.as_str() {
+                    "first_word" => RuleTag::FirstWord,
+                    "fleeting_vowel" => RuleTag::FleetingVowel,
+                    "particle" => RuleTag::Particle,
+                    "stressed_ending" => RuleTag::StressedEnding,
+                    _ => RuleTag::Custom(Cow::Owned(tag)),
+                })
+                .collect(),
+        },
+    ))
+}
+
+fn validate_rules(section: &str, raw: Vec<RawRule>) -> Result<Vec<Rule>, Error> {
+    raw.into_iter()
+        .enumerate()
+        .map(|(index, rule)| validate_rule(section, index, rule))
+        .collect()
+}
+
+fn validate_list(part: &str, raw: RawRuleList) -> Result<RuleList, Error> {
+    Ok(RuleList::new(
+        validate_rules(&format!("{}.exceptions", part), raw.exceptions)?,
+        validate_rules(&format!("{}.suffixes", part), raw.suffixes)?,
+    ))
+}
+
+impl RawRules {
+    /// Checks the deserialized data, shared by every loader
+    pub(crate) fn validate(self) -> Result<Rules, Error> {
+        Ok(Rules::new(
+            validate_list("lastname", self.lastname)?,
+            validate_list("firstname", self.firstname)?,
+            validate_list("middlename", self.middlename)?,
+        ))
+    }
+}
+
+#[cfg(feature = "json")]
+fn raw_rule(rule: &Rule) -> RawRule {
+    RawRule {
+        gender: match rule.gender() {
+            Gender::Male => RawGender::Male,
+            Gender::Female => RawGender::Female,
+            // Rules for unknown gender only come from overrides and act as androgynous
+            Gender::Androgynous | Gender::Unknown => RawGender::Androgynous,
+        },
+        test: rule.tests().map(str::to_owned).collect(),
+        mods: Case::iter()
+            .skip(1)
+            .map(|case| match rule.modifier(case) {
+                Some((skip, ending)) => "-".repeat(skip) + ending,
+                None => ".".to_owned(),
+            })
+            .collect(),
+        tags: rule
+            .tags()
+            .iter()
+            .map(|tag| match tag {
+                RuleTag::FirstWord => "first_word".to_owned(),
+                RuleTag::FleetingVowel => "fleeting_vowel".to_owned(),
+                RuleTag::Particle => "particle".to_owned(),
+                RuleTag::StressedEnding => "stressed_ending".to_owned(),
+                RuleTag::Custom(tag) => tag.clone().into_owned(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(feature = "json")]
+fn raw_list(rules: &Rules, part: NamePart) -> RawRuleList {
+    let list = rules.list(part);
+    RawRuleList {
+        exceptions: list.exceptions().iter().map(raw_rule).collect(),
+        suffixes: list.suffixes().iter().map(raw_rule).collect(),
+    }
+}
+
+impl Rules {
+    /// Serializes the rules to JSON in the format read by `from_json`
+    #[cfg(feature = "json")]
+    pub fn dump_json(&self) -> String {
+        let raw = RawRules {
+            lastname: raw_list(self, NamePart::Last),
+            firstname: raw_list(self, NamePart::First),
+            middlename: raw_list(self, NamePart::Middle),
+        };
+        serde_json::to_string_pretty(&raw).expect("rules always serialize")
+    }
+
+    /// Reads rules in the `rules.yml` format
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(reader: impl std::io::Read) -> Result<Rules, Error> {
+        serde_yaml::from_reader::<_, RawRules>(reader)
+            .map_err(|error| Error::InvalidRules {
+                section: String::new(),
+                message: error.to_string(),
+            })?
+            .validate()
+    }
+
+    /// Reads rules from JSON with the same structure as the embedded `rules.yml`
+    #[cfg(feature = "json")]
+    pub fn from_json(reader: impl std::io::Read) -> Result<Rules, Error> {
+        serde_json::from_reader::<_, RawRules>(reader)
+            .map_err(|error| Error::InvalidRules {
+                section: String::new(),
+                message: error.to_string(),
+            })?
+            .validate()
+    }
+
+    /// Parses rules from TOML with the same structure as the embedded `rules.yml`
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(source: &str) -> Result<Rules, Error> {
+        toml::from_str::<RawRules>(source)
+            .map_err(|error| Error::InvalidRules {
+                section: String::new(),
+                message: error.to_string(),
+            })?
+            .validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Case;
+
+    #[cfg(all(feature = "toml", feature = "lastname-rules"))]
+    #[test]
+    fn should_load_toml_equivalent_to_builtin() {
+        let rules = Rules::from_toml_str(include_str!("../tests/fixtures/rules.toml")).unwrap();
+        assert!(rules == Rules::builtin());
+
+        let inflector = crate::Inflector::new(rules);
+        for &name in &["Бонч-Бруевич", "Иванова", "Цой", "Шевченко", "Кравец"]
+        {
+            for &gender in &[Gender::Male, Gender::Female] {
+                assert_eq!(
+                    inflector.lastname(gender, name, Case::Dative),
+                    crate::lastname(gender, name, Case::Dative)
+                );
+            }
+        }
+    }
+
+    #[cfg(all(feature = "yaml", feature = "lastname-rules"))]
+    #[test]
+    fn should_load_yaml_equivalent_to_builtin() {
+        let rules = Rules::from_yaml(include_str!("rules.yml").as_bytes()).unwrap();
+        assert!(rules == Rules::builtin());
+        assert_eq!(
+            rules.lastname(Gender::Male, "Кравец", Case::Dative),
+            crate::lastname(Gender::Male, "Кравец", Case::Dative)
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn should_load_json_rules() {
+        let source = r#"{
+            "lastname": {
+                "exceptions": [
+                    {"gender": "male", "test": ["Сирота"], "mods": [".", ".", ".", ".", "."]}
+                ],
+                "suffixes": [
+                    {"gender": "androgynous", "test": ["ота"], "mods": ["-ы", "-е", "-у", "-ой", "-е"]}
+                ]
+            }
+        }"#;
+        let rules = Rules::from_json(source.as_bytes()).unwrap();
+        assert_eq!(
+            rules.lastname(Gender::Male, "Сирота", Case::Dative),
+            "Сирота"
+        );
+        assert_eq!(
+            rules.lastname(Gender::Female, "Сирота", Case::Dative),
+            "Сироте"
+        );
+        assert!(Rules::from_json(&b"{\"lastname\": 1}"[..]).is_err());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn should_round_trip_json_dump() {
+        let dump = Rules::embedded().dump_json();
+        assert!(Rules::from_json(dump.as_bytes()).unwrap() == Rules::builtin());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn should_reject_invalid_toml_rules() {
+        let source = r#"
+            [[lastname.suffixes]]
+            gender = "male"
+            test = ["ов"]
+            mods = ["а", "у"]
+        "#;
+        assert_eq!(
+            Rules::from_toml_str(source),
+            Err(Error::InvalidRules {
+                section: "lastname.suffixes[0]".to_owned(),
+                message: "expected 5 mods, found 2".to_owned(),
+            })
+        );
+        assert!(Rules::from_toml_str("lastname = 1").is_err());
+    }
+}
@@ -0,0 +1,394 @@
+//! Fuzzy comparison of full names written in different cases, with initials or diminutives.
+
+#[cfg(feature = "diminutives")]
+use super::canonical_firstname;
+use super::initials::is_initials;
+use super::rules::fold_yo_char;
+use super::{to_nominative, FullName, Gender, NamePart};
+
+/// Options for `FullName::matches`
+///
+/// New options may be added, so start from `MatchOptions::DEFAULT` and set the fields you
+/// need.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct MatchOptions {
+    /// Let names in other cases match their nominative, as "Иванову" matches "Иванов"; on
+    /// by default
+    pub any_case: bool,
+    /// Let initials like "А." match names starting with the letter; on by default
+    pub initials: bool,
+    /// Let diminutives like "Саша" match the full first names they stand for; on by
+    /// default, but only has an effect with the `diminutives` feature
+    pub diminutives: bool,
+}
+
+impl MatchOptions {
+    /// Options returned by `MatchOptions::default()`
+    pub const DEFAULT: MatchOptions = MatchOptions {
+        any_case: true,
+        initials: true,
+        diminutives: true,
+    };
+}
+
+impl Default for MatchOptions {
+    fn default() -> MatchOptions {
+        MatchOptions::DEFAULT
+    }
+}
+
+/// How well two names or parts of them agree, from the best to the worst
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Debug, Hash)]
+pub enum MatchGrade {
+    /// Written the same
+    Exact,
+    /// May belong to the same person
+    Compatible,
+    /// Can't belong to the same person
+    Conflict,
+}
+
+/// Why two parts of names got their `MatchGrade`
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+#[non_exhaustive]
+pub enum MatchReason {
+    /// Same spelling once letter case, ё and е, and hyphens and spaces are ignored
+    Same,
+    /// Only one of the names has the part
+    Missing,
+    /// Initials agree with the first letters of the other name
+    Initial,
+    /// Both are forms of the same name in different cases
+    CaseForm,
+    /// Both stand for the same full first name, as "Саша" and "Александра"
+    Diminutive,
+    /// None of the above
+    Different,
+}
+
+/// Comparison of one part of two names
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+pub struct FieldMatch {
+    /// Compared part
+    pub part: NamePart,
+    /// How well the part agrees
+    pub grade: MatchGrade,
+    /// Why it got the grade
+    pub reason: MatchReason,
+}
+
+/// Result of `FullName::matches`
+#[derive(Eq, PartialEq, Clone, Debug, Hash)]
+pub struct MatchResult {
+    /// Worst grade of the parts; a part only one name has is compatible
+    pub grade: MatchGrade,
+    /// Parts either name has, in the "Фамилия Имя Отчество" order
+    pub fields: Vec<FieldMatch>,
+}
+
+// Lowercase words with ё folded to е, separated by single spaces
+fn normalize(name: &str) -> String {
+    name.split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            word.chars()
+                .flat_map(char::to_lowercase)
+                .map(fold_yo_char)
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Letters of a name made of initials only, like "А." or "А.-М."
+fn initials(name: &str) -> Option<Vec<char>> {
+    let mut tokens = name.split_whitespace().peekable();
+    tokens.peek()?;
+    if !tokens.all(is_initials) {
+        return None;
+    }
+    Some(
+        normalize(name)
+            .chars()
+            .filter(|c| c.is_alphabetic())
+            .collect(),
+    )
+}
+
+// First letters of the words of a normalized name
+fn first_letters(normalized: &str) -> Vec<char> {
+    normalized
+        .split(' ')
+        .filter_map(|word| word.chars().next())
+        .collect()
+}
+
+// Normalized name and the nominatives it may be a case form of
+fn nominatives(part: NamePart, name: &str, options: &MatchOptions) -> Vec<String> {
+    let mut forms = vec![normalize(name)];
+    if options.any_case {
+        let hyphenated = name.split_whitespace().collect::<Vec<_>>().join("-");
+        for candidate in to_nominative(part, Gender::Unknown, &hyphenated) {
+            let form = normalize(&candidate.nominative);
+            if !forms.contains(&form) {
+                forms.push(form);
+            }
+        }
+    }
+    forms
+}
+
+// Full first names the forms may stand for
+#[cfg(feature = "diminutives")]
+fn canonical(part: NamePart, forms: &[String], options: &MatchOptions) -> Vec<String> {
+    if part != NamePart::First || !options.diminutives {
+        return Vec::new();
+    }
+    let mut names: Vec<String> = Vec::new();
+    for form in forms {
+        for candidate in canonical_firstname(form, None) {
+            let name = normalize(candidate.name);
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+#[cfg(not(feature = "diminutives"))]
+fn canonical(_: NamePart, _: &[String], _: &MatchOptions) -> Vec<String> {
+    Vec::new()
+}
+
+fn compare(part: NamePart, a: &str, b: &str, options: &MatchOptions) -> FieldMatch {
+    let field = |grade, reason| FieldMatch {
+        part,
+        grade,
+        reason,
+    };
+    if normalize(a) == normalize(b) {
+        return field(MatchGrade::Exact, MatchReason::Same);
+    }
+    if options.initials {
+        let (letters, full) = match (initials(a), initials(b)) {
+            (Some(letters_a), Some(letters_b)) if letters_a == letters_b => {
+                return field(MatchGrade::Exact, MatchReason::Same)
+            }
+            (Some(_), Some(_)) => return field(MatchGrade::Conflict, MatchReason::Different),
+            (Some(letters), None) => (Some(letters), b),
+            (None, Some(letters)) => (Some(letters), a),
+            (None, None) => (None, a),
+        };
+        if let Some(letters) = letters {
+            let forms = nominatives(part, full, options);
+            let agrees = forms
+                .iter()
+                .chain(&canonical(part, &forms, options))
+                .any(|form| first_letters(form) == letters);
+            return if agrees {
+                field(MatchGrade::Compatible, MatchReason::Initial)
+            } else {
+                field(MatchGrade::Conflict, MatchReason::Different)
+            };
+        }
+    }
+    let (forms_a, forms_b) = (nominatives(part, a, options), nominatives(part, b, options));
+    if forms_a.iter().any(|form| forms_b.contains(form)) {
+        return field(MatchGrade::Compatible, MatchReason::CaseForm);
+    }
+    let (canonical_a, canonical_b) = (
+        canonical(part, &forms_a, options),
+        canonical(part, &forms_b, options),
+    );
+    let same_name =
+        |canonical: &[String], forms: &[String]| canonical.iter().any(|name| forms.contains(name));
+    if same_name(&canonical_a, &forms_b)
+        || same_name(&canonical_b, &forms_a)
+        || same_name(&canonical_a, &canonical_b)
+    {
+        return field(MatchGrade::Compatible, MatchReason::Diminutive);
+    }
+    field(MatchGrade::Conflict, MatchReason::Different)
+}
+
+impl FullName {
+    /// Compares two names part by part to tell whether they may belong to the same person
+    ///
+    /// Parts are compared ignoring letter case, treating ё and е as well as hyphens and
+    /// spaces as equivalent. Depending on the options, names in other cases are traced
+    /// back to the nominative with `to_nominative`, initials are checked against the first
+    /// letters of the other name and of the full names it may be a diminutive of, and
+    /// diminutives are mapped to full first names with `canonical_firstname`. Genders of
+    /// the names aren't compared.
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "firstname-rules", feature = "lastname-rules", feature = "middlename-rules"))]
+    /// # {
+    /// use petrovich::{FullName, MatchGrade, MatchOptions, MatchReason};
+    ///
+    /// let short = FullName::builder().last("Иванову").first("А.").middle("С.").build();
+    /// let full = FullName::builder()
+    ///     .last("Иванова")
+    ///     .first("Александра")
+    ///     .middle("Сергеевна")
+    ///     .build();
+    /// let result = short.matches(&full, MatchOptions::DEFAULT);
+    /// assert_eq!(result.grade, MatchGrade::Compatible);
+    /// assert_eq!(result.fields[0].reason, MatchReason::CaseForm);
+    /// assert_eq!(result.fields[1].reason, MatchReason::Initial);
+    ///
+    /// let other = FullName::builder().last("Петрова").first("Александра").build();
+    /// assert_eq!(full.matches(&other, MatchOptions::DEFAULT).grade, MatchGrade::Conflict);
+    /// # }
+    /// ```
+    pub fn matches(&self, other: &FullName, options: MatchOptions) -> MatchResult {
+        let parts = [
+            (NamePart::Last, self.last(), other.last()),
+            (NamePart::First, self.first(), other.first()),
+            (NamePart::Middle, self.middle(), other.middle()),
+        ];
+        let fields: Vec<FieldMatch> = parts
+            .iter()
+            .filter_map(|&(part, a, b)| match (a, b) {
+                (Some(a), Some(b)) => Some(compare(part, a, b, &options)),
+                (None, None) => None,
+                _ => Some(FieldMatch {
+                    part,
+                    grade: MatchGrade::Compatible,
+                    reason: MatchReason::Missing,
+                }),
+            })
+            .collect();
+        let grade = fields
+            .iter()
+            .map(|field| field.grade)
+            .max()
+            .unwrap_or(MatchGrade::Exact);
+        MatchResult { grade, fields }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(last: &str, first: &str, middle: Option<&str>) -> FullName {
+        let builder = FullName::builder().last(last).first(first);
+        match middle {
+            Some(middle) => builder.middle(middle).build(),
+            None => builder.build(),
+        }
+    }
+
+    fn reasons(result: &MatchResult) -> Vec<MatchReason> {
+        result.fields.iter().map(|field| field.reason).collect()
+    }
+
+    #[test]
+    fn should_match_exact_spellings() {
+        let a = name("Петров-Водкин", "Фёдор", Some("Семёнович"));
+        let b = name("петров водкин", "Федор", Some("СЕМЕНОВИЧ"));
+        let result = a.matches(&b, MatchOptions::DEFAULT);
+        assert_eq!(result.grade, MatchGrade::Exact);
+        assert_eq!(reasons(&result), [MatchReason::Same; 3]);
+    }
+
+    #[cfg(all(
+        feature = "firstname-rules",
+        feature = "lastname-rules",
+        feature = "middlename-rules"
+    ))]
+    #[test]
+    fn should_match_case_forms_and_initials() {
+        let short = name("Иванову", "А.", Some("С."));
+        let full = name("Иванова", "Александра", Some("Сергеевна"));
+        let result = short.matches(&full, MatchOptions::DEFAULT);
+        assert_eq!(result.grade, MatchGrade::Compatible);
+        assert_eq!(
+            reasons(&result),
+            [
+                MatchReason::CaseForm,
+                MatchReason::Initial,
+                MatchReason::Initial
+            ]
+        );
+        assert_eq!(
+            name("Иванова", "А", None)
+                .matches(&name("Иванова", "А.", None), MatchOptions::DEFAULT)
+                .grade,
+            MatchGrade::Exact
+        );
+        let dative = name("Ивановой", "Александре", Some("Сергеевне"));
+        assert_eq!(
+            reasons(&dative.matches(&full, MatchOptions::DEFAULT)),
+            [MatchReason::CaseForm; 3]
+        );
+
+        let mut options = MatchOptions::DEFAULT;
+        options.any_case = false;
+        options.initials = false;
+        let result = short.matches(&full, options);
+        assert_eq!(result.grade, MatchGrade::Conflict);
+        assert_eq!(reasons(&result), [MatchReason::Different; 3]);
+    }
+
+    #[test]
+    fn should_report_conflicts_and_missing_parts() {
+        let a = name("Иванова", "Анна", None);
+        let b = name("Иванова", "Мария", Some("Петровна"));
+        let result = a.matches(&b, MatchOptions::DEFAULT);
+        assert_eq!(result.grade, MatchGrade::Conflict);
+        assert_eq!(
+            reasons(&result),
+            [
+                MatchReason::Same,
+                MatchReason::Different,
+                MatchReason::Missing
+            ]
+        );
+        assert_eq!(result.fields[2].part, NamePart::Middle);
+        let initial = name("Иванова", "М.", None);
+        assert_eq!(
+            initial.matches(&a, MatchOptions::DEFAULT).fields[1].grade,
+            MatchGrade::Conflict
+        );
+    }
+
+    #[cfg(feature = "diminutives")]
+    #[test]
+    fn should_match_diminutives() {
+        let full = name("Иванова", "Александра", Some("Сергеевна"));
+        let short = name("Иванову", "А.", Some("С."));
+        let diminutive = name("Иванова", "Саша", None);
+        let result = full.matches(&diminutive, MatchOptions::DEFAULT);
+        assert_eq!(result.grade, MatchGrade::Compatible);
+        assert_eq!(
+            reasons(&result),
+            [
+                MatchReason::Same,
+                MatchReason::Diminutive,
+                MatchReason::Missing
+            ]
+        );
+        assert_eq!(
+            reasons(&short.matches(&diminutive, MatchOptions::DEFAULT))[1],
+            MatchReason::Initial
+        );
+        assert_eq!(
+            name("Иванов", "Саше", None)
+                .matches(&name("Иванов", "Шура", None), MatchOptions::DEFAULT)
+                .fields[1]
+                .reason,
+            MatchReason::Diminutive
+        );
+
+        let mut options = MatchOptions::DEFAULT;
+        options.diminutives = false;
+        assert_eq!(
+            full.matches(&diminutive, options).grade,
+            MatchGrade::Conflict
+        );
+    }
+}
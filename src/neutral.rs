@@ -0,0 +1,306 @@
+//! Gender-neutral output combining male and female forms, as in "Иванову(ой)" for
+//! recipients of unknown gender.
+
+use super::rules::{NoCustomTags, RuleList, RULES};
+use super::{
+    lastname_to_gender, match_name, nfc, split_segments, Case, Gender, InflectOptions, NamePart,
+    ENDING_RESERVE,
+};
+
+/// How the male and female forms are combined
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Default)]
+pub enum BracketStyle {
+    /// "Иванову(ой)"
+    #[default]
+    Round,
+    /// "Иванову[ой]"
+    Square,
+    /// Both forms in full: "Иванову/Ивановой"
+    Slash,
+}
+
+// Segment of an inflected form: the separator before it, the inflected segment and the
+// byte length of its start the matched rule keeps, when it is known
+struct Piece {
+    separator: String,
+    form: String,
+    stem: Option<usize>,
+}
+
+// Segments of the name as written, with no stems
+fn split_pieces(name: &str) -> Vec<Piece> {
+    let mut separator = "";
+    split_segments(name)
+        .map(|(segment, next)| Piece {
+            separator: std::mem::replace(&mut separator, next).to_owned(),
+            form: segment.to_owned(),
+            stem: None,
+        })
+        .collect()
+}
+
+// Segments of the name inflected for the gender, with the stems the rules keep
+fn inflect_pieces(gender: Gender, name: &str, case: Case, rule_list: &RuleList) -> Vec<Piece> {
+    let name = &*nfc(name);
+    let options = InflectOptions::DEFAULT;
+    let segments = match match_name(gender, name, rule_list, &NoCustomTags, &options) {
+        Ok(segments) => segments,
+        Err(_) => return split_pieces(name),
+    };
+    segments
+        .iter()
+        .map(|segment| {
+            let mut form = String::with_capacity(segment.segment.len() + ENDING_RESERVE);
+            segment.inflect_into(case, &mut form);
+            let kept = match segment.rule.and_then(|(_, rule)| rule.modifier(case)) {
+                Some((skip, _)) => segment.kept_len(skip),
+                None => segment.segment.len(),
+            };
+            Piece {
+                form: form.split_off(segment.separator.len()),
+                separator: segment.separator.to_owned(),
+                stem: Some(segment.head.len() + kept),
+            }
+        })
+        .collect()
+}
+
+// Merge a single segment of male and female forms. The alternative ending starts after the
+// stem both rules keep, as in "Толстому(ой)", or after the common start of the forms when
+// the stem isn't known or differs
+fn merge(male: &Piece, female: &Piece, (open, close): (char, char), output: &mut String) {
+    let stem = male
+        .stem
+        .zip(female.stem)
+        .map(|(male, female)| male.min(female));
+    let (male, female) = (&male.form, &female.form);
+    output.push_str(male);
+    if male == female {
+        return;
+    }
+    let stem =
+        stem.filter(|&stem| male.get(..stem).is_some() && male.get(..stem) == female.get(..stem));
+    let common = stem.unwrap_or_else(|| {
+        male.char_indices()
+            .zip(female.chars())
+            .find(|&((_, m), f)| m != f)
+            .map_or_else(|| male.len().min(female.len()), |((i, _), _)| i)
+    });
+    let ending = &female[common..];
+    output.push(open);
+    output.push_str(if ending.is_empty() { "—" } else { ending });
+    output.push(close);
+}
+
+/// Merges a male and a female form, printing identical forms once
+///
+/// With brackets the male form is followed by the part of the female form that differs
+/// from it: "Иванову(ой)". When the female form is a prefix of the male one the brackets
+/// contain an em dash: "Станкевичу(—)". Words and hyphen-separated parts are merged
+/// separately, so "Петрову Водкину" and "Петровой Водкиной" give "Петрову(ой) Водкину(ой)".
+/// `BracketStyle::Slash` writes both forms in full, as do the other styles when the forms
+/// are split into parts differently.
+///
+/// ```
+/// use petrovich::{merge_neutral, BracketStyle};
+///
+/// assert_eq!(merge_neutral("Уважаемый", "Уважаемая", BracketStyle::Round), "Уважаемый(ая)");
+/// assert_eq!(merge_neutral("Иванову", "Ивановой", BracketStyle::Slash), "Иванову/Ивановой");
+/// assert_eq!(merge_neutral("Шевченко", "Шевченко", BracketStyle::Slash), "Шевченко");
+/// ```
+pub fn merge_neutral(male: &str, female: &str, style: BracketStyle) -> String {
+    merge_pieces(split_pieces(male), split_pieces(female), style)
+}
+
+// Merges the forms segment by segment, or writes them in full when they split differently
+fn merge_pieces(male: Vec<Piece>, female: Vec<Piece>, style: BracketStyle) -> String {
+    let join = |pieces: &[Piece]| -> String {
+        pieces
+            .iter()
+            .flat_map(|piece| [piece.separator.as_str(), piece.form.as_str()])
+            .collect()
+    };
+    let aligned = male.len() == female.len()
+        && male
+            .iter()
+            .zip(&female)
+            .all(|(male, female)| male.separator == female.separator);
+    let brackets = match style {
+        BracketStyle::Round if aligned => ('(', ')'),
+        BracketStyle::Square if aligned => ('[', ']'),
+        _ => {
+            let (male, female) = (join(&male), join(&female));
+            return if male == female {
+                male
+            } else {
+                format!("{}/{}", male, female)
+            };
+        }
+    };
+    let mut output = String::new();
+    for (male, female) in male.iter().zip(&female) {
+        output.push_str(&male.separator);
+        merge(male, female, brackets, &mut output);
+    }
+    output
+}
+
+/// Inflects name for both genders and merges the forms, see `inflect_neutral_with`
+pub fn inflect_neutral(part: NamePart, name: &str, case: Case) -> String {
+    inflect_neutral_with(part, name, case, BracketStyle::default())
+}
+
+/// Inflects name for both genders and merges the forms with `merge_neutral`
+///
+/// Last names are first converted to the form of each gender with `lastname_to_gender`,
+/// so "Иванов" and "Иванова" give the same result. The female ending starts where the
+/// stem both rules keep ends, so "Толстой" gives "Толстому(ой)". Names declining the same
+/// way for both genders, like "Шевченко", come out plain.
+///
+/// ```
+/// # #[cfg(feature = "lastname-rules")]
+/// # {
+/// use petrovich::{inflect_neutral, inflect_neutral_with, BracketStyle, Case, NamePart};
+///
+/// assert_eq!(inflect_neutral(NamePart::Last, "Иванов", Case::Dative), "Иванову(ой)");
+/// assert_eq!(inflect_neutral(NamePart::Last, "Толстой", Case::Dative), "Толстому(ой)");
+/// assert_eq!(
+///     inflect_neutral_with(NamePart::Last, "Петрова", Case::Genitive, BracketStyle::Slash),
+///     "Петрова/Петровой"
+/// );
+/// # }
+/// ```
+pub fn inflect_neutral_with(part: NamePart, name: &str, case: Case, style: BracketStyle) -> String {
+    let rule_list = RULES.list(part);
+    let pieces = |gender| match part {
+        NamePart::Last => {
+            inflect_pieces(gender, &lastname_to_gender(name, gender), case, rule_list)
+        }
+        _ => inflect_pieces(gender, name, case, rule_list),
+    };
+    merge_pieces(pieces(Gender::Male), pieces(Gender::Female), style)
+}
+
+/// Inflects last name for both genders, see `inflect_neutral_with`
+#[cfg(feature = "lastname-rules")]
+pub fn lastname_neutral(name: &str, case: Case, style: BracketStyle) -> String {
+    inflect_neutral_with(NamePart::Last, name, case, style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_merge_differing_forms() {
+        assert_eq!(
+            inflect_neutral(NamePart::Last, "Иванов", Case::Dative),
+            "Иванову(ой)"
+        );
+        assert_eq!(
+            inflect_neutral(NamePart::Last, "Толстой", Case::Dative),
+            "Толстому(ой)"
+        );
+        assert_eq!(
+            inflect_neutral(NamePart::Last, "Толстая", Case::Genitive),
+            "Толстого(ой)"
+        );
+        assert_eq!(
+            inflect_neutral(NamePart::Last, "Толстой", Case::Prepositional),
+            "Толстом(ой)"
+        );
+        assert_eq!(
+            inflect_neutral(NamePart::Last, "Полоцкий", Case::Dative),
+            "Полоцкому(ой)"
+        );
+        assert_eq!(
+            inflect_neutral(NamePart::Last, "Станкевич", Case::Dative),
+            "Станкевичу(—)"
+        );
+        assert_eq!(
+            inflect_neutral_with(
+                NamePart::Last,
+                "Иванова",
+                Case::Dative,
+                BracketStyle::Square
+            ),
+            "Иванову[ой]"
+        );
+    }
+
+    #[cfg(feature = "firstname-rules")]
+    #[test]
+    fn should_print_coinciding_forms_once() {
+        assert_eq!(
+            inflect_neutral(NamePart::Last, "Шевченко", Case::Dative),
+            "Шевченко"
+        );
+        assert_eq!(
+            inflect_neutral(NamePart::First, "Саша", Case::Dative),
+            "Саше"
+        );
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_merge_hyphenated_components() {
+        assert_eq!(
+            inflect_neutral(NamePart::Last, "Иванова-Шевченко", Case::Dative),
+            "Иванову(ой)-Шевченко"
+        );
+        assert_eq!(
+            inflect_neutral(NamePart::Last, "Петров Водкин", Case::Dative),
+            "Петрову(ой) Водкину(ой)"
+        );
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_write_both_forms_with_slash() {
+        assert_eq!(
+            lastname_neutral("Петров", Case::Genitive, BracketStyle::Slash),
+            "Петрова/Петровой"
+        );
+        assert_eq!(
+            lastname_neutral("Станкевич", Case::Dative, BracketStyle::Slash),
+            "Станкевичу/Станкевич"
+        );
+        assert_eq!(
+            lastname_neutral("Шевченко", Case::Genitive, BracketStyle::Slash),
+            "Шевченко"
+        );
+        assert_eq!(
+            lastname_neutral("Петров Водкин", Case::Dative, BracketStyle::Slash),
+            "Петрову Водкину/Петровой Водкиной"
+        );
+    }
+
+    #[test]
+    fn should_merge_given_forms() {
+        assert_eq!(
+            merge_neutral("Петровым", "Петровой", BracketStyle::Round),
+            "Петровым(ой)"
+        );
+        assert_eq!(
+            merge_neutral("Иванову-Петрову", "Ивановой-Петровой", BracketStyle::Round),
+            "Иванову(ой)-Петрову(ой)"
+        );
+        assert_eq!(
+            merge_neutral("Станкевичу", "Станкевич", BracketStyle::Round),
+            "Станкевичу(—)"
+        );
+    }
+
+    #[test]
+    fn should_write_differently_split_forms_in_full() {
+        assert_eq!(
+            merge_neutral("Иванову Петру", "Ивановой", BracketStyle::Round),
+            "Иванову Петру/Ивановой"
+        );
+        assert_eq!(
+            merge_neutral("Иванову-Петру", "Ивановой Петровой", BracketStyle::Square),
+            "Иванову-Петру/Ивановой Петровой"
+        );
+    }
+}
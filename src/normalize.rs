@@ -0,0 +1,357 @@
+//! Cleanup of names typed into forms.
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use super::options::{is_apostrophe, is_cyrillic};
+use super::NamePart;
+
+// Hyphen, non-breaking hyphen, figure dash, en dash, em dash and minus sign
+fn is_dash(c: char) -> bool {
+    matches!(c, '-' | '‐' | '‑' | '‒' | '–' | '—' | '−')
+}
+
+/// Trims the name, collapses whitespace, including non-breaking spaces, to single spaces
+/// and replaces dashes with hyphens, dropping the spaces around them
+///
+/// The name is borrowed when it is already normalized. `InflectOptions::normalize`
+/// applies this before matching the rules.
+///
+/// ```
+/// use petrovich::normalize_name;
+///
+/// assert_eq!(normalize_name(" Иванов\u{a0}— Сидоров "), "Иванов-Сидоров");
+/// assert_eq!(normalize_name("Петров   Водкин"), "Петров Водкин");
+/// ```
+pub fn normalize_name(name: &str) -> Cow<'_, str> {
+    let mut output = String::with_capacity(name.len());
+    let mut space = false;
+    for c in name.trim().chars() {
+        if c.is_whitespace() {
+            space = true;
+        } else if is_dash(c) {
+            space = false;
+            output.push('-');
+        } else {
+            if space && !output.ends_with('-') {
+                output.push(' ');
+            }
+            space = false;
+            output.push(c);
+        }
+    }
+    if output == name {
+        Cow::Borrowed(name)
+    } else {
+        Cow::Owned(output)
+    }
+}
+
+// Latin letters written the same as Cyrillic ones, with the Cyrillic letter
+const HOMOGLYPHS: &[(char, char)] = &[
+    ('a', 'а'),
+    ('c', 'с'),
+    ('e', 'е'),
+    ('o', 'о'),
+    ('p', 'р'),
+    ('x', 'х'),
+    ('y', 'у'),
+    ('A', 'А'),
+    ('B', 'В'),
+    ('C', 'С'),
+    ('E', 'Е'),
+    ('H', 'Н'),
+    ('K', 'К'),
+    ('M', 'М'),
+    ('O', 'О'),
+    ('P', 'Р'),
+    ('T', 'Т'),
+    ('X', 'Х'),
+];
+
+fn cyrillic_lookalike(c: char) -> Option<char> {
+    HOMOGLYPHS
+        .iter()
+        .find(|&&(latin, _)| latin == c)
+        .map(|&(_, cyrillic)| cyrillic)
+}
+
+/// Latin letter that `fix_homoglyphs` replaces with the Cyrillic one it looks like
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Homoglyph {
+    /// Byte range of the letter in the name
+    pub range: Range<usize>,
+    /// Latin letter found, e.g. 'o'
+    pub latin: char,
+    /// Cyrillic letter replacing it, e.g. 'о'
+    pub cyrillic: char,
+}
+
+/// Lists the Latin letters `fix_homoglyphs` would replace, for logging the changes
+///
+/// ```
+/// use petrovich::homoglyphs;
+///
+/// let found = homoglyphs("Иванoв");
+/// assert_eq!(found.len(), 1);
+/// assert_eq!((found[0].latin, found[0].cyrillic), ('o', 'о'));
+/// assert_eq!(found[0].range, 8..9);
+/// ```
+pub fn homoglyphs(name: &str) -> Vec<Homoglyph> {
+    let mut found = Vec::new();
+    for word in name.split(|c: char| !(c.is_alphabetic() || is_apostrophe(c))) {
+        let mut letters = word.chars().filter(|c| c.is_alphabetic());
+        let fixable = letters
+            .clone()
+            .all(|c| is_cyrillic(c) || cyrillic_lookalike(c).is_some());
+        if !fixable || !letters.any(is_cyrillic) {
+            continue;
+        }
+        let start = word.as_ptr() as usize - name.as_ptr() as usize;
+        found.extend(word.char_indices().filter_map(|(i, c)| {
+            Some(Homoglyph {
+                range: start + i..start + i + c.len_utf8(),
+                latin: c,
+                cyrillic: cyrillic_lookalike(c)?,
+            })
+        }));
+    }
+    found
+}
+
+/// Replaces Latin letters looking like Cyrillic ones, as in "Иванoв" with a Latin "o",
+/// in words whose other letters are all Cyrillic
+///
+/// Words with Latin letters that have no Cyrillic lookalike, or no Cyrillic letters at
+/// all, are left alone, so "Coco" and "Jонсон" don't change. The name is borrowed when
+/// nothing is replaced. `InflectOptions::fix_homoglyphs` applies this before matching the
+/// rules, and `homoglyphs` lists the replacements.
+///
+/// ```
+/// use petrovich::fix_homoglyphs;
+///
+/// assert_eq!(fix_homoglyphs("Иванoв"), "Иванов");
+/// assert_eq!(fix_homoglyphs("ПEТРOВ-Vodkin"), "ПЕТРОВ-Vodkin");
+/// assert_eq!(fix_homoglyphs("Coco Шанель"), "Coco Шанель");
+/// ```
+pub fn fix_homoglyphs(name: &str) -> Cow<'_, str> {
+    let found = homoglyphs(name);
+    if found.is_empty() {
+        return Cow::Borrowed(name);
+    }
+    let mut output = String::with_capacity(name.len() + found.len());
+    let mut end = 0;
+    for homoglyph in found {
+        output.push_str(&name[end..homoglyph.range.start]);
+        output.push(homoglyph.cyrillic);
+        end = homoglyph.range.end;
+    }
+    output.push_str(&name[end..]);
+    Cow::Owned(output)
+}
+
+// Words kept lowercase inside last names, like "фон" in "фон Браун"
+const SURNAME_PARTICLES: &[&str] = &[
+    "аль",
+    "ван",
+    "да",
+    "де",
+    "дел",
+    "дель",
+    "делла",
+    "дер",
+    "ди",
+    "дос",
+    "дю",
+    "ибн",
+    "ла",
+    "ле",
+    "тер",
+    "фан",
+    "фон",
+    "цу",
+    "эль",
+];
+
+// Words kept lowercase inside middle names, like "оглы" in "Ибрагим оглы"
+pub(crate) const PATRONYMIC_PARTICLES: &[&str] = &["гызы", "кызы", "оглу", "оглы", "улы", "уулу"];
+
+// Capitalizes the word, keeping the inner capitals of words like "МакГрегор" that already
+// start with a capital and have lowercase letters. A one-letter prefix with an apostrophe,
+// as in "О’Нил" or "Д'Артаньян", is capitalized on its own like a hyphenated part
+fn titlecase_word(word: &str, output: &mut String) {
+    let mut prefix = word.char_indices();
+    if let (Some((_, first)), Some((i, c))) = (prefix.next(), prefix.next()) {
+        if first.is_alphabetic() && is_apostrophe(c) && i + c.len_utf8() < word.len() {
+            let (prefix, rest) = word.split_at(i + c.len_utf8());
+            output.extend(first.to_uppercase());
+            output.push_str(&prefix[first.len_utf8()..]);
+            return titlecase_word(rest, output);
+        }
+    }
+    let mut chars = word.chars();
+    let first = match chars.next() {
+        Some(first) => first,
+        None => return,
+    };
+    let rest = chars.as_str();
+    output.extend(first.to_uppercase());
+    let mixed = first.is_uppercase()
+        && rest.chars().any(char::is_lowercase)
+        && rest.chars().any(char::is_uppercase);
+    if mixed {
+        output.push_str(rest);
+    } else {
+        output.push_str(&rest.to_lowercase());
+    }
+}
+
+/// Writes the name with every word and hyphen-separated part capitalized, as in
+/// "Анна-Мария" or "Бонч-Бруевич", and the name after a one-letter prefix like "О’" or
+/// "Д'" too
+///
+/// Particles of last names like "фон" or "де" and of middle names like "оглы" stay
+/// lowercase unless they are the whole name. Inner capitals are kept when the input has
+/// them, so "МакГрегор" doesn't change, while "МАКГРЕГОР" becomes "Макгрегор".
+/// Separators are kept as written. `InflectOptions::titlecase` applies this before
+/// matching the rules, so the inflected name comes out title-cased too.
+///
+/// ```
+/// use petrovich::{titlecase_name, NamePart};
+///
+/// assert_eq!(titlecase_name(NamePart::Last, "БОНЧ-БРУЕВИЧ"), "Бонч-Бруевич");
+/// assert_eq!(titlecase_name(NamePart::Last, "Фон Браун"), "фон Браун");
+/// assert_eq!(titlecase_name(NamePart::First, "анна-мария"), "Анна-Мария");
+/// assert_eq!(titlecase_name(NamePart::Last, "о’нил"), "О’Нил");
+/// assert_eq!(titlecase_name(NamePart::Middle, "ИБРАГИМ ОГЛЫ"), "Ибрагим оглы");
+/// ```
+pub fn titlecase_name(part: NamePart, name: &str) -> String {
+    let is_separator = |c: char| c.is_whitespace() || is_dash(c);
+    let particles = match part {
+        NamePart::Last => SURNAME_PARTICLES,
+        NamePart::Middle => PATRONYMIC_PARTICLES,
+        NamePart::First => &[],
+    };
+    let words = name.split(is_separator).filter(|word| !word.is_empty());
+    let single = words.count() < 2;
+    let mut output = String::with_capacity(name.len());
+    for piece in name.split_inclusive(is_separator) {
+        let (word, separator) = match piece.char_indices().last() {
+            Some((i, c)) if is_separator(c) => piece.split_at(i),
+            _ => (piece, ""),
+        };
+        let lowercase = word.to_lowercase();
+        if !single && particles.contains(&lowercase.as_str()) {
+            output.push_str(&lowercase);
+        } else {
+            titlecase_word(word, &mut output);
+        }
+        output.push_str(separator);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_borrow_normalized_names() {
+        for &name in &["Иванов", "Петров Водкин", "Бонч-Бруевич", ""] {
+            assert!(matches!(normalize_name(name), Cow::Borrowed(_)));
+        }
+    }
+
+    #[test]
+    fn should_unify_separators() {
+        assert_eq!(normalize_name("\tАнна –  Мария\n"), "Анна-Мария");
+        assert_eq!(normalize_name("Иванов−Сидоров"), "Иванов-Сидоров");
+        assert_eq!(normalize_name("ван\u{a0}\u{a0}дер Берг"), "ван дер Берг");
+        assert_eq!(normalize_name("  "), "");
+    }
+
+    #[test]
+    fn should_fix_homoglyphs_in_cyrillic_words() {
+        assert_eq!(fix_homoglyphs("Ивaнoв"), "Иванов");
+        assert_eq!(fix_homoglyphs("КOВАЛЕНКО"), "КОВАЛЕНКО");
+        assert_eq!(fix_homoglyphs("Д’Apтaньян"), "Д’Артаньян");
+        assert_eq!(fix_homoglyphs("Анна-Mария"), "Анна-Мария");
+        assert!(matches!(fix_homoglyphs("Иванов"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn should_leave_latin_words_alone() {
+        for &name in &["Smith", "Coco", "Jонсон", "Иванов Smith", "APEX"] {
+            assert!(matches!(fix_homoglyphs(name), Cow::Borrowed(_)), "{}", name);
+            assert!(homoglyphs(name).is_empty());
+        }
+    }
+
+    #[test]
+    fn should_report_every_replacement() {
+        let found = homoglyphs("Пeтрoв Cидоров");
+        assert_eq!(
+            found
+                .iter()
+                .map(|homoglyph| (homoglyph.latin, homoglyph.cyrillic))
+                .collect::<Vec<_>>(),
+            [('e', 'е'), ('o', 'о'), ('C', 'С')]
+        );
+        assert_eq!(found[2].range, 11..12);
+    }
+
+    #[test]
+    fn should_titlecase_names() {
+        assert_eq!(titlecase_name(NamePart::Last, "иванов"), "Иванов");
+        assert_eq!(titlecase_name(NamePart::Last, "ИВАНОВ"), "Иванов");
+        assert_eq!(titlecase_name(NamePart::First, "иВАН"), "Иван");
+        assert_eq!(
+            titlecase_name(NamePart::Last, "петров водкин"),
+            "Петров Водкин"
+        );
+        assert_eq!(
+            titlecase_name(NamePart::Last, "ПЕТРОВ\u{a0}-\u{a0}ВОДКИН"),
+            "Петров\u{a0}-\u{a0}Водкин"
+        );
+        assert_eq!(titlecase_name(NamePart::Last, ""), "");
+    }
+
+    #[test]
+    fn should_keep_particles_lowercase() {
+        assert_eq!(
+            titlecase_name(NamePart::Last, "ФОН ДЕР ВАЛЬДЕ"),
+            "фон дер Вальде"
+        );
+        assert_eq!(titlecase_name(NamePart::Last, "аль-фараби"), "аль-Фараби");
+        // A particle on its own is the name itself
+        assert_eq!(titlecase_name(NamePart::Last, "де"), "Де");
+        // Particles only apply to their part
+        assert_eq!(titlecase_name(NamePart::First, "ле ань"), "Ле Ань");
+        assert_eq!(titlecase_name(NamePart::Middle, "мамед кызы"), "Мамед кызы");
+    }
+
+    #[test]
+    fn should_keep_inner_capitals() {
+        assert_eq!(titlecase_name(NamePart::Last, "МакГрегор"), "МакГрегор");
+        assert_eq!(titlecase_name(NamePart::Last, "ДиКаприо"), "ДиКаприо");
+        assert_eq!(titlecase_name(NamePart::Last, "макгрегор"), "Макгрегор");
+        assert_eq!(
+            titlecase_name(NamePart::Last, "Бонч-бруевич"),
+            "Бонч-Бруевич"
+        );
+    }
+
+    #[test]
+    fn should_capitalize_after_apostrophe_prefix() {
+        assert_eq!(titlecase_name(NamePart::Last, "о’нил"), "О’Нил");
+        assert_eq!(titlecase_name(NamePart::Last, "д'артаньян"), "Д'Артаньян");
+        assert_eq!(titlecase_name(NamePart::Last, "Д'АРТАНЬЯН"), "Д'Артаньян");
+        assert_eq!(
+            titlecase_name(NamePart::Last, "о'коннор-смит"),
+            "О'Коннор-Смит"
+        );
+        // Apostrophes inside a word don't start a new part
+        assert_eq!(titlecase_name(NamePart::First, "мар'яна"), "Мар'яна");
+        assert_eq!(titlecase_name(NamePart::Last, "д'"), "Д'");
+    }
+}
@@ -0,0 +1,104 @@
+//! Conversions from and to OpenCorpora grammeme tags, as used by pymorphy.
+
+use super::{Case, Error, Gender};
+
+fn unsupported(tag: &str) -> Error {
+    Error::UnsupportedTag {
+        tag: tag.to_owned(),
+    }
+}
+
+impl Case {
+    /// Converts an OpenCorpora case grammeme ("nomn", "gent", "datv", "accs", "ablt", "loct")
+    pub fn from_opencorpora(tag: &str) -> Result<Case, Error> {
+        match tag {
+            "nomn" => Ok(Case::Nominative),
+            "gent" => Ok(Case::Genitive),
+            "datv" => Ok(Case::Dative),
+            "accs" => Ok(Case::Accusative),
+            "ablt" => Ok(Case::Instrumental),
+            "loct" => Ok(Case::Prepositional),
+            _ => Err(unsupported(tag)),
+        }
+    }
+
+    /// OpenCorpora grammeme of the case
+    pub fn to_opencorpora(self) -> &'static str {
+        match self {
+            Case::Nominative => "nomn",
+            Case::Genitive => "gent",
+            Case::Dative => "datv",
+            Case::Accusative => "accs",
+            Case::Instrumental => "ablt",
+            Case::Prepositional => "loct",
+        }
+    }
+}
+
+impl Gender {
+    /// Converts an OpenCorpora gender grammeme ("masc", "femn", "Ms-f", "GNdr")
+    pub fn from_opencorpora(tag: &str) -> Result<Gender, Error> {
+        match tag {
+            "masc" => Ok(Gender::Male),
+            "femn" => Ok(Gender::Female),
+            "Ms-f" => Ok(Gender::Androgynous),
+            "GNdr" => Ok(Gender::Unknown),
+            _ => Err(unsupported(tag)),
+        }
+    }
+
+    /// OpenCorpora grammeme of the gender
+    pub fn to_opencorpora(self) -> &'static str {
+        match self {
+            Gender::Male => "masc",
+            Gender::Female => "femn",
+            Gender::Androgynous => "Ms-f",
+            Gender::Unknown => "GNdr",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_round_trip_cases() {
+        for &(tag, case) in &[
+            ("nomn", Case::Nominative),
+            ("gent", Case::Genitive),
+            ("datv", Case::Dative),
+            ("accs", Case::Accusative),
+            ("ablt", Case::Instrumental),
+            ("loct", Case::Prepositional),
+        ] {
+            assert_eq!(Case::from_opencorpora(tag), Ok(case));
+            assert_eq!(case.to_opencorpora(), tag);
+        }
+    }
+
+    #[test]
+    fn should_round_trip_genders() {
+        for &(tag, gender) in &[
+            ("masc", Gender::Male),
+            ("femn", Gender::Female),
+            ("Ms-f", Gender::Androgynous),
+            ("GNdr", Gender::Unknown),
+        ] {
+            assert_eq!(Gender::from_opencorpora(tag), Ok(gender));
+            assert_eq!(gender.to_opencorpora(), tag);
+        }
+    }
+
+    #[test]
+    fn should_reject_unsupported_tags() {
+        assert_eq!(
+            Case::from_opencorpora("plur"),
+            Err(Error::UnsupportedTag {
+                tag: "plur".to_owned()
+            })
+        );
+        assert!(Case::from_opencorpora("voct").is_err());
+        assert!(Gender::from_opencorpora("neut").is_err());
+    }
+}
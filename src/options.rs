@@ -0,0 +1,150 @@
+//! Options controlling inflection behavior.
+
+/// How to treat characters that can't be part of a name, such as digits or underscores
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum JunkHandling {
+    /// Match rules against the segment as is, which usually leaves it uninflected
+    #[default]
+    Keep,
+    /// Inflect the last run of letters and keep the surrounding characters untouched,
+    /// so "Иванов2" becomes "Иванову2" and "test_Петров" becomes "test_Петрову"
+    Detach,
+    /// Reject names containing such characters with `Error::InvalidCharacter`
+    Reject,
+}
+
+/// How an `Inflector` treats names in the pre-reform orthography, like "Ѳедоръ"
+#[cfg(feature = "old-orthography")]
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum OldOrthography {
+    /// Match rules against the name as is, which usually leaves old spellings uninflected
+    #[default]
+    Off,
+    /// Inflect the name written with `normalize_old_orthography`, so "Ѳедоръ" becomes
+    /// "Федору"
+    Modernize,
+    /// Inflect the modern spelling and put the old letters back, so "Ѳедоръ" becomes
+    /// "Ѳедору" and "Ѳедоромъ"
+    KeepSpelling,
+}
+
+/// Options for an `Inflector`
+///
+/// New options may be added, so start from `InflectOptions::DEFAULT` and set the fields you
+/// need. Only an `Inflector` applies them; free functions like `lastname` always use the
+/// defaults.
+///
+/// ```
+/// # #[cfg(feature = "lastname-rules")]
+/// # {
+/// use petrovich::{Case, Gender, InflectOptions, Inflector, JunkHandling};
+///
+/// let mut options = InflectOptions::DEFAULT;
+/// options.junk = JunkHandling::Detach;
+/// let inflector = Inflector::default().with_options(options);
+/// assert_eq!(inflector.lastname(Gender::Male, "Иванов2", Case::Dative), "Иванову2");
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+#[non_exhaustive]
+pub struct InflectOptions {
+    /// Handling of digits, underscores and other non-letter characters
+    pub junk: JunkHandling,
+    /// Write endings in capitals when the segment is in capitals, so "ИВАНОВ" becomes
+    /// "ИВАНОВУ" rather than "ИВАНОВу"; on by default. Segments with any lowercase letter,
+    /// including mixed spellings like "МакДональд", get lowercase endings
+    pub preserve_caps: bool,
+    /// Let ё and е match each other, so rules written with ё apply to "Петр" and rules
+    /// written with е apply to "Семён"; the spelling of the name is kept. On by default
+    pub normalize_yo: bool,
+    /// Report names no rule matched with `Error::NoRuleMatched` instead of passing them
+    /// through
+    pub strict: bool,
+    /// Inflect hyphen-separated parts separately, as in "Иванов-Сидоров"; when off a
+    /// hyphenated word is matched as a whole. Words separated by whitespace, as in
+    /// "Петров Водкин", are always inflected separately
+    pub split_hyphens: bool,
+    /// Inflect the given name before a patronymic particle with the first name rules, so
+    /// "Ибрагим оглы" becomes "Ибрагима оглы" rather than staying in the nominative. Only
+    /// applies to middle names; the particle itself never changes
+    pub decline_before_particle: bool,
+    /// Clean up the name with `normalize_name` before matching, so the rules see
+    /// " Иванов — Сидоров " as "Иванов-Сидоров", which is also what is returned
+    pub normalize: bool,
+    /// Replace Latin letters looking like Cyrillic ones with `fix_homoglyphs` before
+    /// matching, so "Иванoв" with a Latin "o" is inflected as "Иванову"; the fixed
+    /// spelling is returned
+    pub fix_homoglyphs: bool,
+    /// Restore ё with `yoficate` before matching, so "Семен" is inflected as "Семёну";
+    /// surnames spelled both ways are left alone
+    #[cfg(feature = "yofication")]
+    pub yoficate: bool,
+    /// Capitalize the name with `titlecase_name` before matching, so "ИВАНОВ" and
+    /// "иванов" both become "Иванову" and "ФОН БРАУН" becomes "фон Брауну"
+    pub titlecase: bool,
+    /// Handling of names in the pre-reform orthography
+    #[cfg(feature = "old-orthography")]
+    pub old_orthography: OldOrthography,
+}
+
+impl InflectOptions {
+    /// Options of `Inflector::default()`, also returned by `InflectOptions::default()`
+    pub const DEFAULT: InflectOptions = InflectOptions {
+        junk: JunkHandling::Keep,
+        preserve_caps: true,
+        normalize_yo: true,
+        strict: false,
+        split_hyphens: true,
+        decline_before_particle: false,
+        normalize: false,
+        fix_homoglyphs: false,
+        #[cfg(feature = "yofication")]
+        yoficate: false,
+        titlecase: false,
+        #[cfg(feature = "old-orthography")]
+        old_orthography: OldOrthography::Off,
+    };
+}
+
+impl Default for InflectOptions {
+    fn default() -> InflectOptions {
+        InflectOptions::DEFAULT
+    }
+}
+
+/// Whether the character is one of the apostrophes typed in names like "Д’Артаньян"
+pub(crate) fn is_apostrophe(c: char) -> bool {
+    matches!(c, '\'' | '’' | '`' | 'ʼ')
+}
+
+/// Whether the word has letters and all of them are Cyrillic, as Russian rules only apply
+/// to such words
+pub(crate) fn is_cyrillic_word(word: &str) -> bool {
+    let mut letters = word.chars().filter(|c| c.is_alphabetic()).peekable();
+    letters.peek().is_some() && letters.all(is_cyrillic)
+}
+
+/// Whether the character is a Cyrillic letter
+pub(crate) fn is_cyrillic(c: char) -> bool {
+    matches!(c, '\u{400}'..='\u{52f}')
+}
+
+/// Whether the character can't be part of a name; whitespace and apostrophes are allowed
+pub(crate) fn is_junk(c: char) -> bool {
+    !(c.is_alphabetic() || c.is_whitespace() || is_apostrophe(c))
+}
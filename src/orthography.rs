@@ -0,0 +1,205 @@
+//! Pre-reform spellings of names from records written before 1918, like "Ѳедоръ".
+
+// Letters dropped by the reform and the letters replacing them
+const OLD_LETTERS: &[(char, char)] = &[
+    ('ѣ', 'е'),
+    ('Ѣ', 'Е'),
+    ('і', 'и'),
+    ('І', 'И'),
+    ('ѳ', 'ф'),
+    ('Ѳ', 'Ф'),
+    ('ѵ', 'и'),
+    ('Ѵ', 'И'),
+];
+
+fn is_hard_sign(c: char) -> bool {
+    matches!(c, 'ъ' | 'Ъ')
+}
+
+/// Writes a name in the pre-reform orthography with modern letters: ѣ becomes е, і and ѵ
+/// become и, ѳ becomes ф, and the hard sign at the end of words is dropped
+///
+/// The hard sign inside words, as in "Подъячевъ", is kept. `InflectOptions::old_orthography`
+/// applies this before matching the rules.
+///
+/// ```
+/// use petrovich::normalize_old_orthography;
+///
+/// assert_eq!(normalize_old_orthography("Ѳедоръ"), "Федор");
+/// assert_eq!(normalize_old_orthography("Алексѣй"), "Алексей");
+/// assert_eq!(normalize_old_orthography("Марія"), "Мария");
+/// assert_eq!(normalize_old_orthography("Подъячевъ"), "Подъячев");
+/// ```
+pub fn normalize_old_orthography(name: &str) -> String {
+    let mut output = String::with_capacity(name.len());
+    let mut chars = name.chars().peekable();
+    while let Some(c) = chars.next() {
+        let word_end = chars.peek().is_none_or(|next| !next.is_alphabetic());
+        if is_hard_sign(c) && word_end {
+            continue;
+        }
+        match OLD_LETTERS.iter().find(|&&(old, _)| old == c) {
+            Some(&(_, modern)) => output.push(modern),
+            None => output.push(c),
+        }
+    }
+    output
+}
+
+// Letters after which the pre-reform orthography writes no hard sign at the end of a word
+fn needs_no_hard_sign(c: char) -> bool {
+    c.to_lowercase()
+        .all(|c| "аеёиоуыэюяйьѣіѵ".contains(c) || !c.is_alphabetic())
+}
+
+/// Puts the old spelling of the name back into its inflected modern form, so "Федору" of
+/// "Ѳедоръ" becomes "Ѳедору" and "Федором" becomes "Ѳедоромъ"
+///
+/// Words are matched by position. The start a word shares with its modern spelling is taken
+/// from the old one, the rest is the modern ending, and words ending in a consonant get
+/// the hard sign when the old word had it. The inflected name is returned as is when its
+/// words don't line up with those of the old one.
+pub(crate) fn restore_old_spelling(old: &str, inflected: &str) -> String {
+    let is_separator = |c: char| c.is_whitespace() || c == '-';
+    if old.split(is_separator).count() != inflected.split(is_separator).count() {
+        return inflected.to_owned();
+    }
+    let mut output = String::with_capacity(inflected.len() + 2);
+    let mut old_words = old.split(is_separator);
+    for piece in inflected.split_inclusive(is_separator) {
+        let (word, separator) = match piece.char_indices().last() {
+            Some((i, c)) if is_separator(c) => piece.split_at(i),
+            _ => (piece, ""),
+        };
+        let old_word = old_words.next().unwrap_or_default();
+        let modern = normalize_old_orthography(old_word);
+        if word == modern {
+            output.push_str(old_word);
+        } else {
+            let shared = modern
+                .chars()
+                .zip(word.chars())
+                .take_while(|(a, b)| a == b)
+                .count();
+            output.extend(old_word.chars().take(shared));
+            output.extend(word.chars().skip(shared));
+            let old_hard_sign = old_word.chars().last().filter(|&c| is_hard_sign(c));
+            match (old_hard_sign, word.chars().last()) {
+                (Some(sign), Some(last)) if !needs_no_hard_sign(last) => output.push(sign),
+                _ => {}
+            }
+        }
+        output.push_str(separator);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Case, Gender, InflectOptions, Inflector, OldOrthography};
+
+    // Names as they are written in parish registers and revision lists of the 19th century
+    const ARCHIVE: &[(&str, &str)] = &[
+        ("Ѳедоръ", "Федор"),
+        ("Алексѣй", "Алексей"),
+        ("Марія", "Мария"),
+        ("Ѳома", "Фома"),
+        ("Іоаннъ", "Иоанн"),
+        ("Сѵмеонъ", "Симеон"),
+        ("Ѳеодосія", "Феодосия"),
+        ("Алексѣевичъ", "Алексеевич"),
+        ("Тимоѳеевна", "Тимофеевна"),
+        ("Бѣлоусовъ", "Белоусов"),
+        ("Ѳедорова-Бѣлая", "Федорова-Белая"),
+    ];
+
+    fn inflector(old_orthography: OldOrthography) -> Inflector {
+        Inflector::default().with_options(InflectOptions {
+            old_orthography,
+            ..InflectOptions::DEFAULT
+        })
+    }
+
+    #[test]
+    fn should_modernize_archival_names() {
+        for &(old, modern) in ARCHIVE {
+            assert_eq!(normalize_old_orthography(old), modern);
+            assert_eq!(normalize_old_orthography(modern), modern);
+        }
+        assert_eq!(normalize_old_orthography("ѲЕДОРЪ"), "ФЕДОР");
+        assert_eq!(normalize_old_orthography("Петръ Ивановъ"), "Петр Иванов");
+    }
+
+    #[test]
+    fn should_inflect_in_modern_spelling() {
+        let inflector = inflector(OldOrthography::Modernize);
+        assert_eq!(
+            inflector.firstname(Gender::Male, "Ѳедоръ", Case::Dative),
+            "Федору"
+        );
+        assert_eq!(
+            inflector.firstname(Gender::Female, "Марія", Case::Genitive),
+            "Марии"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Бѣлоусовъ", Case::Instrumental),
+            "Белоусовым"
+        );
+        assert_eq!(
+            inflector.middlename(Gender::Female, "Тимоѳеевна", Case::Dative),
+            "Тимофеевне"
+        );
+    }
+
+    #[test]
+    fn should_inflect_in_old_spelling() {
+        let inflector = inflector(OldOrthography::KeepSpelling);
+        assert_eq!(
+            inflector.firstname(Gender::Male, "Ѳедоръ", Case::Dative),
+            "Ѳедору"
+        );
+        assert_eq!(
+            inflector.firstname(Gender::Male, "Ѳедоръ", Case::Instrumental),
+            "Ѳедоромъ"
+        );
+        assert_eq!(
+            inflector.firstname(Gender::Male, "Ѳедоръ", Case::Nominative),
+            "Ѳедоръ"
+        );
+        assert_eq!(
+            inflector.firstname(Gender::Male, "Алексѣй", Case::Genitive),
+            "Алексѣя"
+        );
+        assert_eq!(
+            inflector.firstname(Gender::Female, "Марія", Case::Dative),
+            "Маріи"
+        );
+        assert_eq!(
+            inflector.middlename(Gender::Male, "Алексѣевичъ", Case::Dative),
+            "Алексѣевичу"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Female, "Ѳедорова-Бѣлая", Case::Genitive),
+            "Ѳедоровой-Бѣлой"
+        );
+        assert_eq!(
+            inflector
+                .declensions(Gender::Male, "Іоаннъ", crate::NamePart::First)
+                .unwrap()[Case::Instrumental],
+            *"Іоанномъ"
+        );
+    }
+
+    #[test]
+    fn should_leave_names_alone_by_default() {
+        assert_eq!(
+            Inflector::default().firstname(Gender::Male, "Ѳедоръ", Case::Dative),
+            "Ѳедоръ"
+        );
+        assert_eq!(
+            inflector(OldOrthography::KeepSpelling).firstname(Gender::Male, "Иван", Case::Dative),
+            "Ивану"
+        );
+    }
+}
@@ -0,0 +1,246 @@
+//! Explicit forms for particular names, checked before the rules.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use super::rules::{Modifier, Rule, RuleList, RuleParts, Rules};
+use super::{Case, Gender, NamePart};
+
+/// Forms of particular names that take precedence over the rules
+///
+/// Names are matched case-insensitively against a whole word or hyphen-separated part, so
+/// an entry for "Швец" also applies to "Иванов-Швец". The capitalization of the input is
+/// kept in the output. Entries registered for a gender win over entries for any gender.
+///
+/// ```
+/// # #[cfg(feature = "lastname-rules")]
+/// # {
+/// use petrovich::{Case, Gender, Inflector, NamePart, Overrides};
+///
+/// let overrides = Overrides::new()
+///     .with(NamePart::Last, None, "Швец", ["Швеца", "Швецу", "Швеца", "Швецом", "Швеце"])
+///     .with(NamePart::Last, Some(Gender::Male), "Сирота", ["Сирота"; 5]);
+/// let inflector = Inflector::default().with_overrides(&overrides);
+/// assert_eq!(inflector.lastname(Gender::Male, "Швец", Case::Dative), "Швецу");
+/// assert_eq!(inflector.lastname(Gender::Male, "Иванов-Швец", Case::Dative), "Иванову-Швецу");
+/// assert_eq!(inflector.lastname(Gender::Male, "Сирота", Case::Dative), "Сирота");
+/// assert_eq!(inflector.lastname(Gender::Female, "Сирота", Case::Dative), "Сироте");
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Overrides {
+    entries: HashMap<(NamePart, String, Option<Gender>), [String; 5]>,
+}
+
+impl Overrides {
+    /// Creates an empty map
+    pub fn new() -> Overrides {
+        Overrides::default()
+    }
+
+    /// Registers forms of a name for the five oblique cases, from genitive to
+    /// prepositional; `None` applies them to any gender
+    ///
+    /// Returns the forms previously registered for the same name and gender.
+    pub fn insert(
+        &mut self,
+        part: NamePart,
+        gender: Option<Gender>,
+        name: &str,
+        forms: [&str; 5],
+    ) -> Option<[String; 5]> {
+        self.entries.insert(
+            (part, name.to_lowercase(), gender),
+            forms.map(str::to_lowercase),
+        )
+    }
+
+    /// Registers forms of a name, see `insert`
+    pub fn with(
+        mut self,
+        part: NamePart,
+        gender: Option<Gender>,
+        name: &str,
+        forms: [&str; 5],
+    ) -> Overrides {
+        self.insert(part, gender, name, forms);
+        self
+    }
+
+    /// Registers a name that keeps its form in every case, like a foreign surname with a
+    /// stressed ending missing from the embedded exceptions
+    ///
+    /// Returns the forms previously registered for the same name and gender.
+    pub fn insert_indeclinable(
+        &mut self,
+        part: NamePart,
+        gender: Option<Gender>,
+        name: &str,
+    ) -> Option<[String; 5]> {
+        self.insert(part, gender, name, [name; 5])
+    }
+
+    /// Registers a name that keeps its form in every case, see `insert_indeclinable`
+    ///
+    /// ```
+    /// use petrovich::{Case, Gender, Inflector, NamePart, Overrides};
+    ///
+    /// let overrides = Overrides::new().with_indeclinable(NamePart::Last, None, "Гойя");
+    /// let inflector = Inflector::default().with_overrides(&overrides);
+    /// assert_eq!(inflector.lastname(Gender::Male, "Гойя", Case::Dative), "Гойя");
+    /// assert_eq!(inflector.lastname(Gender::Female, "Гойя", Case::Genitive), "Гойя");
+    /// ```
+    pub fn with_indeclinable(
+        mut self,
+        part: NamePart,
+        gender: Option<Gender>,
+        name: &str,
+    ) -> Overrides {
+        self.insert_indeclinable(part, gender, name);
+        self
+    }
+
+    /// Lowercase form registered for a name in the given case
+    pub fn get(&self, part: NamePart, gender: Gender, name: &str, case: Case) -> Option<&str> {
+        let name = name.to_lowercase();
+        let ((_, name, _), forms) = self
+            .entries
+            .get_key_value(&(part, name.clone(), Some(gender)))
+            .or_else(|| self.entries.get_key_value(&(part, name, None)))?;
+        match usize::from(case) {
+            0 => Some(name),
+            index => Some(&forms[index - 1]),
+        }
+    }
+
+    /// Number of registered entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no entries are registered
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn exceptions(&self, part: NamePart) -> Vec<Rule> {
+        let mut exceptions = self
+            .entries
+            .iter()
+            .filter(|((entry_part, _, _), _)| *entry_part == part)
+            .map(|((_, name, gender), forms)| {
+                (gender.is_some(), override_rule(name, *gender, forms))
+            })
+            .collect::<Vec<_>>();
+        // Gender-specific entries go first so that they win over entries for any gender
+        exceptions.sort_by_key(|&(specific, _)| !specific);
+        exceptions.into_iter().map(|(_, rule)| rule).collect()
+    }
+
+    /// Converts the entries into exception rules, ready to be merged in front of other rules
+    pub fn to_rules(&self) -> Rules {
+        Rules::new(
+            RuleList::new(self.exceptions(NamePart::Last), vec![]),
+            RuleList::new(self.exceptions(NamePart::First), vec![]),
+            RuleList::new(self.exceptions(NamePart::Middle), vec![]),
+        )
+    }
+}
+
+// Rewrites the part of the form after the prefix it shares with the name, so the
+// capitalization of that prefix comes from the input
+fn override_modifier(name: &str, form: &str) -> Modifier {
+    let common = name
+        .chars()
+        .zip(form.chars())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let skip = name.chars().count() - common;
+    let ending = form.chars().skip(common).collect::<String>();
+    if skip == 0 && ending.is_empty() {
+        None
+    } else {
+        Some((skip, Cow::Owned(ending)))
+    }
+}
+
+fn override_rule(name: &str, gender: Option<Gender>, forms: &[String; 5]) -> Rule {
+    Rule::from_parts(
+        gender.unwrap_or(Gender::Androgynous),
+        RuleParts {
+            test: Cow::Owned(vec![Cow::Owned(name.to_owned())]),
+            mods: [
+                None,
+                override_modifier(name, &forms[0]),
+                override_modifier(name, &forms[1]),
+                override_modifier(name, &forms[2]),
+                override_modifier(name, &forms[3]),
+                override_modifier(name, &forms[4]),
+            ],
+            tags: Cow::Borrowed(&[]),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Inflector;
+
+    #[test]
+    fn should_preserve_input_capitalization() {
+        let overrides = Overrides::new().with(
+            NamePart::First,
+            None,
+            "ЛЕВ",
+            ["Льва", "Льву", "Льва", "Львом", "Льве"],
+        );
+        let inflector = Inflector::default().with_overrides(&overrides);
+        assert_eq!(
+            inflector.firstname(Gender::Male, "Лев", Case::Genitive),
+            "Льва"
+        );
+        assert_eq!(
+            inflector.firstname(Gender::Male, "лев", Case::Instrumental),
+            "львом"
+        );
+        assert_eq!(
+            inflector.firstname(Gender::Male, "Лев", Case::Nominative),
+            "Лев"
+        );
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_prefer_gender_specific_entries() {
+        let overrides = Overrides::new()
+            .with(
+                NamePart::Last,
+                None,
+                "Сирота",
+                ["Сироты", "Сироте", "Сироту", "Сиротой", "Сироте"],
+            )
+            .with(NamePart::Last, Some(Gender::Male), "Сирота", ["Сирота"; 5]);
+        assert_eq!(
+            overrides.get(NamePart::Last, Gender::Male, "сирота", Case::Dative),
+            Some("сирота")
+        );
+        assert_eq!(
+            overrides.get(NamePart::Last, Gender::Female, "СИРОТА", Case::Dative),
+            Some("сироте")
+        );
+        assert_eq!(
+            overrides.get(NamePart::First, Gender::Female, "Сирота", Case::Dative),
+            None
+        );
+        let inflector = Inflector::default().with_overrides(&overrides);
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Иванов-Сирота", Case::Dative),
+            "Иванову-Сирота"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Female, "Сирота", Case::Genitive),
+            "Сироты"
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! Parts of a full name.
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::Error;
+
+/// Части имени
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum NamePart {
+    /// Имя
+    #[cfg_attr(feature = "serde", serde(alias = "firstname", alias = "имя"))]
+    First,
+    /// Фамилия
+    #[cfg_attr(feature = "serde", serde(alias = "lastname", alias = "фамилия"))]
+    Last,
+    /// Отчество
+    #[cfg_attr(feature = "serde", serde(alias = "middlename", alias = "отчество"))]
+    Middle,
+}
+
+impl NamePart {
+    /// English identifier of the part, e.g. "last"
+    pub fn name(self) -> &'static str {
+        match self {
+            NamePart::First => "first",
+            NamePart::Last => "last",
+            NamePart::Middle => "middle",
+        }
+    }
+
+    /// Russian name of the part, e.g. "фамилия"
+    pub fn russian_name(self) -> &'static str {
+        match self {
+            NamePart::First => "имя",
+            NamePart::Last => "фамилия",
+            NamePart::Middle => "отчество",
+        }
+    }
+}
+
+/// Prints the English identifier, or the Russian name with the alternate flag (`{:#}`)
+impl fmt::Display for NamePart {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.write_str(self.russian_name())
+        } else {
+            f.write_str(self.name())
+        }
+    }
+}
+
+/// Parses English and Russian names, ignoring letter case; "lastname" and the like are
+/// accepted too
+impl FromStr for NamePart {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<NamePart, Error> {
+        match s.trim().to_lowercase().as_str() {
+            "first" | "firstname" | "имя" => Ok(NamePart::First),
+            "last" | "lastname" | "surname" | "фамилия" => Ok(NamePart::Last),
+            "middle" | "middlename" | "patronymic" | "отчество" => Ok(NamePart::Middle),
+            _ => Err(Error::UnknownNamePart(s.to_owned())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_round_trip_display() {
+        for &part in &[NamePart::First, NamePart::Last, NamePart::Middle] {
+            assert_eq!(part.to_string().parse(), Ok(part));
+            assert_eq!(format!("{:#}", part).parse(), Ok(part));
+        }
+        assert_eq!(" Lastname ".parse(), Ok(NamePart::Last));
+        assert_eq!("Отчество".parse(), Ok(NamePart::Middle));
+        assert_eq!(
+            "nickname".parse::<NamePart>(),
+            Err(Error::UnknownNamePart("nickname".to_owned()))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_serialize_as_lowercase_string() {
+        for &part in &[NamePart::First, NamePart::Last, NamePart::Middle] {
+            let json = serde_json::to_string(&part).unwrap();
+            assert_eq!(json, format!("\"{}\"", part));
+            assert_eq!(serde_json::from_str::<NamePart>(&json).unwrap(), part);
+        }
+        assert_eq!(
+            serde_json::from_str::<NamePart>("\"фамилия\"").unwrap(),
+            NamePart::Last
+        );
+    }
+}
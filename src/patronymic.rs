@@ -0,0 +1,375 @@
+//! Patronymics formed from the father's first name.
+
+use super::options::is_cyrillic;
+use super::rules::fold_yo_char;
+use super::{Casing, Gender};
+
+/// Names whose patronymics don't follow the rules, with the male and female ones and the
+/// colloquial male one where it isn't formed by the rules: names in -а and -я, whose
+/// patronymics depend on stress, and names losing or changing a vowel
+const IRREGULAR: &[(&str, &str, &str, Option<&str>)] = &[
+    ("гавриил", "гаврилович", "гавриловна", Some("гаврилыч")),
+    ("даниил", "данилович", "даниловна", Some("данилыч")),
+    ("иона", "ионович", "ионовна", None),
+    ("илья", "ильич", "ильинична", None),
+    ("кузьма", "кузьмич", "кузьминична", None),
+    ("лев", "львович", "львовна", None),
+    ("лука", "лукич", "лукинична", None),
+    ("мина", "минич", "минична", None),
+    ("михаил", "михайлович", "михайловна", Some("михалыч")),
+    ("никита", "никитич", "никитична", None),
+    ("павел", "павлович", "павловна", Some("палыч")),
+    ("петр", "петрович", "петровна", None),
+    ("пётр", "петрович", "петровна", None),
+    ("савва", "саввич", "саввична", None),
+    ("фома", "фомич", "фоминична", None),
+    ("яков", "яковлевич", "яковлевна", Some("яковлич")),
+];
+
+/// Female names ending in a soft sign, which the rules would take for male ones
+const FEMALE_SOFT: &[&str] = &[
+    "адель",
+    "ассоль",
+    "жизель",
+    "любовь",
+    "нинель",
+    "рахиль",
+    "руфь",
+    "эсфирь",
+    "юдифь",
+];
+
+fn is_vowel(c: char) -> bool {
+    "аеёиоуыэюя".contains(c)
+}
+
+// Patronymics of a lowercase name, without allocating them
+enum Forms<'n> {
+    // Endings following the kept start of the name: male, female and colloquial male, as
+    // in "Иваныч" or "Сергеич"
+    Regular {
+        stem: &'n str,
+        male: &'static str,
+        female: &'static str,
+        colloquial: &'static str,
+    },
+    Irregular {
+        male: &'static str,
+        female: &'static str,
+        colloquial: Option<&'static str>,
+    },
+}
+
+impl<'n> Forms<'n> {
+    fn of(lowercase: &'n str) -> Option<Forms<'n>> {
+        if lowercase.chars().count() < 2
+            || !lowercase
+                .chars()
+                .all(|c| is_cyrillic(c) && c.is_alphabetic())
+        {
+            return None;
+        }
+        if let Some(&(_, male, female, colloquial)) = IRREGULAR
+            .iter()
+            .find(|&&(irregular, _, _, _)| irregular == lowercase)
+        {
+            return Some(Forms::Irregular {
+                male,
+                female,
+                colloquial,
+            });
+        }
+        // Drops the last letters of the name before the endings
+        let regular = |dropped: usize, male, female, colloquial| {
+            let end = lowercase
+                .char_indices()
+                .rev()
+                .take(dropped)
+                .last()
+                .map_or(lowercase.len(), |(i, _)| i);
+            Some(Forms::Regular {
+                stem: &lowercase[..end],
+                male,
+                female,
+                colloquial,
+            })
+        };
+        let mut letters = lowercase.chars().rev();
+        match [
+            letters.next(),
+            letters.next(),
+            letters.next(),
+            letters.next(),
+        ] {
+            [Some('й'), Some('и'), Some(before), cluster] if !is_vowel(before) => {
+                if cluster.is_some_and(|c| !is_vowel(c)) {
+                    regular(1, "евич", "евна", "ч")
+                } else {
+                    regular(2, "ьевич", "ьевна", "ьич")
+                }
+            }
+            [Some('й'), Some(before), ..] if is_vowel(before) => regular(1, "евич", "евна", "ич"),
+            [Some('ь'), ..] if FEMALE_SOFT.contains(&lowercase) => None,
+            [Some('ь'), ..] => regular(1, "евич", "евна", "ич"),
+            [Some('ж' | 'ш' | 'ч' | 'щ' | 'ц'), ..] => regular(0, "евич", "евна", "ич"),
+            [Some(last), ..] if !is_vowel(last) && !matches!(last, 'й' | 'ъ') => {
+                regular(0, "ович", "овна", "ыч")
+            }
+            _ => None,
+        }
+    }
+}
+
+// Irregular patronymic written in the case of the name; they all keep its first letter
+fn recase(name: &str, patronymic: &str) -> String {
+    match Casing::of(name) {
+        Casing::Upper => patronymic.to_uppercase(),
+        Casing::Lower => patronymic.to_owned(),
+        Casing::Title | Casing::Mixed => {
+            let mut chars = patronymic.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+    }
+}
+
+/// Patronymic of a son or a daughter of a father with the given first name, like
+/// "Иванович" or "Ивановна" for "Иван"
+///
+/// Names ending in a hard consonant take -ович, names ending in a soft sign, in -й or
+/// in ж, ш, ч, щ or ц take -евич, and names in -ий take -иевич after two consonants, as
+/// in "Дмитриевич", or -ьевич after one, as in "Юрьевич". Names in -а and -я and names
+/// like "Павел" or "Лев" are looked up in a table of irregular patronymics.
+///
+/// Returns `None` for names the rules don't cover, such as names ending in other vowels
+/// or female names in -ь, for names with non-Cyrillic letters or several words, and for
+/// a child gender other than male or female.
+///
+/// ```
+/// use petrovich::{patronymic, Gender};
+///
+/// assert_eq!(patronymic("Иван", Gender::Male).as_deref(), Some("Иванович"));
+/// assert_eq!(patronymic("Илья", Gender::Female).as_deref(), Some("Ильинична"));
+/// assert_eq!(patronymic("Никита", Gender::Male).as_deref(), Some("Никитич"));
+/// assert_eq!(patronymic("Анна", Gender::Male), None);
+/// ```
+pub fn patronymic(father_firstname: &str, child_gender: Gender) -> Option<String> {
+    let female = match child_gender {
+        Gender::Male => false,
+        Gender::Female => true,
+        _ => return None,
+    };
+    let name = father_firstname.trim();
+    let lowercase = name.to_lowercase();
+    match Forms::of(&lowercase)? {
+        Forms::Irregular {
+            male,
+            female: female_form,
+            ..
+        } => Some(recase(name, if female { female_form } else { male })),
+        Forms::Regular {
+            stem,
+            male,
+            female: female_form,
+            ..
+        } => {
+            let ending = if female { female_form } else { male };
+            let stem: String = name.chars().take(stem.chars().count()).collect();
+            Some(stem + &Casing::of(name).apply(ending))
+        }
+    }
+}
+
+/// Whether a patronymic fits a father's first name, see `check_patronymic`
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+pub enum PatronymicMatch {
+    /// Patronymic is one of those formed from the name
+    Matches,
+    /// Patronymic is formed from another name
+    Mismatch,
+    /// No patronymics are known for the name, as with foreign names or names in -а that
+    /// aren't in the table
+    Unknown,
+}
+
+/// Checks whether a patronymic is formed from the father's first name, for either gender
+///
+/// Letter case is ignored, ё and е match each other, and colloquial male forms like
+/// "Иваныч", "Сергеич" or "Палыч" are accepted. Names `patronymic` gives no patronymics
+/// for are `PatronymicMatch::Unknown`. Only the lowercase father's name is allocated.
+///
+/// ```
+/// use petrovich::{check_patronymic, PatronymicMatch};
+///
+/// assert_eq!(check_patronymic("Иван", "Ивановна"), PatronymicMatch::Matches);
+/// assert_eq!(check_patronymic("Иван", "Петрович"), PatronymicMatch::Mismatch);
+/// assert_eq!(check_patronymic("Сергей", "Сергеич"), PatronymicMatch::Matches);
+/// assert_eq!(check_patronymic("John", "Иванович"), PatronymicMatch::Unknown);
+/// ```
+pub fn check_patronymic(father_firstname: &str, patronymic: &str) -> PatronymicMatch {
+    let lowercase = father_firstname.trim().to_lowercase();
+    let forms = match Forms::of(&lowercase) {
+        Some(forms) => forms,
+        None => return PatronymicMatch::Unknown,
+    };
+    let given = || {
+        patronymic
+            .trim()
+            .chars()
+            .flat_map(char::to_lowercase)
+            .map(fold_yo_char)
+    };
+    let is = |stem: &str, ending: &str| {
+        stem.chars()
+            .chain(ending.chars())
+            .map(fold_yo_char)
+            .eq(given())
+    };
+    let matches = match forms {
+        Forms::Regular {
+            stem,
+            male,
+            female,
+            colloquial,
+        } => is(stem, male) || is(stem, female) || is(stem, colloquial),
+        Forms::Irregular {
+            male,
+            female,
+            colloquial,
+        } => is(male, "") || is(female, "") || colloquial.is_some_and(|form| is(form, "")),
+    };
+    if matches {
+        PatronymicMatch::Matches
+    } else {
+        PatronymicMatch::Mismatch
+    }
+}
+
+/// Whether the patronymic is formed from the father's first name, `false` when that is
+/// unknown; see `check_patronymic`
+///
+/// ```
+/// use petrovich::patronymic_matches;
+///
+/// assert!(patronymic_matches("Илья", "Ильинична"));
+/// assert!(!patronymic_matches("Иван", "Петрович"));
+/// ```
+pub fn patronymic_matches(father_firstname: &str, patronymic: &str) -> bool {
+    check_patronymic(father_firstname, patronymic) == PatronymicMatch::Matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn forms(name: &str) -> (Option<String>, Option<String>) {
+        (
+            patronymic(name, Gender::Male),
+            patronymic(name, Gender::Female),
+        )
+    }
+
+    fn some(male: &str, female: &str) -> (Option<String>, Option<String>) {
+        (Some(male.to_owned()), Some(female.to_owned()))
+    }
+
+    #[test]
+    fn should_follow_rules() {
+        assert_eq!(forms("Иван"), some("Иванович", "Ивановна"));
+        assert_eq!(forms("Семён"), some("Семёнович", "Семёновна"));
+        assert_eq!(forms("Игорь"), some("Игоревич", "Игоревна"));
+        assert_eq!(forms("Сергей"), some("Сергеевич", "Сергеевна"));
+        assert_eq!(forms("Николай"), some("Николаевич", "Николаевна"));
+        assert_eq!(forms("Франц"), some("Францевич", "Францевна"));
+        assert_eq!(forms("Юрий"), some("Юрьевич", "Юрьевна"));
+        assert_eq!(forms("Василий"), some("Васильевич", "Васильевна"));
+        assert_eq!(forms("Дмитрий"), some("Дмитриевич", "Дмитриевна"));
+        assert_eq!(forms("Георгий"), some("Георгиевич", "Георгиевна"));
+    }
+
+    #[test]
+    fn should_use_irregular_forms() {
+        assert_eq!(forms("Илья"), some("Ильич", "Ильинична"));
+        assert_eq!(forms("Никита"), some("Никитич", "Никитична"));
+        assert_eq!(forms("Лука"), some("Лукич", "Лукинична"));
+        assert_eq!(forms("Кузьма"), some("Кузьмич", "Кузьминична"));
+        assert_eq!(forms("Павел"), some("Павлович", "Павловна"));
+        assert_eq!(forms("Лев"), some("Львович", "Львовна"));
+        assert_eq!(forms("Пётр"), some("Петрович", "Петровна"));
+        assert_eq!(forms("Михаил"), some("Михайлович", "Михайловна"));
+        assert_eq!(forms("Яков"), some("Яковлевич", "Яковлевна"));
+    }
+
+    #[test]
+    fn should_keep_letter_case() {
+        assert_eq!(forms("ИВАН"), some("ИВАНОВИЧ", "ИВАНОВНА"));
+        assert_eq!(forms("илья"), some("ильич", "ильинична"));
+        assert_eq!(forms("ПАВЕЛ"), some("ПАВЛОВИЧ", "ПАВЛОВНА"));
+    }
+
+    #[test]
+    fn should_reject_unknown_names() {
+        for &name in &[
+            "Анна",
+            "Мария",
+            "Саша",
+            "Любовь",
+            "John",
+            "Иван Петрович",
+            "Й",
+            "",
+        ] {
+            assert_eq!(forms(name), (None, None), "{}", name);
+        }
+        assert_eq!(patronymic("Иван", Gender::Unknown), None);
+        assert_eq!(patronymic("Иван", Gender::Androgynous), None);
+    }
+
+    #[test]
+    fn should_check_patronymics() {
+        for &(father, patronymic) in &[
+            ("Иван", "Иванович"),
+            ("иван", "ИВАНОВНА"),
+            ("Иван", "Иваныч"),
+            ("Сергей", "Сергеевич"),
+            ("Сергей", "Сергеич"),
+            ("Николай", "Николаич"),
+            ("Василий", "Васильич"),
+            ("Дмитрий", "Дмитрич"),
+            ("Пётр", "Петровна"),
+            ("Петр", "Пётрович"),
+            ("Семён", "Семенович"),
+            ("Михаил", "Михалыч"),
+            ("Павел", "Палыч"),
+            ("Илья", "Ильич"),
+        ] {
+            assert_eq!(
+                check_patronymic(father, patronymic),
+                PatronymicMatch::Matches,
+                "{} {}",
+                father,
+                patronymic
+            );
+        }
+        assert_eq!(
+            check_patronymic("Иван", "Петрович"),
+            PatronymicMatch::Mismatch
+        );
+        assert_eq!(
+            check_patronymic("Иван", "Иванович-"),
+            PatronymicMatch::Mismatch
+        );
+        assert_eq!(check_patronymic("Иван", ""), PatronymicMatch::Mismatch);
+        assert_eq!(
+            check_patronymic("Джордж", "Джорджевич"),
+            PatronymicMatch::Matches
+        );
+        assert_eq!(
+            check_patronymic("Анна", "Аннович"),
+            PatronymicMatch::Unknown
+        );
+        assert!(!patronymic_matches("Анна", "Аннович"));
+    }
+}
@@ -0,0 +1,137 @@
+//! Plural forms of family names: "семья Ивановых".
+
+use std::borrow::Cow;
+
+use super::rules::{NoCustomTags, Rule, RuleData, RuleList, RuleParts};
+use super::{match_name, nfc, Case, Gender, InflectOptions, MatchKind};
+
+macro_rules! plural_rule {
+    ($($test:literal),* => -$skip:literal [$($ending:literal),*]) => {
+        Rule {
+            gender: Gender::Androgynous,
+            data: RuleData::Static(&RuleParts {
+                test: Cow::Borrowed(&[$(Cow::Borrowed($test)),*]),
+                mods: [$(Some(($skip, Cow::Borrowed($ending)))),*],
+                tags: Cow::Borrowed(&[]),
+            }),
+        }
+    };
+    ($($test:literal),* => .) => {
+        Rule {
+            gender: Gender::Androgynous,
+            data: RuleData::Static(&RuleParts {
+                test: Cow::Borrowed(&[$(Cow::Borrowed($test)),*]),
+                mods: [None, None, None, None, None, None],
+                tags: Cow::Borrowed(&[]),
+            }),
+        }
+    };
+}
+
+// Endings by case, starting with the nominative
+static PLURAL: RuleList = RuleList {
+    exceptions: Cow::Borrowed(&[plural_rule!("цой", "сой", "хой" => .)]),
+    suffixes: Cow::Borrowed(&[
+        // Possessive surnames: Иванов, Иванова
+        plural_rule!("ов", "ев", "ёв", "ин", "ын" => -0 ["ы", "ых", "ым", "ых", "ыми", "ых"]),
+        plural_rule!("ова", "ева", "ёва", "ина", "ына" => -1 ["ы", "ых", "ым", "ых", "ыми", "ых"]),
+        // Adjective surnames: Толстой, Белый, Толстая
+        plural_rule!("ой", "ый", "ая" => -2 ["ые", "ых", "ым", "ых", "ыми", "ых"]),
+        // Adjective surnames after к, г, х: Полоцкий, Донской, Полоцкая
+        plural_rule!(
+            "кий", "гий", "хий", "кой", "гой", "хой", "кая", "гая", "хая"
+            => -2 ["ие", "их", "им", "их", "ими", "их"]
+        ),
+    ]),
+    index: None,
+    unindexed_exceptions: 0,
+};
+
+// Shortest stem left before the matched suffix, so that "Гой" or "Ов" aren't inflected
+const MIN_STEM: usize = 2;
+
+/// Inflects the plural form of a last name, as in "семья Ивановых"
+///
+/// Handles possessive (-ов, -ев, -ин, -ын) and adjective (-ский, -цкий, -ой, -ый) surnames
+/// given in either the male or the female form. Other surnames, including indeclinable
+/// ones like Шевченко or Черных, and surnames too short to have a stem before the suffix,
+/// like Гой, are returned unchanged. Each part of a hyphenated surname is inflected
+/// separately.
+pub fn lastname_plural(name: &str, case: Case) -> String {
+    let name = &*nfc(name);
+    let segments = match match_name(
+        Gender::Androgynous,
+        name,
+        &PLURAL,
+        &NoCustomTags,
+        &InflectOptions::DEFAULT,
+    ) {
+        Ok(segments) => segments,
+        Err(_) => return name.to_owned(),
+    };
+    let mut output = String::with_capacity(name.len() + 4);
+    for mut segment in segments {
+        if let Some((MatchKind::Suffix, rule)) = segment.rule {
+            let lowercase = segment.segment.to_lowercase();
+            let suffix_len = rule.matched_suffix_len(&lowercase, true).unwrap_or(0);
+            if lowercase.chars().count() < suffix_len + MIN_STEM {
+                segment.rule = None;
+            }
+        }
+        segment.inflect_into(case, &mut output);
+    }
+    output
+}
+
+#[cfg(all(test, feature = "lastname-rules"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_inflect_possessive_surnames() {
+        assert_eq!(lastname_plural("Иванов", Case::Nominative), "Ивановы");
+        assert_eq!(lastname_plural("Иванов", Case::Genitive), "Ивановых");
+        assert_eq!(lastname_plural("Иванова", Case::Dative), "Ивановым");
+        assert_eq!(lastname_plural("Иванов", Case::Accusative), "Ивановых");
+        assert_eq!(lastname_plural("Пушкин", Case::Instrumental), "Пушкиными");
+        assert_eq!(lastname_plural("Королёв", Case::Prepositional), "Королёвых");
+    }
+
+    #[test]
+    fn should_inflect_adjective_surnames() {
+        assert_eq!(lastname_plural("Толстой", Case::Nominative), "Толстые");
+        assert_eq!(lastname_plural("Толстая", Case::Genitive), "Толстых");
+        assert_eq!(lastname_plural("Полоцкий", Case::Nominative), "Полоцкие");
+        assert_eq!(lastname_plural("Полоцкая", Case::Instrumental), "Полоцкими");
+        assert_eq!(lastname_plural("Донской", Case::Dative), "Донским");
+        assert_eq!(lastname_plural("Белый", Case::Prepositional), "Белых");
+    }
+
+    #[test]
+    fn should_keep_indeclinable_surnames() {
+        assert_eq!(lastname_plural("Шевченко", Case::Genitive), "Шевченко");
+        assert_eq!(lastname_plural("Черных", Case::Dative), "Черных");
+        assert_eq!(lastname_plural("Цой", Case::Dative), "Цой");
+    }
+
+    #[test]
+    fn should_keep_surnames_without_stem() {
+        assert_eq!(lastname_plural("Гой", Case::Genitive), "Гой");
+        assert_eq!(lastname_plural("Ов", Case::Dative), "Ов");
+        assert_eq!(lastname_plural("Ин-Гой", Case::Genitive), "Ин-Гой");
+        assert_eq!(lastname_plural("Рогов-Гой", Case::Genitive), "Роговых-Гой");
+        assert_eq!(lastname_plural("Долгой", Case::Genitive), "Долгих");
+    }
+
+    #[test]
+    fn should_inflect_both_parts_of_double_surnames() {
+        assert_eq!(
+            lastname_plural("Иванов-Сидоров", Case::Nominative),
+            "Ивановы-Сидоровы"
+        );
+        assert_eq!(
+            lastname_plural("Иванова-Шевченко", Case::Genitive),
+            "Ивановых-Шевченко"
+        );
+    }
+}
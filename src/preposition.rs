@@ -0,0 +1,235 @@
+//! Prepositions agreeing with the inflected name, like "об Остапенко" or "со Станкевичем".
+
+use std::fmt;
+
+use super::{inflect, Case, Gender, NamePart};
+
+/// Prepositions with a variant depending on the sound the next word starts with
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+#[non_exhaustive]
+pub enum Preposition {
+    /// О, об | _о Иванове, об Остапенко_
+    About,
+    /// С, со | _с Ивановым, со Станкевичем_
+    With,
+    /// В, во | _в Иванове, во Владимире_
+    In,
+    /// К, ко | _к Иванову, ко Льву_
+    To,
+}
+
+fn is_vowel(c: char) -> bool {
+    "аеёиоуыэюя".contains(c)
+}
+
+fn is_consonant(c: char) -> bool {
+    c.is_alphabetic() && !is_vowel(c) && !matches!(c, 'ь' | 'ъ')
+}
+
+impl Preposition {
+    /// Case the preposition takes unless another one is given, e.g. the prepositional
+    /// for "о"
+    pub fn case(self) -> Case {
+        match self {
+            Preposition::About | Preposition::In => Case::Prepositional,
+            Preposition::With => Case::Instrumental,
+            Preposition::To => Case::Dative,
+        }
+    }
+
+    /// Variant of the preposition to put before the word, e.g. "об" before "Остапенко"
+    ///
+    /// О becomes "об" before a vowel sound; е, ё, ю and я start with the sound "й", so
+    /// they take "о". С, в and к take "о" before clusters that are hard to pronounce
+    /// after them: с before с, з, ш or ж followed by a consonant and before щ, в before
+    /// в or ф followed by a consonant, and к before "мн", "вс" and "вт". All of them take
+    /// "о" before a consonant followed by ь and another consonant, as in "Льва".
+    ///
+    /// ```
+    /// use petrovich::Preposition;
+    ///
+    /// assert_eq!(Preposition::About.before("Остапенко"), "об");
+    /// assert_eq!(Preposition::About.before("Юрии"), "о");
+    /// assert_eq!(Preposition::With.before("Станкевичем"), "со");
+    /// assert_eq!(Preposition::With.before("Сергеем"), "с");
+    /// ```
+    pub fn before(self, word: &str) -> &'static str {
+        let mut letters = word
+            .chars()
+            .filter(|c| c.is_alphabetic())
+            .flat_map(char::to_lowercase);
+        let first = letters.next();
+        let second = letters.next();
+        let third = letters.next();
+        let soft_cluster = matches!(
+            (first, second, third),
+            (Some(first), Some('ь'), Some(third)) if is_consonant(first) && is_consonant(third)
+        );
+        let (short, long, long_before) = match self {
+            Preposition::About => {
+                return match first {
+                    Some(first) if "аиоуыэ".contains(first) => "об",
+                    _ => "о",
+                }
+            }
+            Preposition::With => (
+                "с",
+                "со",
+                match (first, second) {
+                    (Some('щ'), _) => true,
+                    (Some('с' | 'з' | 'ш' | 'ж'), Some(second)) => is_consonant(second),
+                    _ => false,
+                },
+            ),
+            Preposition::In => (
+                "в",
+                "во",
+                match (first, second) {
+                    (Some('в' | 'ф'), Some(second)) => is_consonant(second),
+                    _ => false,
+                },
+            ),
+            Preposition::To => (
+                "к",
+                "ко",
+                matches!(
+                    (first, second),
+                    (Some('м'), Some('н')) | (Some('в'), Some('с' | 'т'))
+                ),
+            ),
+        };
+        if long_before || soft_cluster {
+            long
+        } else {
+            short
+        }
+    }
+}
+
+/// Inflected name with the preposition agreeing with it, see `with_preposition`
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct PrepositionalPhrase {
+    /// Lowercase variant of the preposition, as in "об"
+    pub preposition: &'static str,
+    /// Inflected name, as in "Остапенко"
+    pub name: String,
+}
+
+/// Prints the preposition and the name separated by a space
+impl fmt::Display for PrepositionalPhrase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.preposition, self.name)
+    }
+}
+
+/// Inflects a name using the rules for the given part into the case the preposition
+/// takes, and picks the variant of the preposition for the inflected form
+///
+/// ```
+/// # #[cfg(feature = "lastname-rules")]
+/// # {
+/// use petrovich::{with_preposition, Gender, NamePart, Preposition};
+///
+/// let phrase = with_preposition(NamePart::Last, Gender::Male, "Остапенко", Preposition::About);
+/// assert_eq!(phrase.preposition, "об");
+/// assert_eq!(phrase.name, "Остапенко");
+/// assert_eq!(
+///     with_preposition(NamePart::Last, Gender::Male, "Станкевич", Preposition::With).to_string(),
+///     "со Станкевичем"
+/// );
+/// # }
+/// ```
+pub fn with_preposition(
+    part: NamePart,
+    gender: Gender,
+    name: &str,
+    preposition: Preposition,
+) -> PrepositionalPhrase {
+    with_preposition_in(part, gender, name, preposition, preposition.case())
+}
+
+/// Same as `with_preposition`, but inflects the name into the given case, as in
+/// "в Иванова" with the accusative
+pub fn with_preposition_in(
+    part: NamePart,
+    gender: Gender,
+    name: &str,
+    preposition: Preposition,
+    case: Case,
+) -> PrepositionalPhrase {
+    let name = inflect(part, gender, name, case);
+    PrepositionalPhrase {
+        preposition: preposition.before(&name),
+        name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_pick_ob_before_vowels() {
+        for &word in &["Остапенко", "Иванове", "Анне", "Ульяне", "Эдуарде"]
+        {
+            assert_eq!(Preposition::About.before(word), "об", "{}", word);
+        }
+        for &word in &["Евгении", "Ёлкине", "Юрии", "Яне", "Петрове", ""]
+        {
+            assert_eq!(Preposition::About.before(word), "о", "{}", word);
+        }
+    }
+
+    #[test]
+    fn should_pick_long_form_before_clusters() {
+        assert_eq!(Preposition::With.before("Станкевичем"), "со");
+        assert_eq!(Preposition::With.before("Збруевым"), "со");
+        assert_eq!(Preposition::With.before("Ждановым"), "со");
+        assert_eq!(Preposition::With.before("Щукиным"), "со");
+        assert_eq!(Preposition::With.before("Львом"), "со");
+        assert_eq!(Preposition::With.before("Сергеем"), "с");
+        assert_eq!(Preposition::With.before("Ивановым"), "с");
+        assert_eq!(Preposition::In.before("Владимире"), "во");
+        assert_eq!(Preposition::In.before("Фроловой"), "во");
+        assert_eq!(Preposition::In.before("Вере"), "в");
+        assert_eq!(Preposition::To.before("Льву"), "ко");
+        assert_eq!(Preposition::To.before("Мнишек"), "ко");
+        assert_eq!(Preposition::To.before("Владимиру"), "к");
+        assert_eq!(Preposition::To.before("Ивану"), "к");
+    }
+
+    #[cfg(all(feature = "firstname-rules", feature = "lastname-rules"))]
+    #[test]
+    fn should_inflect_into_case_of_preposition() {
+        let phrase = |part, gender, name, preposition| {
+            with_preposition(part, gender, name, preposition).to_string()
+        };
+        assert_eq!(
+            phrase(NamePart::Last, Gender::Male, "Иванов", Preposition::About),
+            "об Иванове"
+        );
+        assert_eq!(
+            phrase(NamePart::First, Gender::Male, "Лев", Preposition::With),
+            "со Львом"
+        );
+        assert_eq!(
+            phrase(NamePart::First, Gender::Male, "Лев", Preposition::To),
+            "ко Льву"
+        );
+        assert_eq!(
+            phrase(NamePart::First, Gender::Female, "Анна", Preposition::To),
+            "к Анне"
+        );
+        assert_eq!(
+            with_preposition_in(
+                NamePart::Last,
+                Gender::Male,
+                "Иванов",
+                Preposition::In,
+                Case::Accusative
+            )
+            .to_string(),
+            "в Иванова"
+        );
+    }
+}
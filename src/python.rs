@@ -0,0 +1,201 @@
+//! Python module built with PyO3.
+//!
+//! Genders, cases and name parts are passed as strings in any form their `FromStr`
+//! implementations accept, like "male", "ж" or "dative"; invalid ones raise `ValueError`.
+//!
+//! ```python
+//! import petrovich
+//!
+//! petrovich.lastname("male", "Иванов", "dative")  # 'Иванову'
+//! petrovich.detect_gender(middlename="Петровна")  # 'female'
+//! ```
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use super::{inflect, Case, Error, Gender, NamePart};
+
+impl From<Error> for PyErr {
+    fn from(error: Error) -> PyErr {
+        PyValueError::new_err(error.to_string())
+    }
+}
+
+#[cfg(any(
+    feature = "firstname-rules",
+    feature = "lastname-rules",
+    feature = "middlename-rules"
+))]
+fn inflect_str(part: NamePart, gender: &str, name: &str, case: &str) -> PyResult<String> {
+    Ok(inflect(part, gender.parse()?, name, case.parse()?))
+}
+
+/// Inflects first name, e.g. `firstname("male", "Лёша", "dative")` gives "Лёше"
+#[cfg(feature = "firstname-rules")]
+#[pyfunction]
+#[pyo3(name = "firstname")]
+fn py_firstname(gender: &str, name: &str, case: &str) -> PyResult<String> {
+    inflect_str(NamePart::First, gender, name, case)
+}
+
+/// Inflects last name, see `firstname`
+#[cfg(feature = "lastname-rules")]
+#[pyfunction]
+#[pyo3(name = "lastname")]
+fn py_lastname(gender: &str, name: &str, case: &str) -> PyResult<String> {
+    inflect_str(NamePart::Last, gender, name, case)
+}
+
+/// Inflects middle name, see `firstname`
+#[cfg(feature = "middlename-rules")]
+#[pyfunction]
+#[pyo3(name = "middlename")]
+fn py_middlename(gender: &str, name: &str, case: &str) -> PyResult<String> {
+    inflect_str(NamePart::Middle, gender, name, case)
+}
+
+/// Detects gender from the parts given, as its English identifier like "female"
+#[cfg(feature = "gender-detection")]
+#[pyfunction]
+#[pyo3(
+    name = "detect_gender",
+    signature = (lastname = None, firstname = None, middlename = None)
+)]
+fn py_detect_gender(
+    lastname: Option<&str>,
+    firstname: Option<&str>,
+    middlename: Option<&str>,
+) -> &'static str {
+    super::detect_gender(lastname, firstname, middlename).name()
+}
+
+/// Inflects a list of `(gender, name, case)` tuples of the same part, keeping their order
+///
+/// Every tuple is checked before inflecting, and the inflection itself runs with the GIL
+/// released.
+#[pyfunction]
+fn inflect_batch(
+    py: Python,
+    part: &str,
+    items: Vec<(String, String, String)>,
+) -> PyResult<Vec<String>> {
+    let part: NamePart = part.parse()?;
+    let items = items
+        .iter()
+        .enumerate()
+        .map(|(i, (gender, name, case))| {
+            let parsed = gender
+                .parse::<Gender>()
+                .and_then(|gender| Ok((gender, name.as_str(), case.parse::<Case>()?)));
+            parsed.map_err(|error| PyValueError::new_err(format!("items[{}]: {}", i, error)))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(py.allow_threads(|| {
+        items
+            .iter()
+            .map(|&(gender, name, case)| inflect(part, gender, name, case))
+            .collect()
+    }))
+}
+
+/// `petrovich` Python module
+#[pymodule]
+fn petrovich(module: &Bound<PyModule>) -> PyResult<()> {
+    #[cfg(feature = "firstname-rules")]
+    module.add_function(wrap_pyfunction!(py_firstname, module)?)?;
+    #[cfg(feature = "lastname-rules")]
+    module.add_function(wrap_pyfunction!(py_lastname, module)?)?;
+    #[cfg(feature = "middlename-rules")]
+    module.add_function(wrap_pyfunction!(py_middlename, module)?)?;
+    #[cfg(feature = "gender-detection")]
+    module.add_function(wrap_pyfunction!(py_detect_gender, module)?)?;
+    module.add_function(wrap_pyfunction!(inflect_batch, module)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pyo3::types::PyDict;
+
+    fn with_module<T>(f: impl FnOnce(Python, &Bound<PyDict>) -> T) -> T {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let module = PyModule::new(py, "petrovich").unwrap();
+            petrovich(&module).unwrap();
+            let globals = PyDict::new(py);
+            globals.set_item("petrovich", module).unwrap();
+            f(py, &globals)
+        })
+    }
+
+    fn eval(code: &str) -> PyResult<String> {
+        with_module(|py, globals| {
+            let code = std::ffi::CString::new(code).unwrap();
+            py.eval(&code, Some(globals), None)?.repr()?.extract()
+        })
+    }
+
+    #[test]
+    fn should_inflect_parts() {
+        assert_eq!(
+            eval("petrovich.lastname('male', 'Иванов', 'dative')").unwrap(),
+            "'Иванову'"
+        );
+        assert_eq!(
+            eval("petrovich.firstname('ж', 'Анна', 'родительный')").unwrap(),
+            "'Анны'"
+        );
+        assert_eq!(
+            eval("petrovich.middlename('male', 'Сергеевич', 'instrumental')").unwrap(),
+            "'Сергеевичем'"
+        );
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_detect_gender() {
+        assert_eq!(
+            eval("petrovich.detect_gender(middlename='Петровна')").unwrap(),
+            "'female'"
+        );
+        assert_eq!(eval("petrovich.detect_gender()").unwrap(), "'unknown'");
+    }
+
+    #[test]
+    fn should_inflect_batch() {
+        assert_eq!(
+            eval(
+                "petrovich.inflect_batch('last', \
+                 [('male', 'Иванов', 'dative'), ('female', 'Иванова', 'genitive')])"
+            )
+            .unwrap(),
+            "['Иванову', 'Ивановой']"
+        );
+    }
+
+    #[test]
+    fn should_raise_value_error() {
+        let message = |code| {
+            with_module(|py, globals| {
+                let code = std::ffi::CString::new(code).unwrap();
+                let error = py.eval(&code, Some(globals), None).unwrap_err();
+                assert!(error.is_instance_of::<PyValueError>(py));
+                error.value(py).to_string()
+            })
+        };
+        assert_eq!(
+            message("petrovich.lastname('мужик', 'Иванов', 'dative')"),
+            "unknown gender \"мужик\""
+        );
+        assert_eq!(
+            message("petrovich.inflect_batch('last', [('male', 'Иванов', 'x')])"),
+            "items[0]: unknown grammatical case \"x\""
+        );
+        assert_eq!(
+            message("petrovich.inflect_batch('имя отчество', [])"),
+            "unknown name part \"имя отчество\""
+        );
+    }
+}
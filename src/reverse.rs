@@ -0,0 +1,385 @@
+//! Recovery of the nominative and the case from an inflected name.
+
+use std::cmp::Reverse;
+
+use super::rules::{NoCustomTags, Rule, RULES};
+use super::{
+    inflect, match_name, split_segments, Case, Casing, Gender, InflectOptions, MatchKind, NamePart,
+};
+
+/// Possible nominative form of an inflected name
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct NominativeCandidate {
+    /// Name in the nominative case
+    pub nominative: String,
+    /// Case the inflected name is in
+    pub case: Case,
+    /// Gender whose rules inflect the nominative into the given name
+    pub gender: Gender,
+}
+
+// Forms of a segment that some rule could have inflected into it, including the segment
+// itself for rules keeping the name as is
+fn segment_candidates<'r>(segment: &str, rules: impl Iterator<Item = &'r Rule>) -> Vec<String> {
+    let lowercase = segment.to_lowercase();
+    let chars = segment.chars().collect::<Vec<_>>();
+    let mut candidates = vec![segment.to_owned()];
+    for rule in rules {
+        for case in Case::iter().skip(1) {
+            let (skip, ending) = match rule.modifier(case) {
+                Some(modifier) => modifier,
+                None => continue,
+            };
+            if !lowercase.ends_with(ending) {
+                continue;
+            }
+            let stem = &chars[..chars.len() - ending.chars().count()];
+            for test in rule.tests() {
+                let test_len = test.chars().count();
+                if skip > test_len {
+                    continue;
+                }
+                let restored = test.chars().skip(test_len - skip).collect::<String>();
+                let restored = Casing::of(segment).apply(&restored);
+                let candidate = stem.iter().collect::<String>() + &restored;
+                if candidate.to_lowercase().ends_with(test)
+                    && !candidates
+                        .iter()
+                        .any(|known| known.to_lowercase() == candidate.to_lowercase())
+                {
+                    candidates.push(candidate);
+                }
+            }
+        }
+    }
+    candidates
+}
+
+// Genders to try: both male and female when the gender isn't known
+fn genders(gender: Gender) -> &'static [Gender] {
+    match gender {
+        Gender::Male => &[Gender::Male],
+        Gender::Female => &[Gender::Female],
+        _ => &[Gender::Male, Gender::Female],
+    }
+}
+
+/// Finds the nominative forms that inflect into the given name with the rules for the part
+///
+/// The rule endings are run in reverse and every reconstructed form is checked by inflecting
+/// it again, so each candidate is exact. A name can be ambiguous, so all candidates are
+/// returned: forms that differ from the name come first, then the name itself for rules
+/// keeping it unchanged. Within each group forms keeping fewer words of the name as they
+/// are come first, then forms matched by more specific rules, such as exceptions or longer
+/// suffixes, then candidates are ordered by case. `Gender::Androgynous` and
+/// `Gender::Unknown` look for both male and female forms. The nominative itself isn't
+/// reported as a case.
+///
+/// ```
+/// # #[cfg(feature = "lastname-rules")]
+/// # {
+/// use petrovich::{to_nominative, Case, Gender, NamePart};
+///
+/// let candidates = to_nominative(NamePart::Last, Gender::Unknown, "Иванову");
+/// assert!(candidates.iter().any(|c| c.nominative == "Иванов" && c.case == Case::Dative));
+/// assert!(candidates.iter().any(|c| c.nominative == "Иванова" && c.case == Case::Accusative));
+/// # }
+/// ```
+pub fn to_nominative(part: NamePart, gender: Gender, name: &str) -> Vec<NominativeCandidate> {
+    let genders = genders(gender);
+    let list = RULES.list(part);
+    // Every combination of candidate segments is a possible nominative
+    let mut nominatives = vec![String::new()];
+    for (segment, separator) in split_segments(name) {
+        let candidates =
+            segment_candidates(segment, list.exceptions().iter().chain(list.suffixes()));
+        nominatives = nominatives
+            .iter()
+            .flat_map(|prefix| {
+                candidates
+                    .iter()
+                    .map(move |candidate| [prefix, candidate.as_str(), separator].concat())
+            })
+            .collect();
+    }
+    let lowercase = name.to_lowercase();
+    let mut found = Vec::new();
+    for nominative in nominatives {
+        for &gender in genders {
+            let specificity = specificity(part, gender, &nominative);
+            for case in Case::iter().skip(1) {
+                if inflect(part, gender, &nominative, case).to_lowercase() == lowercase {
+                    let candidate = NominativeCandidate {
+                        nominative: nominative.clone(),
+                        case,
+                        gender,
+                    };
+                    found.push((specificity, candidate));
+                }
+            }
+        }
+    }
+    // Segments the nominative shares with the name, as "Петрову" in "Петрову Водкин"
+    let kept = |nominative: &str| {
+        split_segments(nominative)
+            .zip(split_segments(name))
+            .filter(|((a, _), (b, _))| a.to_lowercase() == b.to_lowercase())
+            .count()
+    };
+    found.sort_by_key(|(specificity, candidate)| {
+        (
+            candidate.nominative.to_lowercase() == lowercase,
+            kept(&candidate.nominative),
+            Reverse(*specificity),
+            usize::from(candidate.case),
+        )
+    });
+    found.into_iter().map(|(_, candidate)| candidate).collect()
+}
+
+// How specific the rules matching the nominative are: exceptions beat any suffix and longer
+// suffixes beat shorter ones, summed over the segments
+fn specificity(part: NamePart, gender: Gender, nominative: &str) -> usize {
+    let segments = match_name(
+        gender,
+        nominative,
+        RULES.list(part),
+        &NoCustomTags,
+        &InflectOptions::DEFAULT,
+    )
+    .unwrap_or_default();
+    segments
+        .iter()
+        .map(|segment| match segment.rule {
+            Some((MatchKind::Exception, _)) => segment.segment.chars().count() + 1,
+            Some((MatchKind::Suffix, rule)) => rule
+                .matched_suffix_len(&segment.segment.to_lowercase(), false)
+                .unwrap_or(0),
+            None => 0,
+        })
+        .sum()
+}
+
+/// Finds the nominative forms of a first name, see `to_nominative`
+#[cfg(feature = "firstname-rules")]
+pub fn firstname_to_nominative(gender: Gender, name: &str) -> Vec<NominativeCandidate> {
+    to_nominative(NamePart::First, gender, name)
+}
+
+/// Finds the nominative forms of a last name, see `to_nominative`
+#[cfg(feature = "lastname-rules")]
+pub fn lastname_to_nominative(gender: Gender, name: &str) -> Vec<NominativeCandidate> {
+    to_nominative(NamePart::Last, gender, name)
+}
+
+/// Finds the nominative forms of a middle name, see `to_nominative`
+#[cfg(feature = "middlename-rules")]
+pub fn middlename_to_nominative(gender: Gender, name: &str) -> Vec<NominativeCandidate> {
+    to_nominative(NamePart::Middle, gender, name)
+}
+
+/// Finds the cases the name can be in, with the rules for the given part
+///
+/// Cases come in the order of `Case::ALL`. Readings in which the name differs from its
+/// nominative are preferred: "Ивановым" is reported as instrumental only, although the
+/// rules would decline a surname "Ивановым" too. Without such readings the name is taken
+/// as a nominative when the rules decline it, and as consistent with every case when they
+/// don't, as for indeclinable names.
+///
+/// ```
+/// # #[cfg(all(feature = "firstname-rules", feature = "lastname-rules"))]
+/// # {
+/// use petrovich::{detect_cases, Case, Gender, NamePart};
+///
+/// assert_eq!(detect_cases(Gender::Male, "Ивановым", NamePart::Last), [Case::Instrumental]);
+/// assert_eq!(
+///     detect_cases(Gender::Female, "Анне", NamePart::First),
+///     [Case::Dative, Case::Prepositional]
+/// );
+/// # }
+/// ```
+pub fn detect_cases(gender: Gender, name: &str, part: NamePart) -> Vec<Case> {
+    let lowercase = name.to_lowercase();
+    let candidates = to_nominative(part, gender, name);
+    let cases = Case::iter()
+        .filter(|&case| {
+            candidates.iter().any(|candidate| {
+                candidate.case == case && candidate.nominative.to_lowercase() != lowercase
+            })
+        })
+        .collect::<Vec<_>>();
+    if !cases.is_empty() {
+        return cases;
+    }
+    let declines = Case::iter().skip(1).any(|case| {
+        genders(gender)
+            .iter()
+            .any(|&gender| inflect(part, gender, name, case).to_lowercase() != lowercase)
+    });
+    if declines {
+        vec![Case::Nominative]
+    } else {
+        Case::ALL.to_vec()
+    }
+}
+
+/// Finds the case of the name when it can be in only one, see `detect_cases`
+pub fn detect_case(gender: Gender, name: &str, part: NamePart) -> Option<Case> {
+    match *detect_cases(gender, name, part).as_slice() {
+        [case] => Some(case),
+        _ => None,
+    }
+}
+
+/// Finds the cases all given parts of a full name can be in at once, see `detect_cases`
+///
+/// ```
+/// # #[cfg(all(feature = "firstname-rules", feature = "lastname-rules", feature = "middlename-rules"))]
+/// # {
+/// use petrovich::{detect_fullname_cases, Case, Gender};
+///
+/// assert_eq!(
+///     detect_fullname_cases(Gender::Male, Some("Иванову"), Some("Ивану"), Some("Ивановичу")),
+///     [Case::Dative]
+/// );
+/// # }
+/// ```
+pub fn detect_fullname_cases(
+    gender: Gender,
+    last: Option<&str>,
+    first: Option<&str>,
+    middle: Option<&str>,
+) -> Vec<Case> {
+    let parts = [
+        (NamePart::Last, last),
+        (NamePart::First, first),
+        (NamePart::Middle, middle),
+    ];
+    let mut cases = Case::ALL.to_vec();
+    for &(part, name) in &parts {
+        if let Some(name) = name {
+            let part_cases = detect_cases(gender, name, part);
+            cases.retain(|case| part_cases.contains(case));
+        }
+    }
+    cases
+}
+
+#[cfg(all(test, feature = "lastname-rules"))]
+mod tests {
+    use super::*;
+
+    fn nominatives(candidates: &[NominativeCandidate], case: Case) -> Vec<&str> {
+        candidates
+            .iter()
+            .filter(|candidate| candidate.case == case)
+            .map(|candidate| candidate.nominative.as_str())
+            .collect()
+    }
+
+    #[cfg(all(feature = "firstname-rules", feature = "middlename-rules"))]
+    #[test]
+    fn should_recover_nominative() {
+        let candidates = lastname_to_nominative(Gender::Male, "Иванова");
+        assert_eq!(nominatives(&candidates, Case::Genitive), ["Иванов"]);
+        assert_eq!(nominatives(&candidates, Case::Accusative), ["Иванов"]);
+
+        let candidates = firstname_to_nominative(Gender::Male, "Ивана");
+        assert_eq!(candidates[0].nominative, "Иван");
+        let candidates = middlename_to_nominative(Gender::Male, "Ивановича");
+        assert_eq!(candidates[0].nominative, "Иванович");
+        let candidates = firstname_to_nominative(Gender::Male, "Льва");
+        assert_eq!(candidates[0].nominative, "Лев");
+        let candidates = lastname_to_nominative(Gender::Male, "Кравцу");
+        assert_eq!(candidates[0].nominative, "Кравец");
+        assert_eq!(candidates[0].case, Case::Dative);
+    }
+
+    #[test]
+    fn should_report_ambiguity() {
+        let candidates = lastname_to_nominative(Gender::Unknown, "Иванову");
+        assert!(candidates.contains(&NominativeCandidate {
+            nominative: "Иванов".to_owned(),
+            case: Case::Dative,
+            gender: Gender::Male,
+        }));
+        assert!(candidates.contains(&NominativeCandidate {
+            nominative: "Иванова".to_owned(),
+            case: Case::Accusative,
+            gender: Gender::Female,
+        }));
+    }
+
+    #[test]
+    fn should_recover_hyphenated_names() {
+        let candidates = lastname_to_nominative(Gender::Female, "Ивановой-Сидоровой");
+        assert_eq!(candidates[0].nominative, "Иванова-Сидорова");
+        assert_eq!(candidates[0].case, Case::Genitive);
+        assert!(nominatives(&candidates, Case::Dative).contains(&"Иванова-Сидорова"));
+        let candidates = lastname_to_nominative(Gender::Male, "Бонч-Бруевичу");
+        assert_eq!(
+            nominatives(&candidates, Case::Dative),
+            ["Бонч-Бруевич", "Бонч-Бруевичу"]
+        );
+    }
+
+    #[test]
+    fn should_recover_space_separated_names() {
+        let candidates = lastname_to_nominative(Gender::Male, "Петрову Водкину");
+        assert_eq!(candidates[0].nominative, "Петров Водкин");
+        assert_eq!(candidates[0].case, Case::Dative);
+        assert!(candidates
+            .iter()
+            .all(|candidate| candidate.nominative.contains(' ')));
+    }
+
+    #[cfg(feature = "middlename-rules")]
+    #[test]
+    fn should_detect_case() {
+        assert_eq!(
+            detect_case(Gender::Male, "Иванов", NamePart::Last),
+            Some(Case::Nominative)
+        );
+        assert_eq!(
+            detect_case(Gender::Male, "Ивановичем", NamePart::Middle),
+            Some(Case::Instrumental)
+        );
+        assert_eq!(
+            detect_cases(Gender::Female, "Ивановой", NamePart::Last),
+            [
+                Case::Genitive,
+                Case::Dative,
+                Case::Instrumental,
+                Case::Prepositional
+            ]
+        );
+        assert_eq!(detect_case(Gender::Male, "Шевченко", NamePart::Last), None);
+        assert_eq!(
+            detect_cases(Gender::Male, "Шевченко", NamePart::Last),
+            Case::ALL
+        );
+    }
+
+    #[cfg(feature = "firstname-rules")]
+    #[test]
+    fn should_detect_fullname_case() {
+        assert_eq!(
+            detect_fullname_cases(Gender::Female, Some("Ивановой"), Some("Анне"), None),
+            [Case::Dative, Case::Prepositional]
+        );
+        assert_eq!(
+            detect_fullname_cases(Gender::Female, Some("Ивановой"), Some("Анну"), None),
+            []
+        );
+    }
+
+    #[test]
+    fn should_keep_indeclinable_names() {
+        let candidates = lastname_to_nominative(Gender::Male, "Шевченко");
+        assert_eq!(candidates.len(), 5);
+        assert!(candidates
+            .iter()
+            .all(|candidate| candidate.nominative == "Шевченко"));
+        assert!(lastname_to_nominative(Gender::Male, "Иванов").is_empty());
+    }
+}
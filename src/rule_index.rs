@@ -0,0 +1,101 @@
+//! Lookup tables generated by the build script for the embedded rules.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use super::rules::{embedded_test, fold_yo_char};
+
+/// Node of the suffix trie of a `RuleIndex`
+pub(crate) struct SuffixNode {
+    /// Range of `RuleIndex::children` with the next characters towards the start of the
+    /// name
+    pub(crate) children: (u32, u32),
+    /// Range of `RuleIndex::rules` with the suffix rules having a test spelled by the
+    /// path to the node
+    pub(crate) rules: (u32, u32),
+}
+
+/// Exception and suffix tests of a rule list with ё folded to е, so the rules matching a
+/// name are found without checking every test
+pub(crate) struct RuleIndex {
+    /// Embedded exception tests with the exception rules having them, sorted by the test
+    /// with ё folded and then by rule
+    pub(crate) exceptions: &'static [(u32, u32)],
+    /// Trie of the suffix tests read from the end, starting with the root
+    pub(crate) suffixes: &'static [SuffixNode],
+    /// Characters of the trie edges with the nodes they lead to, sorted for every node
+    pub(crate) children: &'static [(char, u32)],
+    /// Suffix rules of the trie nodes, in listed order for every node
+    pub(crate) rules: &'static [u32],
+}
+
+impl RuleIndex {
+    /// Exception rules with a test equal to the lowercase name, in listed order; ё and е
+    /// match each other
+    pub(crate) fn exceptions(&self, name: &str) -> impl Iterator<Item = usize> {
+        let compare = |test: u32| {
+            embedded_test(test)
+                .chars()
+                .map(fold_yo_char)
+                .cmp(name.chars().map(fold_yo_char))
+        };
+        let start = self
+            .exceptions
+            .partition_point(|&(test, _)| compare(test) == Ordering::Less);
+        let len =
+            self.exceptions[start..].partition_point(|&(test, _)| compare(test) == Ordering::Equal);
+        self.exceptions[start..start + len]
+            .iter()
+            .map(|&(_, rule)| rule as usize)
+    }
+
+    fn child(&self, node: usize, c: char) -> Option<usize> {
+        let (start, end) = self.suffixes[node].children;
+        let children = &self.children[start as usize..end as usize];
+        children
+            .binary_search_by_key(&c, |&(child, _)| child)
+            .ok()
+            .map(|i| children[i].1 as usize)
+    }
+
+    // Walks the name from its end and offers the rules of every node on the way to `pick`,
+    // along with the length of their tests, from the longest suffix to the empty one
+    fn find_suffix_from<T>(
+        &self,
+        node: usize,
+        len: usize,
+        chars: &mut impl Iterator<Item = char>,
+        pick: &mut impl FnMut(usize, &[u32]) -> Option<T>,
+    ) -> Option<T> {
+        chars
+            .next()
+            .and_then(|c| self.child(node, fold_yo_char(c)))
+            .and_then(|child| self.find_suffix_from(child, len + 1, chars, pick))
+            .or_else(|| {
+                let (start, end) = self.suffixes[node].rules;
+                match &self.rules[start as usize..end as usize] {
+                    [] => None,
+                    rules => pick(len, rules),
+                }
+            })
+    }
+
+    /// First result `pick` gives for the suffix rules with a test the lowercase name ends
+    /// with, trying longer tests first; ё and е match each other
+    pub(crate) fn find_suffix<T>(
+        &self,
+        name: &str,
+        mut pick: impl FnMut(usize, &[u32]) -> Option<T>,
+    ) -> Option<T> {
+        self.find_suffix_from(0, 0, &mut name.chars().rev(), &mut pick)
+    }
+}
+
+impl fmt::Debug for RuleIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RuleIndex")
+            .field("exceptions", &self.exceptions.len())
+            .field("suffixes", &self.suffixes.len())
+            .finish()
+    }
+}
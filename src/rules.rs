@@ -0,0 +1,965 @@
+//! Rule model shared by the embedded rule data and user-supplied rules.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use super::options::is_apostrophe;
+use super::rule_index::{RuleIndex, SuffixNode};
+use super::{inflect_builtin, Case, Gender, NamePart};
+
+pub(crate) type Modifier = Option<(usize, Cow<'static, str>)>;
+
+/// Тег правила
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum RuleTag {
+    /// Rule only applies to the non-final parts of a compound name
+    FirstWord,
+    /// Rule drops a fleeting vowel, as in "Починок" → "Починка", so it only applies to
+    /// segments with another vowel left: "Блок" keeps its vowel
+    FleetingVowel,
+    /// Rule matches a particle following a name, like "оглы" in "Ибрагим оглы"; it only
+    /// applies to the last of several words, and the words before it are left unchanged
+    Particle,
+    /// Rule matches the stressed ending of a borrowed name, as in "Николя", so it only
+    /// applies to segments of three syllables or more: "Коля" keeps declining
+    StressedEnding,
+    /// User-defined tag, checked by a predicate registered on an `Inflector`
+    Custom(Cow<'static, str>),
+}
+
+use RuleTag::*;
+
+/// Context passed to tag predicates while a name segment is being matched
+#[derive(Clone, Copy, Debug)]
+pub struct MatchContext<'a> {
+    pub(crate) segment: &'a str,
+    pub(crate) index: usize,
+    pub(crate) count: usize,
+    pub(crate) name: &'a str,
+}
+
+impl<'a> MatchContext<'a> {
+    /// Segment being matched, a word or a hyphen-separated part of one
+    pub fn segment(&self) -> &'a str {
+        self.segment
+    }
+
+    /// Index of the segment within the name
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Total number of segments in the name
+    pub fn segment_count(&self) -> usize {
+        self.count
+    }
+
+    /// Full original name
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Whether the segment is the last one in the name
+    pub fn is_last(&self) -> bool {
+        self.index + 1 == self.count
+    }
+}
+
+/// Decides whether a custom tag allows a rule to apply in the given context
+pub(crate) trait TagFilter {
+    fn allows(&self, tag: &str, context: &MatchContext) -> bool;
+}
+
+/// Rejects every custom tag; used by the built-in free functions
+pub(crate) struct NoCustomTags;
+
+impl TagFilter for NoCustomTags {
+    fn allows(&self, _tag: &str, _context: &MatchContext) -> bool {
+        false
+    }
+}
+
+/// Single exception or suffix rule
+#[derive(Clone)]
+pub struct Rule {
+    pub(crate) gender: Gender,
+    pub(crate) data: RuleData,
+}
+
+#[derive(Clone)]
+pub(crate) enum RuleData {
+    /// Position in `RuleTables::rules`
+    #[cfg_attr(
+        not(any(
+            feature = "firstname-rules",
+            feature = "lastname-rules",
+            feature = "middlename-rules"
+        )),
+        allow(dead_code)
+    )]
+    Embedded(u32),
+    /// Rule embedded with `include_rules!`
+    Static(&'static RuleParts),
+    /// Rule created at runtime
+    Owned(Box<RuleParts>),
+}
+
+/// Tests, modifiers and tags of a rule that isn't in the embedded tables
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct RuleParts {
+    pub(crate) test: Cow<'static, [Cow<'static, str>]>,
+    /// Modifiers indexed by `Case`; the nominative slot keeps the name as is
+    pub(crate) mods: [Modifier; 6],
+    pub(crate) tags: Cow<'static, [RuleTag]>,
+}
+
+impl RuleParts {
+    #[doc(hidden)]
+    pub const fn new(
+        test: &'static [Cow<'static, str>],
+        mods: [Modifier; 6],
+        tags: &'static [RuleTag],
+    ) -> RuleParts {
+        RuleParts {
+            test: Cow::Borrowed(test),
+            mods,
+            tags: Cow::Borrowed(tags),
+        }
+    }
+}
+
+/// Tables the embedded rules refer to by position, so the generated data has no nested
+/// literals and few pointers
+pub(crate) struct RuleTables {
+    /// Tests and endings of every rule, concatenated
+    pub(crate) strings: &'static str,
+    /// Byte ranges of the tests in `strings`
+    pub(crate) tests: &'static [(u32, u32)],
+    /// Number of characters to remove and the byte range of the ending in `strings`;
+    /// the first modifier keeps the name as is
+    pub(crate) modifiers: &'static [Option<(u32, u32, u32)>],
+    pub(crate) tags: &'static [RuleTag],
+    pub(crate) rules: &'static [EmbeddedRule],
+}
+
+/// Embedded rule as positions in `RuleTables`
+pub(crate) struct EmbeddedRule {
+    /// Range of `RuleTables::tests`
+    pub(crate) tests: (u32, u32),
+    /// Positions in `RuleTables::modifiers` indexed by `Case`, followed by the vocative
+    /// of the Ukrainian rules
+    pub(crate) mods: [u32; 7],
+    /// Range of `RuleTables::tags`
+    pub(crate) tags: (u32, u32),
+}
+
+// Where the tests, modifiers and tags of a rule are kept
+enum Source<'a> {
+    Tables(&'static EmbeddedRule),
+    Parts(&'a RuleParts),
+}
+
+#[cfg(feature = "generate-rules")]
+static TABLES: RuleTables = include!(concat!(env!("OUT_DIR"), "/rule_tables.inc"));
+#[cfg(not(feature = "generate-rules"))]
+static TABLES: RuleTables = include!("generated/rule_tables.inc");
+
+/// Position of the Ukrainian vocative among the modifiers of a rule, after the six slots
+/// indexed by `Case`
+#[cfg(feature = "lang-uk")]
+pub(crate) const VOCATIVE_SLOT: usize = 6;
+
+fn table_string(start: u32, end: u32) -> &'static str {
+    &TABLES.strings[start as usize..end as usize]
+}
+
+/// Embedded test by its position in the tables
+pub(crate) fn embedded_test(test: u32) -> &'static str {
+    let (start, end) = TABLES.tests[test as usize];
+    table_string(start, end)
+}
+
+/// Parses a modifier written as in `rules.yml`: `.` keeps the name, every leading `-`
+/// removes one character and the rest is appended
+pub(crate) fn parse_modifier(modifier: &str) -> Modifier {
+    if modifier == "." {
+        None
+    } else {
+        let ending = modifier.trim_start_matches('-');
+        Some((modifier.len() - ending.len(), Cow::Owned(ending.to_owned())))
+    }
+}
+
+/// Parses the five oblique case modifiers of `rules.yml` into a slot per `Case`
+pub(crate) fn parse_mods(mods: [&str; 5]) -> [Modifier; 6] {
+    [
+        None,
+        parse_modifier(mods[0]),
+        parse_modifier(mods[1]),
+        parse_modifier(mods[2]),
+        parse_modifier(mods[3]),
+        parse_modifier(mods[4]),
+    ]
+}
+
+// Whether lowercasing keeps the character as it is
+fn is_lowercase_char(c: char) -> bool {
+    let mut lowercase = c.to_lowercase();
+    lowercase.next() == Some(c) && lowercase.next().is_none()
+}
+
+/// Lowercase form of the string, borrowed when it has no capitals
+#[cfg(feature = "gender-detection")]
+pub(crate) fn lowercase(s: &str) -> Cow<'_, str> {
+    if s.chars().all(is_lowercase_char) {
+        Cow::Borrowed(s)
+    } else {
+        Cow::Owned(s.to_lowercase())
+    }
+}
+
+/// Lowercase form of a name or a rule test used for matching, with every apostrophe
+/// written as `'`; borrowed when the name is already in that form
+pub(crate) fn match_key(name: &str) -> Cow<'_, str> {
+    if name
+        .chars()
+        .all(|c| is_lowercase_char(c) && (c == '\'' || !is_apostrophe(c)))
+    {
+        return Cow::Borrowed(name);
+    }
+    Cow::Owned(
+        name.chars()
+            .flat_map(char::to_lowercase)
+            .map(|c| if is_apostrophe(c) { '\'' } else { c })
+            .collect(),
+    )
+}
+
+impl Rule {
+    /// Creates a rule from test strings and modifiers for the five oblique cases in
+    /// `rules.yml` notation
+    pub fn new<I, S>(gender: Gender, test: I, mods: [&str; 5]) -> Rule
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Rule::from_parts(
+            gender,
+            RuleParts {
+                test: test
+                    .into_iter()
+                    .map(|test| Cow::Owned(match_key(&test.into()).into_owned()))
+                    .collect(),
+                mods: parse_mods(mods),
+                tags: Cow::Borrowed(&[]),
+            },
+        )
+    }
+
+    pub(crate) fn from_parts(gender: Gender, parts: RuleParts) -> Rule {
+        Rule {
+            gender,
+            data: RuleData::Owned(Box::new(parts)),
+        }
+    }
+
+    #[doc(hidden)]
+    pub const fn from_static(gender: Gender, parts: &'static RuleParts) -> Rule {
+        Rule {
+            gender,
+            data: RuleData::Static(parts),
+        }
+    }
+
+    /// Adds a tag to the rule
+    pub fn with_tag(mut self, tag: RuleTag) -> Rule {
+        self.parts_mut().tags.to_mut().push(tag);
+        self
+    }
+
+    fn source(&self) -> Source<'_> {
+        match &self.data {
+            RuleData::Embedded(rule) => Source::Tables(&TABLES.rules[*rule as usize]),
+            RuleData::Static(parts) => Source::Parts(parts),
+            RuleData::Owned(parts) => Source::Parts(parts),
+        }
+    }
+
+    // Parts that can be changed, copied from the tables or the static rule first
+    fn parts_mut(&mut self) -> &mut RuleParts {
+        if !matches!(self.data, RuleData::Owned(_)) {
+            let modifier = |case| {
+                self.modifier(case)
+                    .map(|(skip, ending)| (skip, Cow::Owned(ending.to_owned())))
+            };
+            let parts = RuleParts {
+                test: self
+                    .tests()
+                    .map(|test| Cow::Owned(test.to_owned()))
+                    .collect(),
+                mods: [
+                    modifier(Case::Nominative),
+                    modifier(Case::Genitive),
+                    modifier(Case::Dative),
+                    modifier(Case::Accusative),
+                    modifier(Case::Instrumental),
+                    modifier(Case::Prepositional),
+                ],
+                tags: Cow::Owned(self.tags().to_vec()),
+            };
+            self.data = RuleData::Owned(Box::new(parts));
+        }
+        match &mut self.data {
+            RuleData::Owned(parts) => parts,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Gender the rule applies to; androgynous rules apply to every gender
+    pub fn gender(&self) -> Gender {
+        self.gender
+    }
+
+    /// Lowercase strings a name is compared with: the whole name for exceptions, its
+    /// ending for suffix rules
+    pub fn tests(&self) -> impl Iterator<Item = &str> {
+        let (embedded, listed): (&[(u32, u32)], &[Cow<'static, str>]) = match self.source() {
+            Source::Tables(rule) => {
+                let (start, end) = rule.tests;
+                (&TABLES.tests[start as usize..end as usize], &[])
+            }
+            Source::Parts(parts) => (&[], &parts.test),
+        };
+        embedded
+            .iter()
+            .map(|&(start, end)| table_string(start, end))
+            .chain(listed.iter().map(|test| test.as_ref()))
+    }
+
+    /// Number of trailing characters to remove and the ending to append for the case,
+    /// or `None` when the name is kept as is
+    pub fn modifier(&self, case: Case) -> Option<(usize, &str)> {
+        self.modifier_at(usize::from(case))
+    }
+
+    // Modifier in the slot, which is the index of a `Case` or `VOCATIVE_SLOT`; rules with
+    // fewer slots keep the name as is in the others
+    pub(crate) fn modifier_at(&self, slot: usize) -> Option<(usize, &str)> {
+        match self.source() {
+            Source::Tables(rule) => TABLES.modifiers[*rule.mods.get(slot)? as usize]
+                .map(|(skip, start, end)| (skip as usize, table_string(start, end))),
+            Source::Parts(parts) => parts
+                .mods
+                .get(slot)?
+                .as_ref()
+                .map(|(skip, postfix)| (*skip, postfix.as_ref())),
+        }
+    }
+
+    /// Tags restricting where the rule applies
+    pub fn tags(&self) -> &[RuleTag] {
+        match self.source() {
+            Source::Tables(rule) => {
+                let (start, end) = rule.tags;
+                &TABLES.tags[start as usize..end as usize]
+            }
+            Source::Parts(parts) => &parts.tags,
+        }
+    }
+
+    pub(crate) fn has_tag(&self, tag: &RuleTag) -> bool {
+        self.tags().contains(tag)
+    }
+
+    pub(crate) fn fully_matches(&self, name: &str, fold_yo: bool) -> bool {
+        self.matched_exception(name, fold_yo).is_some()
+    }
+
+    // Test string equal to the name
+    pub(crate) fn matched_exception(&self, name: &str, fold_yo: bool) -> Option<&str> {
+        self.tests().find(|test| {
+            if fold_yo {
+                test.chars()
+                    .map(fold_yo_char)
+                    .eq(name.chars().map(fold_yo_char))
+            } else {
+                *test == name
+            }
+        })
+    }
+
+    // Longest test string the name ends with
+    pub(crate) fn matched_suffix(&self, name: &str, fold_yo: bool) -> Option<&str> {
+        self.tests()
+            .filter(|test| ends_with(name, test, fold_yo))
+            .max_by_key(|test| test.chars().count())
+    }
+
+    // Length in characters of the longest test string the name ends with
+    pub(crate) fn matched_suffix_len(&self, name: &str, fold_yo: bool) -> Option<usize> {
+        self.matched_suffix(name, fold_yo)
+            .map(|test| test.chars().count())
+    }
+
+    // Whether a test of `len` characters matches the end of the name exactly, without
+    // folding ё
+    fn has_exact_suffix(&self, name: &str, len: usize) -> bool {
+        self.tests()
+            .any(|test| test.chars().count() == len && ends_with(name, test, false))
+    }
+
+    fn suffix_matches(&self, name: &str, fold_yo: bool) -> bool {
+        self.tests().any(|test| ends_with(name, test, fold_yo))
+    }
+
+    fn gender_matches(&self, gender: Gender) -> bool {
+        self.gender == gender || self.gender == Gender::Androgynous
+    }
+
+    fn custom_tags_allow(&self, context: &MatchContext, filter: &impl TagFilter) -> bool {
+        self.tags().iter().all(|tag| match tag {
+            FirstWord => !context.is_last(),
+            FleetingVowel => vowel_count(context.segment) > 1,
+            Particle => context.index > 0 && context.is_last(),
+            StressedEnding => vowel_count(context.segment) > 2,
+            Custom(name) => filter.allows(name, context),
+        })
+    }
+}
+
+// Rules from the tables, statics and owned parts are equal when they read the same
+impl PartialEq for Rule {
+    fn eq(&self, other: &Rule) -> bool {
+        self.gender == other.gender
+            && self.tests().eq(other.tests())
+            && Case::iter().all(|case| self.modifier(case) == other.modifier(case))
+            && self.tags() == other.tags()
+    }
+}
+
+impl fmt::Debug for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mods: Vec<_> = Case::iter().map(|case| self.modifier(case)).collect();
+        f.debug_struct("Rule")
+            .field("gender", &self.gender)
+            .field("test", &self.tests().collect::<Vec<_>>())
+            .field("mods", &mods)
+            .field("tags", &self.tags())
+            .finish()
+    }
+}
+
+fn vowel_count(segment: &str) -> usize {
+    segment
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| "аеёиоуыэюя".contains(*c))
+        .count()
+}
+
+pub(crate) fn fold_yo_char(c: char) -> char {
+    if c == 'ё' {
+        'е'
+    } else {
+        c
+    }
+}
+
+pub(crate) fn ends_with(name: &str, suffix: &str, fold_yo: bool) -> bool {
+    if fold_yo {
+        let mut name = name.chars().rev().map(fold_yo_char);
+        suffix
+            .chars()
+            .rev()
+            .all(|c| name.next() == Some(fold_yo_char(c)))
+    } else {
+        name.ends_with(suffix)
+    }
+}
+
+/// Exceptions and suffixes for a single name part
+#[derive(Clone, Debug)]
+pub struct RuleList {
+    pub(crate) exceptions: Cow<'static, [Rule]>,
+    pub(crate) suffixes: Cow<'static, [Rule]>,
+    /// Lookup tables generated for the embedded rules; other lists are scanned
+    pub(crate) index: Option<&'static RuleIndex>,
+    /// Number of exceptions put in front of the indexed ones, checked before them
+    pub(crate) unindexed_exceptions: usize,
+}
+
+// The index only speeds lookups up, so lists with and without one are equal
+impl PartialEq for RuleList {
+    fn eq(&self, other: &RuleList) -> bool {
+        self.exceptions == other.exceptions && self.suffixes == other.suffixes
+    }
+}
+
+impl RuleList {
+    /// Creates a rule list from exception and suffix rules
+    pub fn new(exceptions: Vec<Rule>, suffixes: Vec<Rule>) -> RuleList {
+        RuleList {
+            exceptions: Cow::Owned(exceptions),
+            suffixes: Cow::Owned(suffixes),
+            index: None,
+            unindexed_exceptions: 0,
+        }
+    }
+
+    #[doc(hidden)]
+    pub const fn from_static(exceptions: &'static [Rule], suffixes: &'static [Rule]) -> RuleList {
+        RuleList {
+            exceptions: Cow::Borrowed(exceptions),
+            suffixes: Cow::Borrowed(suffixes),
+            index: None,
+            unindexed_exceptions: 0,
+        }
+    }
+
+    /// Rules matching a whole name, checked in order before the suffixes
+    pub fn exceptions(&self) -> &[Rule] {
+        &self.exceptions
+    }
+
+    /// Rules matching the ending of a name
+    pub fn suffixes(&self) -> &[Rule] {
+        &self.suffixes
+    }
+
+    fn merge(&mut self, overrides: RuleList) {
+        self.unindexed_exceptions += overrides.exceptions.len();
+        let mut exceptions = overrides.exceptions.into_owned();
+        exceptions.extend_from_slice(&self.exceptions);
+        self.exceptions = Cow::Owned(exceptions);
+        // Suffixes put in front shift the indexed ones
+        if !overrides.suffixes.is_empty() {
+            let mut suffixes = overrides.suffixes.into_owned();
+            suffixes.extend_from_slice(&self.suffixes);
+            self.suffixes = Cow::Owned(suffixes);
+            self.index = None;
+        }
+    }
+
+    // Find exception by name and gender; `fold_yo` makes ё match е
+    pub(crate) fn find_exception(
+        &self,
+        name: &str,
+        gender: Gender,
+        context: &MatchContext,
+        filter: &impl TagFilter,
+        fold_yo: bool,
+    ) -> Option<&Rule> {
+        // Search exceptions with matching name and gender
+        let matches = |exception: &&Rule| {
+            exception.fully_matches(name, fold_yo)
+                && exception.gender_matches(gender)
+                && exception.custom_tags_allow(context, filter)
+        };
+        let found = match self.index {
+            Some(index) => {
+                let (front, indexed) = self.exceptions.split_at(self.unindexed_exceptions);
+                front
+                    .iter()
+                    .find(matches)
+                    .or_else(|| index.exceptions(name).map(|i| &indexed[i]).find(matches))
+            }
+            None => self.exceptions.iter().find(matches),
+        };
+        #[cfg(test)]
+        debug_assert!(
+            found.map(|rule| rule as *const Rule)
+                == self
+                    .exceptions
+                    .iter()
+                    .find(matches)
+                    .map(|rule| rule as *const Rule),
+            "exception index disagrees with the scan for {:?}",
+            name
+        );
+        found
+    }
+
+    // Find suffix by name and gender. The rule with the longest matching test in characters
+    // wins; among equally long matches a rule for the exact gender wins over an androgynous
+    // one, and then the rule listed first
+    pub(crate) fn find_suffix(
+        &self,
+        name: &str,
+        gender: Gender,
+        context: &MatchContext,
+        filter: &impl TagFilter,
+        fold_yo: bool,
+    ) -> Option<&Rule> {
+        let found = match self.index {
+            Some(index) => index.find_suffix(name, |len, candidates| {
+                let mut candidates = candidates
+                    .iter()
+                    .map(|&i| &self.suffixes[i as usize])
+                    .filter(|&suffix| {
+                        (fold_yo || suffix.has_exact_suffix(name, len))
+                            && suffix.gender_matches(gender)
+                            && suffix.custom_tags_allow(context, filter)
+                    });
+                let first = candidates.next()?;
+                if first.gender == gender {
+                    Some(first)
+                } else {
+                    Some(
+                        candidates
+                            .find(|&suffix| suffix.gender == gender)
+                            .unwrap_or(first),
+                    )
+                }
+            }),
+            None => self.scan_suffixes(name, gender, context, filter, fold_yo),
+        };
+        #[cfg(test)]
+        debug_assert!(
+            found.map(|rule| rule as *const Rule)
+                == self
+                    .scan_suffixes(name, gender, context, filter, fold_yo)
+                    .map(|rule| rule as *const Rule),
+            "suffix index disagrees with the scan for {:?}",
+            name
+        );
+        found
+    }
+
+    // `find_suffix` checking every test of every rule
+    fn scan_suffixes(
+        &self,
+        name: &str,
+        gender: Gender,
+        context: &MatchContext,
+        filter: &impl TagFilter,
+        fold_yo: bool,
+    ) -> Option<&Rule> {
+        self.suffixes
+            .iter()
+            // `max_by_key` returns the last of equal maximums, so the list is reversed
+            .rev()
+            .filter(|&suffix| {
+                suffix.suffix_matches(name, fold_yo)
+                    && suffix.gender_matches(gender)
+                    && suffix.custom_tags_allow(context, filter)
+            })
+            .max_by_key(|&rule| {
+                (
+                    rule.matched_suffix_len(name, fold_yo).unwrap(),
+                    rule.gender == gender,
+                )
+            })
+    }
+}
+
+/// Rule lists for every name part
+#[derive(PartialEq, Clone, Debug)]
+pub struct Rules {
+    pub(crate) lastname: RuleList,
+    pub(crate) firstname: RuleList,
+    pub(crate) middlename: RuleList,
+}
+
+#[cfg(feature = "generate-rules")]
+pub(crate) static RULES: Rules = include!(concat!(env!("OUT_DIR"), "/rules.inc"));
+#[cfg(not(feature = "generate-rules"))]
+pub(crate) static RULES: Rules = include!("generated/rules.inc");
+
+/// Ukrainian rules from `rules_uk.yml`, with the vocative in `VOCATIVE_SLOT`
+#[cfg(all(feature = "lang-uk", feature = "generate-rules"))]
+pub(crate) static UK_RULES: Rules = include!(concat!(env!("OUT_DIR"), "/rules_uk.inc"));
+#[cfg(all(feature = "lang-uk", not(feature = "generate-rules")))]
+pub(crate) static UK_RULES: Rules = include!("generated/rules_uk.inc");
+
+impl Rules {
+    /// Creates a rule set from lists for last, first and middle names
+    pub const fn new(lastname: RuleList, firstname: RuleList, middlename: RuleList) -> Rules {
+        Rules {
+            lastname,
+            firstname,
+            middlename,
+        }
+    }
+
+    /// Rules for the given name part
+    pub fn list(&self, part: NamePart) -> &RuleList {
+        match part {
+            NamePart::First => &self.firstname,
+            NamePart::Last => &self.lastname,
+            NamePart::Middle => &self.middlename,
+        }
+    }
+
+    /// Rules embedded into the crate
+    pub fn builtin() -> Rules {
+        RULES.clone()
+    }
+
+    /// Rules embedded into the crate, without copying them
+    pub fn embedded() -> &'static Rules {
+        &RULES
+    }
+
+    /// Inflects a name using the rules for the given part
+    pub fn inflect(&self, part: NamePart, gender: Gender, name: &str, case: Case) -> String {
+        inflect_builtin(gender, name, case, self.list(part))
+    }
+
+    /// Inflects first name
+    pub fn firstname(&self, gender: Gender, name: &str, case: Case) -> String {
+        self.inflect(NamePart::First, gender, name, case)
+    }
+
+    /// Inflects last name
+    pub fn lastname(&self, gender: Gender, name: &str, case: Case) -> String {
+        self.inflect(NamePart::Last, gender, name, case)
+    }
+
+    /// Inflects middle name
+    pub fn middlename(&self, gender: Gender, name: &str, case: Case) -> String {
+        self.inflect(NamePart::Middle, gender, name, case)
+    }
+
+    /// Adds rules in front of these ones
+    ///
+    /// Exceptions from `overrides` are checked first; suffix rules still compete by the
+    /// length of the matched suffix.
+    pub fn merge(mut self, overrides: Rules) -> Rules {
+        self.lastname.merge(overrides.lastname);
+        self.firstname.merge(overrides.firstname);
+        self.middlename.merge(overrides.middlename);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Inflector;
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_merge_overrides() {
+        let overrides = Rules::new(
+            RuleList::new(
+                vec![Rule::new(
+                    Gender::Male,
+                    vec!["сирота"],
+                    [".", ".", ".", ".", "."],
+                )],
+                vec![],
+            ),
+            RuleList::new(vec![], vec![]),
+            RuleList::new(vec![], vec![]),
+        );
+        let inflector = Inflector::new(Rules::builtin().merge(overrides));
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Сирота", Case::Dative),
+            "Сирота"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Female, "Сирота", Case::Dative),
+            "Сироте"
+        );
+    }
+
+    #[test]
+    fn should_apply_first_word_suffixes_to_leading_words() {
+        let inflector = Inflector::new(Rules::new(
+            RuleList::new(
+                vec![],
+                vec![
+                    Rule::new(Gender::Male, vec!["ч"], ["а", "у", "а", "ем", "е"])
+                        .with_tag(RuleTag::FirstWord),
+                    Rule::new(Gender::Male, vec!["вич"], ["а", "у", "а", "ем", "е"]),
+                ],
+            ),
+            RuleList::new(vec![], vec![]),
+            RuleList::new(vec![], vec![]),
+        ));
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Бонч-Бруевич", Case::Dative),
+            "Бончу-Бруевичу"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Бонч", Case::Dative),
+            "Бонч"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Иванов-Бонч", Case::Dative),
+            "Иванов-Бонч"
+        );
+    }
+
+    #[test]
+    fn should_break_suffix_ties() {
+        let inflector = Inflector::new(Rules::new(
+            RuleList::new(
+                vec![],
+                vec![
+                    Rule::new(Gender::Androgynous, vec!["ов"], ["1", "1", "1", "1", "1"]),
+                    Rule::new(Gender::Male, vec!["в"], ["2", "2", "2", "2", "2"]),
+                    Rule::new(Gender::Male, vec!["ов"], ["3", "3", "3", "3", "3"]),
+                    Rule::new(Gender::Male, vec!["ов"], ["4", "4", "4", "4", "4"]),
+                    Rule::new(Gender::Androgynous, vec!["ёв"], ["5", "5", "5", "5", "5"]),
+                ],
+            ),
+            RuleList::new(vec![], vec![]),
+            RuleList::new(vec![], vec![]),
+        ));
+        // The longest match wins even when listed first
+        assert_eq!(
+            inflector.lastname(Gender::Female, "Иванов", Case::Dative),
+            "Иванов1"
+        );
+        // Of equally long matches the exact gender wins, then the rule listed first
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Иванов", Case::Dative),
+            "Иванов3"
+        );
+        // "ёв" matches "ев" with the same length as "ов" would
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Лебедев", Case::Dative),
+            "Лебедев5"
+        );
+    }
+
+    #[test]
+    fn should_find_same_rules_with_index() {
+        for &part in &[NamePart::Last, NamePart::First, NamePart::Middle] {
+            let list = RULES.list(part);
+            assert!(list.index.is_some());
+            // Every test, with prefixes and with ё and е swapped
+            let mut names = vec!["".to_owned(), "ё".to_owned(), "лёв".to_owned()];
+            for rule in list.exceptions().iter().chain(list.suffixes()) {
+                for test in rule.tests() {
+                    for prefix in &["", "а", "ко", "ёл", "бр"] {
+                        names.push(format!("{}{}", prefix, test));
+                        names.push(format!("{}{}", prefix, test.replace('е', "ё")));
+                        names.push(format!("{}{}", prefix, test.replace('ё', "е")));
+                    }
+                }
+            }
+            for name in &names {
+                let context = MatchContext {
+                    segment: name,
+                    index: 0,
+                    count: 1,
+                    name,
+                };
+                for gender in [Gender::Male, Gender::Female, Gender::Androgynous] {
+                    for fold_yo in [true, false] {
+                        let indexed =
+                            list.find_suffix(name, gender, &context, &NoCustomTags, fold_yo);
+                        let scanned =
+                            list.scan_suffixes(name, gender, &context, &NoCustomTags, fold_yo);
+                        assert_eq!(
+                            indexed.map(|rule| rule as *const Rule),
+                            scanned.map(|rule| rule as *const Rule),
+                            "{}",
+                            name
+                        );
+                        // `find_exception` compares itself with a scan in tests
+                        list.find_exception(name, gender, &context, &NoCustomTags, fold_yo);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn should_lowercase_only_when_needed() {
+        assert!(matches!(match_key("семён"), Cow::Borrowed("семён")));
+        assert!(matches!(match_key("д'артаньян"), Cow::Borrowed(_)));
+        assert_eq!(match_key("СЕМЁН"), "семён");
+        assert_eq!(match_key("Ёлкин"), "ёлкин");
+        assert_eq!(match_key("д’артаньян"), "д'артаньян");
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_lowercase_detection_keys_only_when_needed() {
+        assert!(matches!(lowercase("ёлкин"), Cow::Borrowed(_)));
+        assert_eq!(lowercase("ЁЛКИН"), "ёлкин");
+        // Lowercase "i" with a combining dot above
+        assert_eq!(lowercase("İ"), "i\u{307}");
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_expose_embedded_rules() {
+        let list = Rules::embedded().list(NamePart::Last);
+        let first = &list.exceptions()[0];
+        assert_eq!(first.gender(), Gender::Androgynous);
+        assert!(first.tests().any(|test| test == "бонч"));
+        assert_eq!(first.tags(), &[RuleTag::FirstWord]);
+        assert_eq!(first.modifier(Case::Dative), None);
+        let suffix = list
+            .suffixes()
+            .iter()
+            .find(|rule| rule.tests().any(|test| test == "ова"))
+            .unwrap();
+        assert_eq!(suffix.modifier(Case::Nominative), None);
+        assert_eq!(suffix.gender(), Gender::Female);
+        assert_eq!(suffix.modifier(Case::Dative), Some((1, "ой")));
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_copy_embedded_rules_when_changed() {
+        let embedded = &Rules::embedded().list(NamePart::Last).exceptions()[0];
+        assert!(matches!(embedded.data, RuleData::Embedded(_)));
+        let tagged = embedded
+            .clone()
+            .with_tag(RuleTag::Custom(Cow::Borrowed("знать")));
+        assert!(matches!(tagged.data, RuleData::Owned(_)));
+        assert_eq!(
+            tagged.tags(),
+            &[RuleTag::FirstWord, RuleTag::Custom(Cow::Borrowed("знать"))]
+        );
+        assert!(tagged.tests().eq(embedded.tests()));
+        assert!(Case::iter().all(|case| tagged.modifier(case) == embedded.modifier(case)));
+        assert_ne!(&tagged, embedded);
+    }
+
+    #[cfg(all(
+        feature = "firstname-rules",
+        feature = "lastname-rules",
+        feature = "middlename-rules"
+    ))]
+    #[test]
+    fn should_inflect_with_rules_instance() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+        let rules = std::sync::Arc::new(Rules::builtin());
+        assert_send_sync(&rules);
+        assert_eq!(
+            rules.lastname(Gender::Male, "Бонч-Бруевич", Case::Dative),
+            "Бонч-Бруевичу"
+        );
+        assert_eq!(rules.firstname(Gender::Male, "Саша", Case::Dative), "Саше");
+        assert_eq!(
+            rules.middlename(Gender::Female, "Прокопьевна", Case::Accusative),
+            "Прокопьевну"
+        );
+    }
+
+    #[cfg(all(feature = "macros", feature = "lastname-rules"))]
+    #[test]
+    fn should_include_rules() {
+        static OVERRIDES: Rules = crate::include_rules!("tests/fixtures/overrides.yml");
+        let inflector = Inflector::new(Rules::builtin().merge(OVERRIDES.clone()));
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Сирота", Case::Dative),
+            "Сирота"
+        );
+        assert_eq!(
+            inflector.lastname(Gender::Male, "Кравец", Case::Dative),
+            "Кравецу"
+        );
+        assert_eq!(
+            crate::lastname(Gender::Male, "Кравец", Case::Dative),
+            "Кравцу"
+        );
+    }
+}
@@ -0,0 +1,208 @@
+//! Stems shared by every declined form of a name, for search indexes.
+
+use super::rules::{fold_yo_char, NoCustomTags, RULES};
+use super::{match_name, nfc, Case, Gender, InflectOptions, NamePart, SegmentMatch};
+
+// Most characters a declined form may have after the stem, as in "Л" + "ьвом"
+const MAX_ENDING: usize = 4;
+
+fn fold(c: char) -> char {
+    fold_yo_char(c.to_lowercase().next().unwrap_or(c))
+}
+
+// Byte length of the start of the segment every case keeps, found from the modifiers of
+// the matched rule: the part no modifier cuts, then the characters the cut parts and the
+// appended endings of all cases agree on
+fn stem_len(segment: &SegmentMatch) -> usize {
+    let forms: Vec<(usize, &str)> = Case::iter()
+        .map(
+            |case| match segment.rule.and_then(|(_, rule)| rule.modifier(case)) {
+                Some((skip, postfix)) => (segment.kept_len(skip), postfix),
+                None => (segment.segment.len(), ""),
+            },
+        )
+        .collect();
+    let cut = forms
+        .iter()
+        .map(|&(kept, _)| kept)
+        .min()
+        .unwrap_or(segment.segment.len());
+    let mut continuations: Vec<_> = forms
+        .iter()
+        .map(|&(kept, postfix)| segment.segment[cut..kept].chars().chain(postfix.chars()))
+        .collect();
+    let mut len = cut;
+    for c in segment.segment[cut..].chars() {
+        let agree = continuations
+            .iter_mut()
+            .all(|continuation| continuation.next().map(fold) == Some(fold(c)));
+        if !agree {
+            break;
+        }
+        len += c.len_utf8();
+    }
+    len
+}
+
+/// Start of the name every declined form shares, as a token for search indexes
+///
+/// The stem comes from the endings the matched rule writes, so "Иванов", "Иванову" and
+/// "Ивановым" all give "Иванов", and "Анна" gives "Анн". Names the rules don't decline
+/// are returned as is. Hyphenated and multi-word names get a stem for every part, joined
+/// with the separators of the name. Use `stem_matches` to check a word against the stem.
+///
+/// ```
+/// # #[cfg(all(feature = "firstname-rules", feature = "lastname-rules"))]
+/// # {
+/// use petrovich::{stem, Gender, NamePart};
+///
+/// assert_eq!(stem(NamePart::Last, Gender::Male, "Иванов"), "Иванов");
+/// assert_eq!(stem(NamePart::First, Gender::Female, "Анна"), "Анн");
+/// assert_eq!(stem(NamePart::First, Gender::Male, "Павел"), "Пав");
+/// assert_eq!(stem(NamePart::Last, Gender::Male, "Петров-Водкин"), "Петров-Водкин");
+/// assert_eq!(stem(NamePart::Last, Gender::Male, "Черных"), "Черных");
+/// # }
+/// ```
+pub fn stem(part: NamePart, gender: Gender, name: &str) -> String {
+    let name = &*nfc(name);
+    let segments = match match_name(
+        gender,
+        name,
+        RULES.list(part),
+        &NoCustomTags,
+        &InflectOptions::DEFAULT,
+    ) {
+        Ok(segments) => segments,
+        Err(_) => return name.to_owned(),
+    };
+    let mut output = String::with_capacity(name.len());
+    for segment in segments {
+        output.push_str(segment.separator);
+        output.push_str(segment.head);
+        let len = stem_len(&segment);
+        output.push_str(&segment.segment[..len]);
+        if len == segment.segment.len() {
+            output.push_str(segment.tail);
+        }
+    }
+    output
+}
+
+/// Whether the candidate may be a declined form of a name with the given stem
+///
+/// Letter case and the difference between ё and е are ignored. Every part of the candidate
+/// has to start with the matching part of the stem and go on with an ending of at most
+/// four letters, so "Ивановым" matches "Иванов" while "Ивановскому" doesn't.
+///
+/// ```
+/// # #[cfg(feature = "firstname-rules")]
+/// # {
+/// use petrovich::{stem, stem_matches, Gender, NamePart};
+///
+/// let stem = stem(NamePart::First, Gender::Female, "Алёна");
+/// assert!(stem_matches(&stem, "АЛЕНОЙ"));
+/// assert!(stem_matches(&stem, "Алёну"));
+/// assert!(!stem_matches(&stem, "Алёнушкой"));
+/// # }
+/// ```
+pub fn stem_matches(stem: &str, candidate: &str) -> bool {
+    let is_separator = |c: char| c.is_whitespace() || c == '-';
+    let stem = nfc(stem);
+    let candidate = nfc(candidate);
+    let mut stems = stem.split(is_separator).filter(|part| !part.is_empty());
+    let mut candidates = candidate
+        .split(is_separator)
+        .filter(|part| !part.is_empty());
+    loop {
+        match (stems.next(), candidates.next()) {
+            (None, None) => return true,
+            (Some(stem), Some(candidate)) => {
+                let mut rest = candidate.chars();
+                if !stem.chars().all(|c| rest.next().map(fold) == Some(fold(c))) {
+                    return false;
+                }
+                let rest = rest.as_str();
+                if rest.chars().count() > MAX_ENDING || !rest.chars().all(char::is_alphabetic) {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inflect;
+
+    fn parts(name: &str) -> Vec<&str> {
+        name.split([' ', '-']).collect()
+    }
+
+    // Checks the stem and that every part of every form starts with the part of the stem
+    fn check_paradigm(part: NamePart, gender: Gender, name: &str, expected: &str) {
+        let stem = stem(part, gender, name);
+        assert_eq!(stem, expected, "{}", name);
+        for case in Case::iter() {
+            let form = inflect(part, gender, name, case);
+            assert!(
+                parts(&form)
+                    .iter()
+                    .zip(parts(&stem))
+                    .all(|(form, stem)| form.starts_with(stem)),
+                "{} {}",
+                form,
+                stem
+            );
+            assert!(stem_matches(&stem, &form), "{} {}", form, stem);
+        }
+    }
+
+    #[cfg(all(
+        feature = "firstname-rules",
+        feature = "lastname-rules",
+        feature = "middlename-rules"
+    ))]
+    #[test]
+    fn should_find_common_prefix() {
+        check_paradigm(NamePart::Last, Gender::Male, "Иванов", "Иванов");
+        check_paradigm(NamePart::Last, Gender::Female, "Иванова", "Иванов");
+        check_paradigm(NamePart::Last, Gender::Male, "Толстой", "Толст");
+        check_paradigm(NamePart::First, Gender::Female, "Мария", "Мари");
+        check_paradigm(NamePart::First, Gender::Male, "Лев", "Л");
+        check_paradigm(NamePart::First, Gender::Male, "Павел", "Пав");
+        check_paradigm(NamePart::Middle, Gender::Male, "Сергеевич", "Сергеевич");
+        check_paradigm(NamePart::Last, Gender::Male, "ИВАНОВ", "ИВАНОВ");
+    }
+
+    #[test]
+    fn should_keep_indeclinable_names() {
+        check_paradigm(NamePart::Last, Gender::Male, "Черных", "Черных");
+        check_paradigm(NamePart::Last, Gender::Female, "Станкевич", "Станкевич");
+        check_paradigm(NamePart::Last, Gender::Male, "Smith", "Smith");
+    }
+
+    #[cfg(all(feature = "firstname-rules", feature = "lastname-rules"))]
+    #[test]
+    fn should_stem_every_part() {
+        check_paradigm(
+            NamePart::Last,
+            Gender::Female,
+            "Римская-Корсакова",
+            "Римск-Корсаков",
+        );
+        check_paradigm(NamePart::First, Gender::Female, "Анна Мария", "Анн Мари");
+    }
+
+    #[test]
+    fn should_match_forms_only() {
+        assert!(stem_matches("Анн Мари", "анне марии"));
+        assert!(stem_matches("Иванов", "Иванов"));
+        assert!(!stem_matches("Иванов", "Иванов-Петров"));
+        assert!(!stem_matches("Иванов", "Ивановскому"));
+        assert!(!stem_matches("Иванов", "Иванов2"));
+        assert!(!stem_matches("Иванов", "Петров"));
+        assert!(!stem_matches("Иванов", "Иван"));
+    }
+}
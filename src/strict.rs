@@ -0,0 +1,192 @@
+//! Inflection that tells matched names from names passed through unchanged.
+
+use super::options::is_cyrillic;
+use super::rules::{NoCustomTags, RULES};
+use super::{match_name, nfc, Case, Error, Gender, InflectOptions, NamePart, ENDING_RESERVE};
+
+/// Inflected name together with how much of it the rules matched
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum Matched {
+    /// Every segment of the name matched a rule
+    Full(String),
+    /// Some segments matched a rule, the others were passed through
+    Partial(String),
+}
+
+impl Matched {
+    /// Inflected name
+    pub fn as_str(&self) -> &str {
+        match self {
+            Matched::Full(name) | Matched::Partial(name) => name,
+        }
+    }
+
+    /// Whether some segments were passed through
+    pub fn is_partial(&self) -> bool {
+        matches!(self, Matched::Partial(_))
+    }
+
+    /// Converts into the inflected name
+    pub fn into_string(self) -> String {
+        match self {
+            Matched::Full(name) | Matched::Partial(name) => name,
+        }
+    }
+}
+
+fn try_inflect(part: NamePart, gender: Gender, name: &str, case: Case) -> Option<Matched> {
+    let name = &*nfc(name);
+    let segments = match_name(
+        gender,
+        name,
+        RULES.list(part),
+        &NoCustomTags,
+        &InflectOptions::DEFAULT,
+    )
+    .ok()?;
+    let matched = segments
+        .iter()
+        .filter(|segment| segment.rule.is_some())
+        .count();
+    if matched == 0 {
+        return None;
+    }
+    let mut output = String::with_capacity(name.len() + ENDING_RESERVE);
+    for segment in &segments {
+        segment.inflect_into(case, &mut output);
+    }
+    if matched < segments.len() {
+        Some(Matched::Partial(output))
+    } else {
+        Some(Matched::Full(output))
+    }
+}
+
+/// Inflects first name, returning `None` when no rule matched any of its segments
+#[cfg(feature = "firstname-rules")]
+pub fn try_firstname(gender: Gender, name: &str, case: Case) -> Option<Matched> {
+    try_inflect(NamePart::First, gender, name, case)
+}
+
+/// Inflects last name, returning `None` when no rule matched any of its segments
+#[cfg(feature = "lastname-rules")]
+pub fn try_lastname(gender: Gender, name: &str, case: Case) -> Option<Matched> {
+    try_inflect(NamePart::Last, gender, name, case)
+}
+
+/// Inflects middle name, returning `None` when no rule matched any of its segments
+#[cfg(feature = "middlename-rules")]
+pub fn try_middlename(gender: Gender, name: &str, case: Case) -> Option<Matched> {
+    try_inflect(NamePart::Middle, gender, name, case)
+}
+
+fn validate(name: &str) -> Result<(), Error> {
+    if name.trim().is_empty() {
+        return Err(Error::EmptyName);
+    }
+    if let Some((position, character)) = name
+        .char_indices()
+        .find(|&(_, c)| c.is_numeric() || c.is_control())
+    {
+        return Err(Error::InvalidCharacter {
+            character,
+            position,
+        });
+    }
+    if !name.chars().any(is_cyrillic) {
+        return Err(Error::NonCyrillicInput(name.to_owned()));
+    }
+    Ok(())
+}
+
+pub(crate) fn inflect_checked(
+    part: NamePart,
+    gender: Gender,
+    name: &str,
+    case: Case,
+) -> Result<String, Error> {
+    validate(name)?;
+    try_inflect(part, gender, name, case)
+        .map(Matched::into_string)
+        .ok_or_else(|| Error::NoRuleMatched(name.to_owned()))
+}
+
+/// Inflects first name, rejecting empty names, digits, control characters, names without
+/// Cyrillic letters and names no rule matched
+#[cfg(feature = "firstname-rules")]
+pub fn firstname_checked(gender: Gender, name: &str, case: Case) -> Result<String, Error> {
+    inflect_checked(NamePart::First, gender, name, case)
+}
+
+/// Inflects last name, rejecting invalid input like `firstname_checked`
+#[cfg(feature = "lastname-rules")]
+pub fn lastname_checked(gender: Gender, name: &str, case: Case) -> Result<String, Error> {
+    inflect_checked(NamePart::Last, gender, name, case)
+}
+
+/// Inflects middle name, rejecting invalid input like `firstname_checked`
+#[cfg(feature = "middlename-rules")]
+pub fn middlename_checked(gender: Gender, name: &str, case: Case) -> Result<String, Error> {
+    inflect_checked(NamePart::Middle, gender, name, case)
+}
+
+#[cfg(all(test, feature = "lastname-rules"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_tell_unmatched_names() {
+        assert_eq!(try_lastname(Gender::Female, "Qwerty", Case::Dative), None);
+        #[cfg(feature = "firstname-rules")]
+        assert_eq!(try_firstname(Gender::Male, "Blabla", Case::Dative), None);
+        assert_eq!(
+            try_lastname(Gender::Female, "Иванова", Case::Dative),
+            Some(Matched::Full("Ивановой".to_owned()))
+        );
+        // Rule applied, but the form doesn't change
+        assert_eq!(
+            try_lastname(Gender::Female, "Станкевич", Case::Dative),
+            Some(Matched::Full("Станкевич".to_owned()))
+        );
+    }
+
+    #[cfg(feature = "middlename-rules")]
+    #[test]
+    fn should_report_partial_matches() {
+        let matched = try_lastname(Gender::Male, "Иванов-Qwerty", Case::Dative).unwrap();
+        assert!(matched.is_partial());
+        assert_eq!(matched.as_str(), "Иванову-Qwerty");
+        assert_eq!(
+            try_middlename(Gender::Male, "Сергеевич", Case::Genitive).map(Matched::into_string),
+            Some("Сергеевича".to_owned())
+        );
+    }
+
+    #[cfg(all(feature = "firstname-rules", feature = "middlename-rules"))]
+    #[test]
+    fn should_validate_checked_input() {
+        assert_eq!(
+            lastname_checked(Gender::Male, "Иванов", Case::Dative),
+            Ok("Иванову".to_owned())
+        );
+        assert_eq!(
+            lastname_checked(Gender::Male, " ", Case::Dative),
+            Err(Error::EmptyName)
+        );
+        assert_eq!(
+            firstname_checked(Gender::Male, "Иван2", Case::Dative),
+            Err(Error::InvalidCharacter {
+                character: '2',
+                position: 8
+            })
+        );
+        assert_eq!(
+            firstname_checked(Gender::Male, "Blabla", Case::Dative),
+            Err(Error::NonCyrillicInput("Blabla".to_owned()))
+        );
+        assert_eq!(
+            middlename_checked(Gender::Male, "Оно", Case::Dative),
+            Err(Error::NoRuleMatched("Оно".to_owned()))
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! Conversion of surnames between their male and female forms.
+
+use super::options::is_cyrillic;
+use super::{split_segments, Casing, Gender};
+
+/// Male and female endings of gendered surnames
+///
+/// Both sides are looked up in this order, so the first pair with a matching ending wins:
+/// female "-ская" becomes "-ский" rather than "-ской", and "-ая" becomes "-ой".
+const SURNAME_FORMS: &[(&str, &str)] = &[
+    ("ский", "ская"),
+    ("цкий", "цкая"),
+    ("ской", "ская"),
+    ("цкой", "цкая"),
+    ("ов", "ова"),
+    ("ев", "ева"),
+    ("ёв", "ёва"),
+    ("ин", "ина"),
+    ("ын", "ына"),
+    ("ой", "ая"),
+    ("ый", "ая"),
+];
+
+// Shortest stem left before a gendered ending, so that "Ов" or "Ина" aren't converted
+const MIN_STEM: usize = 2;
+
+fn convert_segment(segment: &str, target: Gender) -> String {
+    if !segment.chars().all(is_cyrillic) {
+        return segment.to_owned();
+    }
+    let lowercase = segment.to_lowercase();
+    let ends_with = |suffix: &str| {
+        lowercase.ends_with(suffix)
+            && lowercase.chars().count() >= suffix.chars().count() + MIN_STEM
+    };
+    let (from, to) = match target {
+        Gender::Male if SURNAME_FORMS.iter().any(|&(male, _)| ends_with(male)) => {
+            return segment.to_owned()
+        }
+        Gender::Male => match SURNAME_FORMS.iter().find(|&&(_, female)| ends_with(female)) {
+            Some(&(male, female)) => (female, male),
+            None => return segment.to_owned(),
+        },
+        Gender::Female if SURNAME_FORMS.iter().any(|&(_, female)| ends_with(female)) => {
+            return segment.to_owned()
+        }
+        Gender::Female => match SURNAME_FORMS.iter().find(|&&(male, _)| ends_with(male)) {
+            Some(&(male, female)) => (male, female),
+            None => return segment.to_owned(),
+        },
+        _ => return segment.to_owned(),
+    };
+    let stem = segment
+        .chars()
+        .take(segment.chars().count() - from.chars().count())
+        .collect::<String>();
+    stem + &Casing::of(segment).apply(to)
+}
+
+/// Converts a surname to its form for the target gender, like "Иванов" to "Иванова" or
+/// "Полоцкая" to "Полоцкий"
+///
+/// Covers the -ов/-ев/-ин/-ын, -ский/-цкий and -ой/-ый/-ая classes; female "-ая" always
+/// becomes "-ой", so "Белая" turns into "Белой". Surnames of other classes, such as
+/// "Шевченко", "Черных" or "Станкевич", and surnames with non-Cyrillic letters are
+/// returned unchanged, as are names already in the target form. Words and parts of a double
+/// surname, as in "Петров Водкин" or "Иванов-Сидоров", are converted separately.
+/// `Gender::Androgynous` and `Gender::Unknown` keep the name.
+pub fn lastname_to_gender(name: &str, target: Gender) -> String {
+    let mut output = String::with_capacity(name.len() + 2);
+    for (segment, separator) in split_segments(name) {
+        output.push_str(&convert_segment(segment, target));
+        output.push_str(separator);
+    }
+    output
+}
+
+#[cfg(all(test, feature = "lastname-rules"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_convert_gendered_classes() {
+        for &(male, female) in &[
+            ("Иванов", "Иванова"),
+            ("Соловьёв", "Соловьёва"),
+            ("Григорьев", "Григорьева"),
+            ("Пушкин", "Пушкина"),
+            ("Птицын", "Птицына"),
+            ("Полоцкий", "Полоцкая"),
+            ("Достоевский", "Достоевская"),
+            ("Толстой", "Толстая"),
+            ("Иванов-Сидоров", "Иванова-Сидорова"),
+            ("Петров Водкин", "Петрова Водкина"),
+            ("ПЕТРОВ", "ПЕТРОВА"),
+        ] {
+            assert_eq!(lastname_to_gender(male, Gender::Female), female);
+            assert_eq!(lastname_to_gender(female, Gender::Male), male);
+            assert_eq!(lastname_to_gender(male, Gender::Male), male);
+            assert_eq!(lastname_to_gender(female, Gender::Female), female);
+        }
+        assert_eq!(lastname_to_gender("Белый", Gender::Female), "Белая");
+        assert_eq!(lastname_to_gender("Трубецкой", Gender::Female), "Трубецкая");
+    }
+
+    #[test]
+    fn should_keep_ungendered_surnames() {
+        for &name in &[
+            "Шевченко",
+            "Черных",
+            "Станкевич",
+            "Цой",
+            "Smirnov",
+            "Ов",
+            "Бонч-Бруевич",
+        ] {
+            assert_eq!(lastname_to_gender(name, Gender::Female), name);
+            assert_eq!(lastname_to_gender(name, Gender::Male), name);
+        }
+        assert_eq!(lastname_to_gender("Иванов", Gender::Unknown), "Иванов");
+    }
+}
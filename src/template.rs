@@ -0,0 +1,272 @@
+//! Formatting of full names by templates.
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::{inflect, Case, Error, FullName, NamePart};
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+enum Field {
+    Last,
+    First,
+    Middle,
+    LastInitial,
+    FirstInitial,
+    MiddleInitial,
+}
+
+impl Field {
+    fn parse(name: &str) -> Option<Field> {
+        Some(match name {
+            "last" => Field::Last,
+            "first" => Field::First,
+            "middle" => Field::Middle,
+            "l" => Field::LastInitial,
+            "f" => Field::FirstInitial,
+            "m" => Field::MiddleInitial,
+            _ => return None,
+        })
+    }
+
+    fn render(self, name: &FullName, case: Case) -> Option<String> {
+        let gender = name.gender();
+        Some(match self {
+            Field::Last => inflect(NamePart::Last, gender, name.last()?, case),
+            Field::First => inflect(NamePart::First, gender, name.first()?, case),
+            Field::Middle => inflect(NamePart::Middle, gender, name.middle()?, case),
+            Field::LastInitial => initials(name.last()?),
+            Field::FirstInitial => initials(name.first()?),
+            Field::MiddleInitial => initials(name.middle()?),
+        })
+    }
+}
+
+// Capital first letters of the hyphen-separated segments, so "Анна-Мария" gives "А.-М";
+// the final dot is left to the template
+fn initials(name: &str) -> String {
+    name.split('-')
+        .filter_map(|segment| segment.chars().next())
+        .map(|letter| letter.to_uppercase().collect::<String>())
+        .collect::<Vec<_>>()
+        .join(".-")
+}
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+enum Piece {
+    Literal(String),
+    /// Field with the text glued to it up to the next whitespace, like the dot in "{f}."
+    Field(Field, String),
+}
+
+/// Template describing how to print a full name
+///
+/// `{last}`, `{first}` and `{middle}` are replaced with the inflected parts, `{l}`, `{f}`
+/// and `{m}` with their initials; `{{` and `}}` print braces. When a part is missing its
+/// placeholder is dropped together with the text glued to it and the whitespace before it.
+/// Parse the template once and reuse it.
+///
+/// ```
+/// # #[cfg(all(feature = "firstname-rules", feature = "lastname-rules", feature = "middlename-rules"))]
+/// # {
+/// use petrovich::{Case, FullName, NameFormat};
+///
+/// let format: NameFormat = "{f}. {m}. {last}".parse().unwrap();
+/// let name = FullName::builder().last("Иванов").first("Иван").middle("Иванович").build();
+/// assert_eq!(format.format(&name, Case::Dative), "И. И. Иванову");
+///
+/// let name = FullName::builder().last("Иванова").first("Анна-Мария").build();
+/// assert_eq!(format.format(&name, Case::Dative), "А.-М. Ивановой");
+/// # }
+/// ```
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct NameFormat {
+    pieces: Vec<Piece>,
+}
+
+impl NameFormat {
+    /// Parses a template, reporting unknown placeholders and unbalanced braces with
+    /// `Error::InvalidFormat`
+    pub fn parse(template: &str) -> Result<NameFormat, Error> {
+        let invalid = |message: String| Error::InvalidFormat(message);
+        let mut pieces = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => placeholder.push(c),
+                            None => {
+                                return Err(invalid(format!("unclosed {{{}", placeholder)));
+                            }
+                        }
+                    }
+                    let field = Field::parse(&placeholder).ok_or_else(|| {
+                        invalid(format!("unknown placeholder {{{}}}", placeholder))
+                    })?;
+                    if !literal.is_empty() {
+                        pieces.push(Piece::Literal(std::mem::take(&mut literal)));
+                    }
+                    let mut glued = String::new();
+                    while let Some(&c) = chars.peek() {
+                        if c.is_whitespace() || c == '{' || c == '}' {
+                            break;
+                        }
+                        glued.push(c);
+                        chars.next();
+                    }
+                    pieces.push(Piece::Field(field, glued));
+                }
+                '}' => return Err(invalid("unmatched }".to_owned())),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            pieces.push(Piece::Literal(literal));
+        }
+        Ok(NameFormat { pieces })
+    }
+
+    /// Prints the name in the given case
+    pub fn format(&self, name: &FullName, case: Case) -> String {
+        let mut output = String::new();
+        // Whitespace after a dropped leading placeholder isn't printed
+        let mut skip_whitespace = false;
+        for piece in &self.pieces {
+            match piece {
+                Piece::Literal(text) if skip_whitespace => {
+                    output.push_str(text.trim_start());
+                    skip_whitespace = false;
+                }
+                Piece::Literal(text) => output.push_str(text),
+                Piece::Field(field, glued) => match field.render(name, case) {
+                    Some(value) => {
+                        output.push_str(&value);
+                        output.push_str(glued);
+                        skip_whitespace = false;
+                    }
+                    None => {
+                        let trimmed = output.trim_end().len();
+                        output.truncate(trimmed);
+                        skip_whitespace = output.is_empty();
+                    }
+                },
+            }
+        }
+        output
+    }
+}
+
+impl FromStr for NameFormat {
+    type Err = Error;
+
+    fn from_str(template: &str) -> Result<NameFormat, Error> {
+        NameFormat::parse(template)
+    }
+}
+
+/// Prints the template back, with braces escaped
+impl fmt::Display for NameFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let escape = |text: &str| text.replace('{', "{{").replace('}', "}}");
+        for piece in &self.pieces {
+            match piece {
+                Piece::Literal(text) => f.write_str(&escape(text))?,
+                Piece::Field(field, glued) => {
+                    let name = match field {
+                        Field::Last => "last",
+                        Field::First => "first",
+                        Field::Middle => "middle",
+                        Field::LastInitial => "l",
+                        Field::FirstInitial => "f",
+                        Field::MiddleInitial => "m",
+                    };
+                    write!(f, "{{{}}}{}", name, glued)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(all(
+        feature = "firstname-rules",
+        feature = "lastname-rules",
+        feature = "middlename-rules"
+    ))]
+    fn ivanov() -> FullName {
+        FullName::builder()
+            .last("Иванов")
+            .first("Иван")
+            .middle("Иванович")
+            .build()
+    }
+
+    #[cfg(all(
+        feature = "firstname-rules",
+        feature = "lastname-rules",
+        feature = "middlename-rules"
+    ))]
+    #[test]
+    fn should_format_shapes() {
+        let name = ivanov();
+        let format =
+            |template: &str, case| NameFormat::parse(template).unwrap().format(&name, case);
+        assert_eq!(format("{last} {f}. {m}.", Case::Nominative), "Иванов И. И.");
+        assert_eq!(format("{f}. {m}. {last}", Case::Dative), "И. И. Иванову");
+        assert_eq!(format("{last} {first}", Case::Genitive), "Иванова Ивана");
+        assert_eq!(format("{{{last}}}", Case::Instrumental), "{Ивановым}");
+    }
+
+    #[cfg(all(
+        feature = "firstname-rules",
+        feature = "lastname-rules",
+        feature = "middlename-rules"
+    ))]
+    #[test]
+    fn should_drop_missing_parts() {
+        let name = FullName::builder().last("Иванова").first("Анна").build();
+        let format = |template: &str| {
+            NameFormat::parse(template)
+                .unwrap()
+                .format(&name, Case::Dative)
+        };
+        assert_eq!(format("{last} {f}. {m}."), "Ивановой А.");
+        assert_eq!(format("{m}. {f}. {last}"), "А. Ивановой");
+        assert_eq!(format("{first} {middle} {last}"), "Анне Ивановой");
+    }
+
+    #[test]
+    fn should_reject_invalid_templates() {
+        assert_eq!(
+            NameFormat::parse("{last} {x}"),
+            Err(Error::InvalidFormat("unknown placeholder {x}".to_owned()))
+        );
+        assert_eq!(
+            NameFormat::parse("{last"),
+            Err(Error::InvalidFormat("unclosed {last".to_owned()))
+        );
+        assert!("last}".parse::<NameFormat>().is_err());
+    }
+
+    #[test]
+    fn should_print_template_back() {
+        let template = "{last} {f}. {m}. {{id}}";
+        assert_eq!(NameFormat::parse(template).unwrap().to_string(), template);
+    }
+}
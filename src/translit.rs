@@ -0,0 +1,630 @@
+//! Transliteration of Russian names into the Latin script and back.
+
+use super::{inflect, Case, Gender, NamePart};
+
+/// Transliteration scheme for `to_latin`
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+#[non_exhaustive]
+pub enum TranslitStandard {
+    /// ГОСТ 7.79-2000, system B: reversible, with "j" for й, "x" for х, "cz" for ц and
+    /// backticks for ъ and ь, as in "Dmitrij" or "Solov`yov"
+    Gost779B,
+    /// ICAO Doc 9303, used in Russian passports since 2013: "i" for й, "ie" for ъ, ь left
+    /// out, as in "Dmitrii" or "Solovev"
+    Icao9303,
+    /// Practical spelling of emails and logins: "y" for й, ь left out except before a vowel,
+    /// and "y" for the -ый ending, as in "Dmitriy", "Ilyin" or "Chorny"
+    Practical,
+}
+
+impl TranslitStandard {
+    /// Whether `from_latin` can restore the Cyrillic spelling
+    pub fn is_reversible(self) -> bool {
+        self == TranslitStandard::Gost779B
+    }
+}
+
+/// Letters of the Russian alphabet with their spelling in system B of ГОСТ 7.79, ICAO
+/// 9303 and the practical scheme; ц, ь and ъ depend on the context in some of them
+const LETTERS: &[(char, &str, &str, &str)] = &[
+    ('а', "a", "a", "a"),
+    ('б', "b", "b", "b"),
+    ('в', "v", "v", "v"),
+    ('г', "g", "g", "g"),
+    ('д', "d", "d", "d"),
+    ('е', "e", "e", "e"),
+    ('ё', "yo", "e", "yo"),
+    ('ж', "zh", "zh", "zh"),
+    ('з', "z", "z", "z"),
+    ('и', "i", "i", "i"),
+    ('й', "j", "i", "y"),
+    ('к', "k", "k", "k"),
+    ('л', "l", "l", "l"),
+    ('м', "m", "m", "m"),
+    ('н', "n", "n", "n"),
+    ('о', "o", "o", "o"),
+    ('п', "p", "p", "p"),
+    ('р', "r", "r", "r"),
+    ('с', "s", "s", "s"),
+    ('т', "t", "t", "t"),
+    ('у', "u", "u", "u"),
+    ('ф', "f", "f", "f"),
+    ('х', "x", "kh", "kh"),
+    ('ц', "cz", "ts", "ts"),
+    ('ч', "ch", "ch", "ch"),
+    ('ш', "sh", "sh", "sh"),
+    ('щ', "shh", "shch", "shch"),
+    ('ъ', "``", "ie", ""),
+    ('ы', "y`", "y", "y"),
+    ('ь', "`", "", ""),
+    ('э', "e`", "e", "e"),
+    ('ю', "yu", "iu", "yu"),
+    ('я', "ya", "ia", "ya"),
+];
+
+fn is_vowel(c: char) -> bool {
+    "аеёиоуыэюя".contains(c)
+}
+
+fn spelling(standard: TranslitStandard, c: char) -> Option<&'static str> {
+    let &(_, gost, icao, practical) = LETTERS.iter().find(|&&(letter, ..)| letter == c)?;
+    Some(match standard {
+        TranslitStandard::Gost779B => gost,
+        TranslitStandard::Icao9303 => icao,
+        TranslitStandard::Practical => practical,
+    })
+}
+
+// Whether each character belongs to a word of two or more letters, all of them capitals
+fn in_capitals(chars: &[char]) -> Vec<bool> {
+    let mut capitals = vec![false; chars.len()];
+    let mut start = 0;
+    while start < chars.len() {
+        let end = chars[start..]
+            .iter()
+            .position(|c| !c.is_alphabetic())
+            .map_or(chars.len(), |len| start + len);
+        if end - start > 1 && chars[start..end].iter().all(|c| c.is_uppercase()) {
+            capitals[start..end]
+                .iter_mut()
+                .for_each(|capital| *capital = true);
+        }
+        start = end + 1;
+    }
+    capitals
+}
+
+/// Writes a Russian name in the Latin script
+///
+/// Characters other than Russian letters, such as hyphens, apostrophes or spaces, are
+/// kept as they are. Words in capitals stay in capitals and a capital letter starts the
+/// spelling of a capitalized one, so "Щукин" becomes "Shchukin" and "ЩУКИН" becomes
+/// "SHCHUKIN".
+///
+/// ```
+/// use petrovich::{to_latin, TranslitStandard};
+///
+/// assert_eq!(to_latin("Дмитрий", TranslitStandard::Gost779B), "Dmitrij");
+/// assert_eq!(to_latin("Дмитрий", TranslitStandard::Icao9303), "Dmitrii");
+/// assert_eq!(to_latin("Дмитрий", TranslitStandard::Practical), "Dmitriy");
+/// assert_eq!(to_latin("Бонч-Бруевич", TranslitStandard::Icao9303), "Bonch-Bruevich");
+/// ```
+pub fn to_latin(name: &str, standard: TranslitStandard) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let capitals = in_capitals(&chars);
+    let lower = |i: usize| chars.get(i).map(|c| c.to_lowercase().next().unwrap_or(*c));
+    let mut output = String::with_capacity(name.len());
+    for (i, &c) in chars.iter().enumerate() {
+        let letter = lower(i).unwrap_or(c);
+        let next = lower(i + 1);
+        let at_end = !next.is_some_and(char::is_alphabetic);
+        let latin = match (standard, letter) {
+            (TranslitStandard::Gost779B, 'ц') if matches!(next, Some('е' | 'и' | 'ы' | 'й')) => {
+                Some("c")
+            }
+            // "Ильин" becomes "Ilyin", but "Соловьёв" becomes "Solovyov"
+            (TranslitStandard::Practical, 'ь')
+                if next.is_some_and(|next| {
+                    is_vowel(next) && !spelling(standard, next).unwrap_or("").starts_with('y')
+                }) =>
+            {
+                Some("y")
+            }
+            (TranslitStandard::Practical, 'й') if at_end && i > 0 && lower(i - 1) == Some('ы') => {
+                Some("")
+            }
+            _ => spelling(standard, letter),
+        };
+        match latin {
+            Some(latin) if capitals[i] => output.push_str(&latin.to_uppercase()),
+            Some(latin) if c.is_uppercase() => {
+                let mut latin = latin.chars();
+                output.extend(latin.next().into_iter().flat_map(char::to_uppercase));
+                output.extend(latin);
+            }
+            Some(latin) => output.push_str(latin),
+            None => output.push(c),
+        }
+    }
+    output
+}
+
+/// Restores the Russian spelling of a name written with a reversible standard, or gives
+/// `None` for the others
+///
+/// Sequences that no letter is spelled with, as well as hyphens, apostrophes and other
+/// characters, are kept as they are.
+///
+/// ```
+/// use petrovich::{from_latin, to_latin, TranslitStandard};
+///
+/// let latin = to_latin("Соловьёв-Щукин", TranslitStandard::Gost779B);
+/// assert_eq!(latin, "Solov`yov-Shhukin");
+/// assert_eq!(
+///     from_latin(&latin, TranslitStandard::Gost779B).as_deref(),
+///     Some("Соловьёв-Щукин")
+/// );
+/// assert_eq!(from_latin("Dmitrii", TranslitStandard::Icao9303), None);
+/// ```
+pub fn from_latin(name: &str, standard: TranslitStandard) -> Option<String> {
+    if !standard.is_reversible() {
+        return None;
+    }
+    let chars: Vec<char> = name.chars().collect();
+    let mut output = String::with_capacity(name.len());
+    let mut i = 0;
+    while i < chars.len() {
+        // Longest spelling first, so "shh" isn't read as "sh" followed by "h"
+        let found = (1..=3).rev().find_map(|len| {
+            let latin = chars.get(i..i + len)?;
+            let lowercase: String = latin.iter().flat_map(|c| c.to_lowercase()).collect();
+            let letter = match lowercase.as_str() {
+                "c" => 'ц',
+                spelling => LETTERS.iter().find(|&&(_, gost, ..)| gost == spelling)?.0,
+            };
+            Some((letter, len))
+        });
+        match found {
+            Some((letter, len)) => {
+                if chars[i].is_uppercase() {
+                    output.extend(letter.to_uppercase());
+                } else {
+                    output.push(letter);
+                }
+                i += len;
+            }
+            None => {
+                output.push(chars[i]);
+                i += 1;
+            }
+        }
+    }
+    Some(output)
+}
+
+/// Beginnings of names read with э rather than е when written with a plain "e"
+const E_NAMES: &[&str] = &[
+    "eduard", "eleonor", "elvir", "eldar", "elin", "ell", "elmir", "elz", "emil", "emm", "erast",
+    "erik", "ernest", "erdni", "evelin",
+];
+
+// Whether the word starts with three consonants other than в, as after the э of "Эрнст",
+// while names like "Евстафий" or "Ершов" start with е
+fn before_cluster(rest: &str, standard: TranslitStandard) -> bool {
+    let rest = to_cyrillic_word(rest, standard);
+    let mut consonants = rest
+        .chars()
+        .take_while(|&c| !is_vowel(c) && !"йьъ".contains(c));
+    consonants.next().is_some_and(|c| c != 'в') && consonants.count() >= 2
+}
+
+// Cyrillic spelling of a lowercase word of Latin letters
+fn to_cyrillic_word(word: &str, standard: TranslitStandard) -> String {
+    let icao = standard == TranslitStandard::Icao9303;
+    let mut output = String::with_capacity(word.len() * 2);
+    let mut i = 0;
+    while i < word.len() {
+        let rest = &word[i..];
+        let after_consonant = output
+            .chars()
+            .last()
+            .is_some_and(|c| !is_vowel(c) && !"йьъ".contains(c));
+        // ё is written without ь after ж, ч, ш and щ, as in "Хрущёв"
+        let after_hushing = output.ends_with(|c| "жчшщ".contains(c));
+        // "Rimsky" is read as "Римский" rather than "Римскый"
+        let adjective = if output.ends_with(|c| "гкхжчшщ".contains(c)) {
+            "ий"
+        } else {
+            "ый"
+        };
+        let (cyrillic, len) = match rest {
+            // ь is only restored in the endings of names where it is expected
+            "yev" | "yeva" | "yevich" | "yevna" if !icao && after_consonant => ("ье", 2),
+            "yov" | "yova" if !icao && after_consonant && !after_hushing => ("ьё", 2),
+            "ya" if after_consonant => ("ья", 2),
+            // ICAO drops ь and writes я as "ia", so its ия is "iia"
+            _ if after_consonant && rest.starts_with("ia") => (if icao { "ья" } else { "ия" }, 2),
+            "iy" | "ii" | "ij" => ("ий", 2),
+            "yi" if after_consonant => (adjective, 2),
+            "y" if after_consonant => (adjective, 1),
+            "i" if icao && !after_consonant && i > 0 => ("й", 1),
+            _ if !icao && after_consonant && rest.starts_with("yi") => ("ьи", 2),
+            _ if rest.starts_with("shch") => ("щ", 4),
+            _ if rest.starts_with("sch") => ("щ", 3),
+            _ if rest.starts_with("zh") => ("ж", 2),
+            _ if rest.starts_with("kh") => ("х", 2),
+            _ if rest.starts_with("ts") => ("ц", 2),
+            _ if rest.starts_with("ch") => ("ч", 2),
+            _ if rest.starts_with("sh") => ("ш", 2),
+            _ if rest.starts_with("ck") => ("к", 2),
+            _ if rest.starts_with("yu") || rest.starts_with("iu") && icao => ("ю", 2),
+            _ if rest.starts_with("ya") || rest.starts_with("ia") && icao => ("я", 2),
+            _ if rest.starts_with("yo") => ("ё", 2),
+            _ if rest.starts_with("ye") => ("е", 2),
+            _ if rest.starts_with('e') && i == 0 => {
+                if E_NAMES.iter().any(|name| word.starts_with(name))
+                    || before_cluster(&word[1..], standard)
+                {
+                    ("э", 1)
+                } else {
+                    ("е", 1)
+                }
+            }
+            _ if rest.starts_with('y') => (if after_consonant { "ы" } else { "й" }, 1),
+            _ if rest.starts_with('c') => match rest.as_bytes().get(1) {
+                Some(b'e' | b'i' | b'y') => ("ц", 1),
+                _ => ("к", 1),
+            },
+            _ => {
+                let letter = match rest.as_bytes()[0] {
+                    b'a' => "а",
+                    b'b' => "б",
+                    b'v' | b'w' => "в",
+                    b'g' => "г",
+                    b'd' => "д",
+                    b'e' => "е",
+                    b'z' => "з",
+                    b'i' => "и",
+                    b'j' => "й",
+                    b'k' | b'q' => "к",
+                    b'l' => "л",
+                    b'm' => "м",
+                    b'n' => "н",
+                    b'o' => "о",
+                    b'p' => "п",
+                    b'r' => "р",
+                    b's' => "с",
+                    b't' => "т",
+                    b'u' => "у",
+                    b'f' => "ф",
+                    b'h' => "х",
+                    _ => "кс",
+                };
+                (letter, 1)
+            }
+        };
+        output.push_str(cyrillic);
+        i += len;
+    }
+    output
+}
+
+/// Reads a Russian name written in the Latin script with any standard
+///
+/// ГОСТ 7.79 is restored exactly with `from_latin`. The other standards lose letters, so
+/// the most likely reading for a name is chosen: "e" is read as е except at the start of
+/// names like "Eduard" or before three consonants as in "Ernst", "y" after a consonant as
+/// ы, and ь is only restored in endings like "-yev", "-yov" or "-ya", but not after ж, ч,
+/// ш and щ, as in "Khrushchyov". "ia" after a consonant is read as ья in ICAO, as in
+/// "Natalia", and as ия otherwise. Casing is kept as in `to_latin` and other characters
+/// are kept as they are.
+///
+/// ```
+/// use petrovich::{to_cyrillic, TranslitStandard};
+///
+/// assert_eq!(to_cyrillic("Ivanov", TranslitStandard::Practical), "Иванов");
+/// assert_eq!(to_cyrillic("Grigoryev", TranslitStandard::Practical), "Григорьев");
+/// assert_eq!(to_cyrillic("Evgenii", TranslitStandard::Icao9303), "Евгений");
+/// assert_eq!(to_cyrillic("Eduard", TranslitStandard::Icao9303), "Эдуард");
+/// ```
+pub fn to_cyrillic(name: &str, standard: TranslitStandard) -> String {
+    if let Some(cyrillic) = from_latin(name, standard) {
+        return cyrillic;
+    }
+    let mut output = String::with_capacity(name.len() * 2);
+    let mut rest = name;
+    while let Some(c) = rest.chars().next() {
+        let len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        if len == 0 {
+            output.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        let (word, tail) = rest.split_at(len);
+        let cyrillic = to_cyrillic_word(&word.to_ascii_lowercase(), standard);
+        if len > 1 && word.bytes().all(|b| b.is_ascii_uppercase()) {
+            output.push_str(&cyrillic.to_uppercase());
+        } else if c.is_ascii_uppercase() {
+            let mut cyrillic = cyrillic.chars();
+            output.extend(cyrillic.next().into_iter().flat_map(char::to_uppercase));
+            output.extend(cyrillic);
+        } else {
+            output.push_str(&cyrillic);
+        }
+        rest = tail;
+    }
+    output
+}
+
+/// Name written in the Latin script inflected through its Cyrillic spelling
+#[derive(Eq, PartialEq, Clone, Debug, Hash)]
+pub struct LatinInflection {
+    /// Name as read in Cyrillic before inflecting, e.g. "Иванов"
+    pub cyrillic: String,
+    /// Inflected Cyrillic form, e.g. "Иванову"
+    pub inflected: String,
+    /// Inflected form written back with the same standard, e.g. "Ivanovu"
+    pub latin: String,
+}
+
+/// Inflects a name written in the Latin script using the rules for the given part
+///
+/// The name is read with `to_cyrillic`, inflected like any other and written back with
+/// `to_latin`; every step is kept in the result so the reading can be checked.
+///
+/// ```
+/// use petrovich::{inflect_latin, Case, Gender, NamePart, TranslitStandard};
+///
+/// let dative = inflect_latin(
+///     NamePart::Last,
+///     Gender::Male,
+///     "Ivanov",
+///     Case::Dative,
+///     TranslitStandard::Practical,
+/// );
+/// assert_eq!(dative.cyrillic, "Иванов");
+/// assert_eq!(dative.inflected, "Иванову");
+/// assert_eq!(dative.latin, "Ivanovu");
+/// ```
+pub fn inflect_latin(
+    part: NamePart,
+    gender: Gender,
+    name: &str,
+    case: Case,
+    standard: TranslitStandard,
+) -> LatinInflection {
+    let cyrillic = to_cyrillic(name, standard);
+    let inflected = inflect(part, gender, &cyrillic, case);
+    let latin = to_latin(&inflected, standard);
+    LatinInflection {
+        cyrillic,
+        inflected,
+        latin,
+    }
+}
+
+/// Inflects first name written in the Latin script, see `inflect_latin`
+#[cfg(feature = "firstname-rules")]
+pub fn firstname_latin(
+    gender: Gender,
+    name: &str,
+    case: Case,
+    standard: TranslitStandard,
+) -> LatinInflection {
+    inflect_latin(NamePart::First, gender, name, case, standard)
+}
+
+/// Inflects last name written in the Latin script, see `inflect_latin`
+#[cfg(feature = "lastname-rules")]
+pub fn lastname_latin(
+    gender: Gender,
+    name: &str,
+    case: Case,
+    standard: TranslitStandard,
+) -> LatinInflection {
+    inflect_latin(NamePart::Last, gender, name, case, standard)
+}
+
+/// Inflects middle name written in the Latin script, see `inflect_latin`
+#[cfg(feature = "middlename-rules")]
+pub fn middlename_latin(
+    gender: Gender,
+    name: &str,
+    case: Case,
+    standard: TranslitStandard,
+) -> LatinInflection {
+    inflect_latin(NamePart::Middle, gender, name, case, standard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAMES: &[&str] = &[
+        "Дмитрий",
+        "Соловьёв",
+        "Щукина",
+        "Цветков",
+        "Лицей",
+        "Ильин",
+        "Подъячев",
+        "Чёрный",
+        "Эрдниев",
+        "Юлия",
+        "Хабибуллин",
+        "Бонч-Бруевич",
+        "Д'Артаньян",
+        "ЩЕРБАКОВ",
+    ];
+
+    fn all(standard: TranslitStandard) -> Vec<String> {
+        NAMES.iter().map(|name| to_latin(name, standard)).collect()
+    }
+
+    #[test]
+    fn should_follow_gost() {
+        assert_eq!(
+            all(TranslitStandard::Gost779B),
+            [
+                "Dmitrij",
+                "Solov`yov",
+                "Shhukina",
+                "Czvetkov",
+                "Licej",
+                "Il`in",
+                "Pod``yachev",
+                "Chyorny`j",
+                "E`rdniev",
+                "Yuliya",
+                "Xabibullin",
+                "Bonch-Bruevich",
+                "D'Artan`yan",
+                "SHHERBAKOV",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_follow_icao() {
+        assert_eq!(
+            all(TranslitStandard::Icao9303),
+            [
+                "Dmitrii",
+                "Solovev",
+                "Shchukina",
+                "Tsvetkov",
+                "Litsei",
+                "Ilin",
+                "Podieiachev",
+                "Chernyi",
+                "Erdniev",
+                "Iuliia",
+                "Khabibullin",
+                "Bonch-Bruevich",
+                "D'Artanian",
+                "SHCHERBAKOV",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_follow_practical_spelling() {
+        assert_eq!(
+            all(TranslitStandard::Practical),
+            [
+                "Dmitriy",
+                "Solovyov",
+                "Shchukina",
+                "Tsvetkov",
+                "Litsey",
+                "Ilyin",
+                "Podyachev",
+                "Chyorny",
+                "Erdniev",
+                "Yuliya",
+                "Khabibullin",
+                "Bonch-Bruevich",
+                "D'Artanyan",
+                "SHCHERBAKOV",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_restore_gost_spelling() {
+        for &name in NAMES {
+            let latin = to_latin(name, TranslitStandard::Gost779B);
+            assert_eq!(
+                from_latin(&latin, TranslitStandard::Gost779B).as_deref(),
+                Some(name),
+                "{}",
+                latin
+            );
+        }
+        assert_eq!(
+            from_latin("Ivanov Q.", TranslitStandard::Gost779B).as_deref(),
+            Some("Иванов Q.")
+        );
+        assert_eq!(from_latin("Ivanov", TranslitStandard::Practical), None);
+    }
+
+    #[test]
+    fn should_read_lossy_standards() {
+        let read = |name| to_cyrillic(name, TranslitStandard::Practical);
+        assert_eq!(read("Dmitriy"), "Дмитрий");
+        assert_eq!(read("Sergey"), "Сергей");
+        assert_eq!(read("Tsvetkov"), "Цветков");
+        assert_eq!(read("Shchukina"), "Щукина");
+        assert_eq!(read("Ilyin"), "Ильин");
+        assert_eq!(read("Natalya"), "Наталья");
+        assert_eq!(read("Solovyov"), "Соловьёв");
+        assert_eq!(read("Fyodor"), "Фёдор");
+        assert_eq!(read("Yuryevich"), "Юрьевич");
+        assert_eq!(read("Krylov"), "Крылов");
+        assert_eq!(read("Cherny"), "Черный");
+        assert_eq!(read("Yelena"), "Елена");
+        assert_eq!(read("Alexandr"), "Александр");
+        assert_eq!(read("Mikhail"), "Михаил");
+        assert_eq!(read("ERIK"), "ЭРИК");
+        assert_eq!(read("Rimsky-Korsakov"), "Римский-Корсаков");
+        assert_eq!(read("Khrushchyov"), "Хрущёв");
+        assert_eq!(read("Gorbachyov"), "Горбачёв");
+        assert_eq!(read("Ernst"), "Эрнст");
+        assert_eq!(read("Evdokimov"), "Евдокимов");
+        assert_eq!(read("Ershov"), "Ершов");
+        assert_eq!(read("Eshchenko"), "Ещенко");
+        assert_eq!(read("Maria"), "Мария");
+
+        let read = |name| to_cyrillic(name, TranslitStandard::Icao9303);
+        assert_eq!(read("Dmitrii"), "Дмитрий");
+        assert_eq!(read("Sergei"), "Сергей");
+        assert_eq!(read("Iuliia"), "Юлия");
+        assert_eq!(read("Chernyi"), "Черный");
+        assert_eq!(read("Andreev"), "Андреев");
+        assert_eq!(read("Raisa"), "Раиса");
+        assert_eq!(read("Natalia"), "Наталья");
+        assert_eq!(read("Ilia"), "Илья");
+        assert_eq!(read("Ilya"), "Илья");
+        assert_eq!(read("Mariia"), "Мария");
+        assert_eq!(read("Ernst"), "Эрнст");
+        assert_eq!(read("Evstafii"), "Евстафий");
+        assert_eq!(read("Khrushchev"), "Хрущев");
+
+        assert_eq!(to_cyrillic("Il`in", TranslitStandard::Gost779B), "Ильин");
+    }
+
+    #[cfg(all(
+        feature = "firstname-rules",
+        feature = "lastname-rules",
+        feature = "middlename-rules"
+    ))]
+    #[test]
+    fn should_inflect_latin_names() {
+        let inflection = firstname_latin(
+            Gender::Female,
+            "Natalya",
+            Case::Dative,
+            TranslitStandard::Practical,
+        );
+        assert_eq!(inflection.inflected, "Наталье");
+        assert_eq!(inflection.latin, "Natalye");
+        let inflection = middlename_latin(
+            Gender::Male,
+            "Sergeevich",
+            Case::Genitive,
+            TranslitStandard::Icao9303,
+        );
+        assert_eq!(inflection.cyrillic, "Сергеевич");
+        assert_eq!(inflection.latin, "Sergeevicha");
+        let inflection = lastname_latin(
+            Gender::Male,
+            "SHHERBAKOV",
+            Case::Instrumental,
+            TranslitStandard::Gost779B,
+        );
+        assert_eq!(inflection.inflected, "ЩЕРБАКОВЫМ");
+        assert_eq!(inflection.latin, "SHHERBAKOVY`M");
+    }
+}
@@ -0,0 +1,324 @@
+//! Ukrainian names: "Шевченкові", "Олені Петрівні".
+//!
+//! The rules come from `rules_uk.yml` and are matched the same way as the Russian ones;
+//! they give a form for one more case, the vocative.
+//!
+//! ```
+//! use petrovich::uk::{self, Case};
+//! use petrovich::Gender;
+//!
+//! assert_eq!(uk::lastname(Gender::Male, "Шевченко", Case::Dative), "Шевченкові");
+//! assert_eq!(uk::firstname(Gender::Female, "Олена", Case::Dative), "Олені");
+//! assert_eq!(uk::middlename(Gender::Female, "Петрівна", Case::Dative), "Петрівні");
+//! assert_eq!(uk::firstname(Gender::Male, "Тарас", Case::Vocative), "Тарасе");
+//! ```
+
+use std::fmt;
+
+use super::detect::detect_gender_uk;
+use super::rules::{NoCustomTags, UK_RULES, VOCATIVE_SLOT};
+use super::{match_name, nfc, Gender, InflectOptions, NamePart, ENDING_RESERVE};
+
+/// Відмінки української мови
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+#[non_exhaustive]
+pub enum Case {
+    /// Називний  | _Хто? Що?_
+    Nominative,
+    /// Родовий   | _Кого? Чого?_
+    Genitive,
+    /// Давальний | _Кому? Чому?_
+    Dative,
+    /// Знахідний | _Кого? Що?_
+    Accusative,
+    /// Орудний   | _Ким? Чим?_
+    Instrumental,
+    /// Місцевий  | _На кому? На чому?_
+    Locative,
+    /// Кличний   | _звертання_
+    Vocative,
+}
+
+impl Case {
+    /// All cases in the order of the grammars
+    pub const ALL: [Case; 7] = [
+        Case::Nominative,
+        Case::Genitive,
+        Case::Dative,
+        Case::Accusative,
+        Case::Instrumental,
+        Case::Locative,
+        Case::Vocative,
+    ];
+
+    /// Iterates over all cases
+    pub fn iter() -> impl Iterator<Item = Case> {
+        Case::ALL.iter().copied()
+    }
+
+    /// English identifier of the case, e.g. "vocative"
+    pub fn name(self) -> &'static str {
+        match self {
+            Case::Nominative => "nominative",
+            Case::Genitive => "genitive",
+            Case::Dative => "dative",
+            Case::Accusative => "accusative",
+            Case::Instrumental => "instrumental",
+            Case::Locative => "locative",
+            Case::Vocative => "vocative",
+        }
+    }
+
+    /// Ukrainian name of the case, e.g. "кличний"
+    pub fn ukrainian_name(self) -> &'static str {
+        match self {
+            Case::Nominative => "називний",
+            Case::Genitive => "родовий",
+            Case::Dative => "давальний",
+            Case::Accusative => "знахідний",
+            Case::Instrumental => "орудний",
+            Case::Locative => "місцевий",
+            Case::Vocative => "кличний",
+        }
+    }
+
+    // Slot of the rule modifiers: the Russian case with the same ending, the locative
+    // taking the place of the prepositional
+    fn slot(self) -> usize {
+        match self {
+            Case::Vocative => VOCATIVE_SLOT,
+            case => case as usize,
+        }
+    }
+}
+
+/// The Russian prepositional becomes the locative
+impl From<crate::Case> for Case {
+    fn from(case: crate::Case) -> Case {
+        match case {
+            crate::Case::Nominative => Case::Nominative,
+            crate::Case::Genitive => Case::Genitive,
+            crate::Case::Dative => Case::Dative,
+            crate::Case::Accusative => Case::Accusative,
+            crate::Case::Instrumental => Case::Instrumental,
+            crate::Case::Prepositional => Case::Locative,
+        }
+    }
+}
+
+/// Prints the English identifier, or the Ukrainian name with the alternate flag (`{:#}`)
+impl fmt::Display for Case {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.write_str(self.ukrainian_name())
+        } else {
+            f.write_str(self.name())
+        }
+    }
+}
+
+/// Inflects a Ukrainian name using the rules for the given part
+///
+/// Hyphenated and multi-word names, capitals and names no rule matches are handled like
+/// in `petrovich::inflect`.
+pub fn inflect(part: NamePart, gender: Gender, name: &str, case: Case) -> String {
+    let name = &*nfc(name);
+    let segments = match match_name(
+        gender,
+        name,
+        UK_RULES.list(part),
+        &NoCustomTags,
+        &InflectOptions::DEFAULT,
+    ) {
+        Ok(segments) => segments,
+        Err(_) => return name.to_owned(),
+    };
+    let mut output = String::with_capacity(name.len() + ENDING_RESERVE);
+    for segment in segments {
+        // Writing to a String never fails
+        let _ = segment.write_slot(case.slot(), &mut output);
+    }
+    output
+}
+
+/// Inflects Ukrainian first name
+#[cfg(feature = "firstname-rules")]
+pub fn firstname(gender: Gender, name: &str, case: Case) -> String {
+    inflect(NamePart::First, gender, name, case)
+}
+
+/// Inflects Ukrainian last name
+#[cfg(feature = "lastname-rules")]
+pub fn lastname(gender: Gender, name: &str, case: Case) -> String {
+    inflect(NamePart::Last, gender, name, case)
+}
+
+/// Inflects Ukrainian middle name
+#[cfg(feature = "middlename-rules")]
+pub fn middlename(gender: Gender, name: &str, case: Case) -> String {
+    inflect(NamePart::Middle, gender, name, case)
+}
+
+/// Detects gender from Ukrainian name parts, fallbacks to `Gender::Unknown`
+///
+/// Parts are checked in order of reliability like in `petrovich::detect_gender`. Last names
+/// like "Шевченко" or "Ковальчук" are the same for men and women, so they only give
+/// `Gender::Androgynous` when nothing else is known.
+///
+/// ```
+/// use petrovich::{uk, Gender};
+///
+/// assert_eq!(uk::detect_gender(Some("Шевченко"), Some("Олена"), None), Gender::Female);
+/// assert_eq!(uk::detect_gender(Some("Ковальчук"), None, None), Gender::Androgynous);
+/// ```
+pub fn detect_gender(
+    lastname: Option<&str>,
+    firstname: Option<&str>,
+    middlename: Option<&str>,
+) -> Gender {
+    detect_gender_uk(lastname, firstname, middlename).unwrap_or(Gender::Unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn forms(part: NamePart, gender: Gender, name: &str) -> Vec<String> {
+        Case::iter()
+            .map(|case| inflect(part, gender, name, case))
+            .collect()
+    }
+
+    #[cfg(feature = "lastname-rules")]
+    #[test]
+    fn should_decline_lastnames() {
+        assert_eq!(
+            forms(NamePart::Last, Gender::Male, "Шевченко"),
+            [
+                "Шевченко",
+                "Шевченка",
+                "Шевченкові",
+                "Шевченка",
+                "Шевченком",
+                "Шевченкові",
+                "Шевченку",
+            ]
+        );
+        assert_eq!(
+            forms(NamePart::Last, Gender::Female, "Заньковецька"),
+            [
+                "Заньковецька",
+                "Заньковецької",
+                "Заньковецькій",
+                "Заньковецьку",
+                "Заньковецькою",
+                "Заньковецькій",
+                "Заньковецька",
+            ]
+        );
+        assert_eq!(
+            inflect(NamePart::Last, Gender::Female, "Шевченко", Case::Dative),
+            "Шевченко"
+        );
+        assert_eq!(
+            inflect(NamePart::Last, Gender::Male, "Ковальчук", Case::Dative),
+            "Ковальчукові"
+        );
+        assert_eq!(
+            inflect(NamePart::Last, Gender::Female, "Ковальчук", Case::Dative),
+            "Ковальчук"
+        );
+        assert_eq!(
+            inflect(NamePart::Last, Gender::Male, "Кравець", Case::Genitive),
+            "Кравця"
+        );
+        assert_eq!(
+            inflect(
+                NamePart::Last,
+                Gender::Male,
+                "Яворський",
+                Case::Instrumental
+            ),
+            "Яворським"
+        );
+        assert_eq!(
+            inflect(NamePart::Last, Gender::Male, "ШЕВЧЕНКО", Case::Dative),
+            "ШЕВЧЕНКОВІ"
+        );
+    }
+
+    #[test]
+    fn should_decline_first_and_middle_names() {
+        assert_eq!(
+            forms(NamePart::First, Gender::Female, "Ольга"),
+            [
+                "Ольга",
+                "Ольги",
+                "Ользі",
+                "Ольгу",
+                "Ольгою",
+                "Ользі",
+                "Ольго"
+            ]
+        );
+        assert_eq!(
+            forms(NamePart::First, Gender::Male, "Андрій"),
+            [
+                "Андрій",
+                "Андрія",
+                "Андрієві",
+                "Андрія",
+                "Андрієм",
+                "Андрієві",
+                "Андрію"
+            ]
+        );
+        assert_eq!(
+            inflect(NamePart::First, Gender::Female, "Марія", Case::Vocative),
+            "Маріє"
+        );
+        assert_eq!(
+            inflect(NamePart::First, Gender::Male, "Олег", Case::Vocative),
+            "Олеже"
+        );
+        assert_eq!(
+            inflect(NamePart::First, Gender::Female, "Любов", Case::Instrumental),
+            "Любов'ю"
+        );
+        assert_eq!(
+            inflect(NamePart::Middle, Gender::Male, "Тарасович", Case::Vocative),
+            "Тарасовичу"
+        );
+        assert_eq!(
+            inflect(NamePart::Middle, Gender::Female, "Петрівна", Case::Vocative),
+            "Петрівно"
+        );
+    }
+
+    #[test]
+    fn should_map_russian_cases() {
+        assert_eq!(Case::from(crate::Case::Prepositional), Case::Locative);
+        assert_eq!(Case::Vocative.to_string(), "vocative");
+        assert_eq!(format!("{:#}", Case::Locative), "місцевий");
+    }
+
+    #[cfg(feature = "gender-detection")]
+    #[test]
+    fn should_detect_gender() {
+        assert_eq!(
+            detect_gender(Some("Ковальчук"), Some("Микола"), None),
+            Gender::Male
+        );
+        assert_eq!(
+            detect_gender(Some("Бондар"), None, Some("Петрівна")),
+            Gender::Female
+        );
+        assert_eq!(detect_gender(Some("Іванова"), None, None), Gender::Female);
+        assert_eq!(detect_gender(None, None, None), Gender::Unknown);
+    }
+}
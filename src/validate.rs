@@ -0,0 +1,257 @@
+//! Checks of names before they are stored.
+
+use std::fmt;
+use std::ops::Range;
+
+use super::options::{is_apostrophe, is_cyrillic};
+use super::NamePart;
+
+/// Kind of problem found by `validate_name`
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+#[non_exhaustive]
+pub enum NameIssueKind {
+    /// Name is empty or consists of whitespace only
+    Empty,
+    /// Digits, as in "Иванов2"
+    Digit,
+    /// Word mixing Latin and Cyrillic letters, as in "Ивaнов" with a Latin "a"
+    MixedScripts,
+    /// Hyphens, apostrophes or spaces following each other
+    RepeatedSeparator,
+    /// Hyphen, apostrophe or space at the start of the name
+    LeadingSeparator,
+    /// Hyphen, apostrophe or space at the end of the name
+    TrailingSeparator,
+    /// Name has no capital letters
+    Lowercase,
+    /// Name is a single letter or longer than names of the part usually are
+    SuspiciousLength,
+    /// Character that doesn't belong in a name, like "_" or a Greek letter
+    UnexpectedCharacter,
+}
+
+impl NameIssueKind {
+    fn description(self) -> &'static str {
+        match self {
+            NameIssueKind::Empty => "name is empty",
+            NameIssueKind::Digit => "digits",
+            NameIssueKind::MixedScripts => "Latin and Cyrillic letters in one word",
+            NameIssueKind::RepeatedSeparator => "repeated separators",
+            NameIssueKind::LeadingSeparator => "leading separator",
+            NameIssueKind::TrailingSeparator => "trailing separator",
+            NameIssueKind::Lowercase => "no capital letters",
+            NameIssueKind::SuspiciousLength => "suspicious length",
+            NameIssueKind::UnexpectedCharacter => "unexpected characters",
+        }
+    }
+}
+
+/// Problem found by `validate_name`, with the byte range of the offending characters
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct NameIssue {
+    /// What is wrong
+    pub kind: NameIssueKind,
+    /// Byte range of the characters to highlight
+    pub range: Range<usize>,
+}
+
+impl fmt::Display for NameIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at bytes {}..{}",
+            self.kind.description(),
+            self.range.start,
+            self.range.end
+        )
+    }
+}
+
+// Longest name of the part, in characters, that doesn't look suspicious
+fn max_length(part: NamePart) -> usize {
+    match part {
+        NamePart::Last => 50,
+        NamePart::First | NamePart::Middle => 30,
+    }
+}
+
+fn is_separator(c: char) -> bool {
+    c == '-' || c.is_whitespace() || is_apostrophe(c)
+}
+
+fn is_latin(c: char) -> bool {
+    c.is_ascii_alphabetic() || matches!(c, '\u{c0}'..='\u{24f}')
+}
+
+// Adds the issue, extending the previous one when they are of the same kind and adjacent
+fn push(issues: &mut Vec<NameIssue>, kind: NameIssueKind, range: Range<usize>) {
+    match issues.last_mut() {
+        Some(last) if last.kind == kind && last.range.end == range.start => {
+            last.range.end = range.end
+        }
+        _ => issues.push(NameIssue { kind, range }),
+    }
+}
+
+/// Checks a name before it is stored, returning every problem found
+///
+/// Latin names pass, but a word mixing Latin and Cyrillic letters doesn't. Names are
+/// split into words on spaces and hyphens, as when they are inflected.
+///
+/// ```
+/// use petrovich::{validate_name, NameIssueKind, NamePart};
+///
+/// assert_eq!(validate_name(NamePart::Last, "Д’Артаньян"), Ok(()));
+/// let issues = validate_name(NamePart::Last, "Иванов--Петров2").unwrap_err();
+/// assert_eq!(issues[0].kind, NameIssueKind::RepeatedSeparator);
+/// assert_eq!(issues[0].range, 12..14);
+/// assert_eq!(issues[1].kind, NameIssueKind::Digit);
+/// ```
+pub fn validate_name(part: NamePart, name: &str) -> Result<(), Vec<NameIssue>> {
+    if name.trim().is_empty() {
+        return Err(vec![NameIssue {
+            kind: NameIssueKind::Empty,
+            range: 0..name.len(),
+        }]);
+    }
+    let mut issues = Vec::new();
+    let mut chars = name.char_indices().peekable();
+    let mut previous = None;
+    while let Some((i, c)) = chars.next() {
+        let range = i..i + c.len_utf8();
+        let next = chars.peek().map(|&(_, next)| next);
+        if is_separator(c) {
+            if i == 0 {
+                push(&mut issues, NameIssueKind::LeadingSeparator, range);
+            } else if next.is_none() {
+                push(&mut issues, NameIssueKind::TrailingSeparator, range);
+            } else if next.is_some_and(is_separator) || previous.is_some_and(is_separator) {
+                push(&mut issues, NameIssueKind::RepeatedSeparator, range);
+            }
+        } else if c.is_numeric() {
+            push(&mut issues, NameIssueKind::Digit, range);
+        } else if !(c.is_alphabetic() && (is_cyrillic(c) || is_latin(c))) {
+            push(&mut issues, NameIssueKind::UnexpectedCharacter, range);
+        }
+        previous = Some(c);
+    }
+    // Words are located by their offsets in the name
+    for word in name.split(|c: char| c == '-' || c.is_whitespace()) {
+        if word.chars().any(is_cyrillic) && word.chars().any(is_latin) {
+            let start = word.as_ptr() as usize - name.as_ptr() as usize;
+            push(
+                &mut issues,
+                NameIssueKind::MixedScripts,
+                start..start + word.len(),
+            );
+        }
+    }
+    let whole = 0..name.len();
+    if name.chars().any(char::is_alphabetic) && !name.chars().any(char::is_uppercase) {
+        push(&mut issues, NameIssueKind::Lowercase, whole.clone());
+    }
+    let length = name.chars().filter(|&c| !is_separator(c)).count();
+    if length == 1 || length > max_length(part) {
+        push(&mut issues, NameIssueKind::SuspiciousLength, whole);
+    }
+    issues.sort_by_key(|issue| issue.range.start);
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(issues)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(part: NamePart, name: &str) -> Vec<NameIssueKind> {
+        validate_name(part, name)
+            .err()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|issue| issue.kind)
+            .collect()
+    }
+
+    #[test]
+    fn should_accept_names() {
+        for &name in &[
+            "Иванов",
+            "Бонч-Бруевич",
+            "О'Нил",
+            "ван дер Берг",
+            "Smith",
+            "Мюллер-Smith",
+            "Пётр",
+        ] {
+            assert_eq!(validate_name(NamePart::Last, name), Ok(()), "{}", name);
+        }
+        assert_eq!(validate_name(NamePart::Middle, "Ибрагим оглы"), Ok(()));
+    }
+
+    #[test]
+    fn should_report_issues_with_ranges() {
+        assert_eq!(
+            validate_name(NamePart::First, "  "),
+            Err(vec![NameIssue {
+                kind: NameIssueKind::Empty,
+                range: 0..2
+            }])
+        );
+        assert_eq!(
+            validate_name(NamePart::First, "-Анна12"),
+            Err(vec![
+                NameIssue {
+                    kind: NameIssueKind::LeadingSeparator,
+                    range: 0..1
+                },
+                NameIssue {
+                    kind: NameIssueKind::Digit,
+                    range: 9..11
+                },
+            ])
+        );
+        // The Latin "a" in the middle
+        let issues = validate_name(NamePart::Last, "Ивaнов").unwrap_err();
+        assert_eq!(issues[0].kind, NameIssueKind::MixedScripts);
+        assert_eq!(issues[0].range, 0.."Ивaнов".len());
+        assert_eq!(
+            issues[0].to_string(),
+            "Latin and Cyrillic letters in one word at bytes 0..11"
+        );
+    }
+
+    #[test]
+    fn should_report_every_kind() {
+        assert_eq!(
+            kinds(NamePart::Last, "Иванов- "),
+            [
+                NameIssueKind::RepeatedSeparator,
+                NameIssueKind::TrailingSeparator
+            ]
+        );
+        assert_eq!(
+            kinds(NamePart::Last, "Иванов'"),
+            [NameIssueKind::TrailingSeparator]
+        );
+        assert_eq!(kinds(NamePart::First, "анна"), [NameIssueKind::Lowercase]);
+        assert_eq!(
+            kinds(NamePart::First, "Я"),
+            [NameIssueKind::SuspiciousLength]
+        );
+        assert_eq!(
+            kinds(NamePart::First, &"Анна".repeat(10)),
+            [NameIssueKind::SuspiciousLength]
+        );
+        assert_eq!(
+            kinds(NamePart::Last, "Иван_ов"),
+            [NameIssueKind::UnexpectedCharacter]
+        );
+        assert_eq!(
+            kinds(NamePart::Last, "Σωκράτης"),
+            [NameIssueKind::UnexpectedCharacter]
+        );
+    }
+}
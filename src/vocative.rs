@@ -0,0 +1,106 @@
+//! Colloquial vocative of first names: "Саш", "Петь".
+
+use super::rules::{NoCustomTags, RULES};
+use super::{match_name, Case, Gender, InflectOptions};
+
+fn is_vowel(c: char) -> bool {
+    "аеёиоуыэюяАЕЁИОУЫЭЮЯ".contains(c)
+}
+
+fn is_consonant(c: char) -> bool {
+    c.is_alphabetic() && !is_vowel(c) && !"ьъйЬЪЙ".contains(c)
+}
+
+// Truncated form of a single name, if the truncation sounds natural
+fn truncate(name: &str) -> Option<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let (last, consonant, before) = match chars.as_slice() {
+        [.., before, consonant, last] => (*last, *consonant, *before),
+        _ => return None,
+    };
+    // Consonant clusters and soft signs don't truncate naturally: Анна, Ольга, Илья
+    if !is_vowel(before) || !is_consonant(consonant) {
+        return None;
+    }
+    let stem: String = chars[..chars.len() - 1].iter().collect();
+    match last {
+        'а' | 'А' => Some(stem),
+        'я' => Some(stem + "ь"),
+        'Я' => Some(stem + "Ь"),
+        _ => None,
+    }
+}
+
+/// Colloquial vocative of a first name: "Саша" → "Саш", "Петя" → "Петь"
+///
+/// Only names ending in -а/-я that decline for the given gender are truncated, and only
+/// when the ending follows a single consonant, so "Анна", "Ольга" and "Илья" stay
+/// unchanged. Names ending in a consonant, like "Игорь" or "Димок", are returned as is.
+/// In hyphenated and multi-word names like "Анна Маша" only the last part is truncated.
+pub fn firstname_vocative(gender: Gender, name: &str) -> String {
+    let (head, last) = match name
+        .char_indices()
+        .rfind(|&(_, c)| c == '-' || c.is_whitespace())
+    {
+        Some((i, c)) => name.split_at(i + c.len_utf8()),
+        None => ("", name),
+    };
+    let declines = match_name(
+        gender,
+        last,
+        &RULES.firstname,
+        &NoCustomTags,
+        &InflectOptions::DEFAULT,
+    )
+    .map(|segments| {
+        segments.iter().any(|segment| {
+            segment
+                .rule
+                .is_some_and(|(_, rule)| rule.modifier(Case::Genitive).is_some())
+        })
+    })
+    .unwrap_or(false);
+    match truncate(last) {
+        Some(truncated) if declines => [head, &truncated].concat(),
+        _ => name.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "firstname-rules")]
+    #[test]
+    fn should_truncate_names() {
+        assert_eq!(firstname_vocative(Gender::Male, "Саша"), "Саш");
+        assert_eq!(firstname_vocative(Gender::Male, "Петя"), "Петь");
+        assert_eq!(firstname_vocative(Gender::Female, "Наташа"), "Наташ");
+        assert_eq!(firstname_vocative(Gender::Female, "Таня"), "Тань");
+        assert_eq!(firstname_vocative(Gender::Male, "Дима"), "Дим");
+    }
+
+    #[cfg(feature = "firstname-rules")]
+    #[test]
+    fn should_keep_names_that_dont_truncate() {
+        assert_eq!(firstname_vocative(Gender::Male, "Игорь"), "Игорь");
+        assert_eq!(firstname_vocative(Gender::Male, "Олег"), "Олег");
+        assert_eq!(firstname_vocative(Gender::Male, "Димок"), "Димок");
+        assert_eq!(firstname_vocative(Gender::Female, "Анна"), "Анна");
+        assert_eq!(firstname_vocative(Gender::Female, "Ольга"), "Ольга");
+        assert_eq!(firstname_vocative(Gender::Male, "Илья"), "Илья");
+        assert_eq!(firstname_vocative(Gender::Female, "Мария"), "Мария");
+    }
+
+    #[cfg(feature = "firstname-rules")]
+    #[test]
+    fn should_truncate_last_part_only() {
+        assert_eq!(firstname_vocative(Gender::Female, "Анна-Маша"), "Анна-Маш");
+        assert_eq!(firstname_vocative(Gender::Female, "Маша-Анна"), "Маша-Анна");
+        assert_eq!(firstname_vocative(Gender::Female, "Анна Маша"), "Анна Маш");
+        assert_eq!(
+            firstname_vocative(Gender::Female, "Маша\u{a0}Анна"),
+            "Маша\u{a0}Анна"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! JavaScript bindings built with `wasm-bindgen`.
+//!
+//! Genders, cases and name parts are passed as strings in any form their `FromStr`
+//! implementations accept, like "male", "ж" or "dative". Invalid ones throw a JavaScript
+//! `Error` with a readable message.
+
+use wasm_bindgen::prelude::*;
+
+#[cfg(feature = "gender-detection")]
+use super::detect_gender;
+use super::{inflect_many, Case, Gender, NamePart};
+
+// Inflects a name with the gender and the case given as strings
+#[cfg(any(
+    feature = "firstname-rules",
+    feature = "lastname-rules",
+    feature = "middlename-rules"
+))]
+fn inflect_str(
+    part: NamePart,
+    gender: &str,
+    name: &str,
+    case: &str,
+) -> Result<String, super::Error> {
+    Ok(super::inflect(part, gender.parse()?, name, case.parse()?))
+}
+
+/// Inflects first name: `firstname_js("male", "Лёша", "dative")` gives "Лёше"
+#[cfg(feature = "firstname-rules")]
+#[wasm_bindgen]
+pub fn firstname_js(gender: &str, name: &str, case: &str) -> Result<String, JsError> {
+    Ok(inflect_str(NamePart::First, gender, name, case)?)
+}
+
+/// Inflects last name, see `firstname_js`
+#[cfg(feature = "lastname-rules")]
+#[wasm_bindgen]
+pub fn lastname_js(gender: &str, name: &str, case: &str) -> Result<String, JsError> {
+    Ok(inflect_str(NamePart::Last, gender, name, case)?)
+}
+
+/// Inflects middle name, see `firstname_js`
+#[cfg(feature = "middlename-rules")]
+#[wasm_bindgen]
+pub fn middlename_js(gender: &str, name: &str, case: &str) -> Result<String, JsError> {
+    Ok(inflect_str(NamePart::Middle, gender, name, case)?)
+}
+
+/// Detects gender from the parts given, as its English identifier like "female"
+///
+/// Missing parts are passed as `undefined` or `null`.
+#[cfg(feature = "gender-detection")]
+#[wasm_bindgen]
+pub fn detect_gender_js(
+    lastname: Option<String>,
+    firstname: Option<String>,
+    middlename: Option<String>,
+) -> String {
+    let gender = detect_gender(
+        lastname.as_deref(),
+        firstname.as_deref(),
+        middlename.as_deref(),
+    );
+    gender.name().to_owned()
+}
+
+/// Inflects an array of names of the same part and gender in one call, see
+/// `inflect_many`
+///
+/// Throws when an element of `names` isn't a string.
+#[wasm_bindgen]
+pub fn inflect_many_js(
+    part: &str,
+    gender: &str,
+    names: Vec<JsValue>,
+    case: &str,
+) -> Result<Vec<JsValue>, JsError> {
+    let part: NamePart = part.parse()?;
+    let gender: Gender = gender.parse()?;
+    let case: Case = case.parse()?;
+    let names = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            name.as_string()
+                .ok_or_else(|| JsError::new(&format!("names[{}] is not a string", i)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let inflected = inflect_many(part, case, names.iter().map(|name| (gender, name.as_str())));
+    Ok(inflected.into_iter().map(JsValue::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_gender_and_case_strings() {
+        assert_eq!(
+            inflect_str(NamePart::First, "м", "Лёша", "дат").unwrap(),
+            "Лёше"
+        );
+        assert_eq!(
+            inflect_str(NamePart::Last, "female", "Иванова", "Genitive").unwrap(),
+            "Ивановой"
+        );
+    }
+
+    #[test]
+    fn should_report_bad_gender_and_case_strings() {
+        let error = inflect_str(NamePart::First, "неясно", "Лёша", "dative").unwrap_err();
+        assert_eq!(error.to_string(), "unknown gender \"неясно\"");
+        let error = inflect_str(NamePart::First, "male", "Лёша", "звательный").unwrap_err();
+        assert_eq!(error.to_string(), "unknown grammatical case \"звательный\"");
+    }
+}
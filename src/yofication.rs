@@ -0,0 +1,143 @@
+//! Restoring ё in names typed with е, like "Семен" or "Королев".
+
+use std::borrow::Cow;
+
+use super::rules::fold_yo_char;
+use super::NamePart;
+
+// Lowercase names with ё folded to е, their spellings with ё, the parts they are names of
+// and whether the name is also spelled with е, sorted by the name
+#[cfg(feature = "generate-rules")]
+const YOFICATION: &[(&str, &str, NamePart, bool)] =
+    include!(concat!(env!("OUT_DIR"), "/yofication.inc"));
+#[cfg(not(feature = "generate-rules"))]
+const YOFICATION: &[(&str, &str, NamePart, bool)] = include!("generated/yofication.inc");
+
+// Dictionary entry for the word as the given part, if any
+fn lookup(part: NamePart, word: &str) -> Option<(&'static str, bool)> {
+    let key: String = word.to_lowercase().chars().map(fold_yo_char).collect();
+    let start = YOFICATION.partition_point(|&(name, _, _, _)| name < key.as_str());
+    YOFICATION[start..]
+        .iter()
+        .take_while(|&&(name, _, _, _)| name == key)
+        .find(|&&(_, _, entry_part, _)| entry_part == part)
+        .map(|&(_, spelling, _, ambiguous)| (spelling, ambiguous))
+}
+
+// Words of the name, as runs of letters with their byte offsets
+fn words(name: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in name
+        .char_indices()
+        .chain(std::iter::once((name.len(), ' ')))
+    {
+        match (start, c.is_alphabetic()) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                words.push((s, &name[s..i]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+/// Restores ё in the name from the dictionary of names spelled with it
+///
+/// Each word of a hyphenated or multi-word name is looked up separately and keeps its
+/// capitals. Surnames spelled both ways, like "Чебышев", and names missing from the
+/// dictionary are returned as is; see `is_yo_ambiguous`.
+///
+/// ```
+/// use petrovich::{yoficate, NamePart};
+///
+/// assert_eq!(yoficate(NamePart::First, "Семен"), "Семён");
+/// assert_eq!(yoficate(NamePart::Last, "КОРОЛЕВА-Соловьева"), "КОРОЛЁВА-Соловьёва");
+/// assert_eq!(yoficate(NamePart::Last, "Чебышев"), "Чебышев");
+/// assert_eq!(yoficate(NamePart::Last, "Семен"), "Семен");
+/// ```
+pub fn yoficate(part: NamePart, name: &str) -> Cow<'_, str> {
+    let mut output: Option<String> = None;
+    for (start, word) in words(name) {
+        let spelling = match lookup(part, word) {
+            Some((spelling, false)) => spelling,
+            _ => continue,
+        };
+        let fixed: String = word
+            .chars()
+            .zip(spelling.chars())
+            .map(|(c, expected)| match (c, expected) {
+                ('е', 'ё') => 'ё',
+                ('Е', 'ё') => 'Ё',
+                _ => c,
+            })
+            .collect();
+        if fixed != word {
+            output
+                .get_or_insert_with(|| name.to_owned())
+                .replace_range(start..start + word.len(), &fixed);
+        }
+    }
+    match output {
+        Some(output) => Cow::Owned(output),
+        None => Cow::Borrowed(name),
+    }
+}
+
+/// Whether the name has a word spelled both with е and with ё, like "Чебышев"
+///
+/// `yoficate` leaves such words alone, so the spelling has to come from elsewhere.
+///
+/// ```
+/// use petrovich::{is_yo_ambiguous, NamePart};
+///
+/// assert!(is_yo_ambiguous(NamePart::Last, "Чебышев"));
+/// assert!(!is_yo_ambiguous(NamePart::Last, "Королев"));
+/// ```
+pub fn is_yo_ambiguous(part: NamePart, name: &str) -> bool {
+    words(name)
+        .into_iter()
+        .any(|(_, word)| matches!(lookup(part, word), Some((_, true))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_sorted() {
+        assert!(YOFICATION
+            .windows(2)
+            .all(|pair| (pair[0].0, pair[0].2 as u8) < (pair[1].0, pair[1].2 as u8)));
+    }
+
+    #[test]
+    fn should_restore_yo() {
+        assert_eq!(yoficate(NamePart::First, "Артем"), "Артём");
+        assert_eq!(yoficate(NamePart::First, "ФЕДОР"), "ФЁДОР");
+        assert_eq!(yoficate(NamePart::Middle, "Семеновна"), "Семёновна");
+        assert_eq!(yoficate(NamePart::Last, "Королева"), "Королёва");
+        assert_eq!(
+            yoficate(NamePart::Last, "Петров-Водкин Журавлев"),
+            "Петров-Водкин Журавлёв"
+        );
+    }
+
+    #[test]
+    fn should_keep_unknown_and_ambiguous_names() {
+        assert!(matches!(
+            yoficate(NamePart::First, "Иван"),
+            Cow::Borrowed("Иван")
+        ));
+        assert!(matches!(
+            yoficate(NamePart::First, "Семён"),
+            Cow::Borrowed("Семён")
+        ));
+        assert_eq!(yoficate(NamePart::Last, "Чернышева"), "Чернышева");
+        assert_eq!(yoficate(NamePart::Last, "Федор"), "Федор");
+        assert!(is_yo_ambiguous(NamePart::Last, "Иванова-Чернышева"));
+        assert!(!is_yo_ambiguous(NamePart::First, "Чебышев"));
+    }
+}
@@ -0,0 +1,85 @@
+//! Runs the command-line tool on small inputs.
+
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn run(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_petrovich"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn should_append_inflected_columns() {
+    let output = run(
+        &["inflect", "--case", "dative", "--detect-gender"],
+        "Иванов\tИван\tИванович\tmale\nПетрова\tАнна\t\n",
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Иванов\tИван\tИванович\tmale\tИванову\tИвану\tИвановичу\n\
+         Петрова\tАнна\t\tfemale\tПетровой\tАнне\t\n"
+    );
+
+    let output = run(
+        &["inflect", "--csv", "--parts", "first", "--case", "тв"],
+        "Петрова,Анна,Сергеевна,ж\n",
+    );
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Петрова,Анна,Сергеевна,female,Анной\n"
+    );
+}
+
+#[test]
+fn should_detect_gender_and_parse() {
+    let output = run(&["gender"], "\tАнна\t\n");
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "\tАнна\t\tfemale\n"
+    );
+
+    let output = run(&["parse", "--csv"], "Иван Иванович Петров\n");
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Петров,Иван,Иванович,male\n"
+    );
+}
+
+#[test]
+fn should_report_malformed_row() {
+    let output = run(&["inflect"], "Иванов\tИван\tИванович\tmale\nИванов\n");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("petrovich: row 2:"));
+    // The rows before the malformed one are written
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Иванов\tИван\tИванович\tmale\tИванова\tИвана\tИвановича\n"
+    );
+
+    let output = run(&["gender"], "Иванов\tИван\t\nИванов\n");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Иванов\tИван\t\tmale\n"
+    );
+
+    let output = run(&["inflect"], "Иванов\tИван\tИванович\n");
+    assert_eq!(output.status.code(), Some(1));
+}
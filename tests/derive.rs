@@ -0,0 +1,9 @@
+//! Checks that misuse of `#[derive(Inflectable)]` is reported at compile time.
+
+#![cfg(feature = "derive")]
+
+#[test]
+fn should_reject_misuse() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/derive/*.rs");
+}
@@ -0,0 +1,31 @@
+//! Checks that the C header shipped with the crate matches `src/ffi.rs`.
+
+#![cfg(feature = "ffi")]
+
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn should_match_generated_header() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let config = cbindgen::Config::from_file(root.join("cbindgen.toml")).unwrap();
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(root.join("src/ffi.rs"))
+        .generate()
+        .unwrap()
+        .write(&mut generated);
+    let generated = String::from_utf8(generated).unwrap();
+
+    let path = root.join("include/petrovich.h");
+    if std::env::var_os("PETROVICH_UPDATE_HEADER").is_some() {
+        fs::write(&path, &generated).unwrap();
+    }
+    let shipped = fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        shipped == generated,
+        "include/petrovich.h is out of date, regenerate it with \
+         PETROVICH_UPDATE_HEADER=1 cargo test --features ffi --test ffi_header"
+    );
+}
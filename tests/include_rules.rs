@@ -0,0 +1,22 @@
+//! Checks that malformed rule files given to `include_rules!` are reported at compile time.
+
+#![cfg(feature = "macros")]
+
+use std::path::Path;
+
+// The cases are built in `tests/trybuild/petrovich` of the target directory, wherever it is,
+// so their rule files are copied to the `tmp` directory next to it
+#[test]
+fn should_reject_malformed_rules() {
+    let fixtures = Path::new(env!("CARGO_TARGET_TMPDIR")).join("include_rules");
+    std::fs::create_dir_all(&fixtures).unwrap();
+    for entry in std::fs::read_dir("tests/ui/include_rules").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension() == Some("yml".as_ref()) {
+            std::fs::copy(&path, fixtures.join(path.file_name().unwrap())).unwrap();
+        }
+    }
+
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/include_rules/*.rs");
+}
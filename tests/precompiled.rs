@@ -0,0 +1,16 @@
+//! Checks that the code in `src/generated`, used unless the `generate-rules` feature is on, is
+//! up to date with the data it is generated from.
+
+use std::path::Path;
+
+#[test]
+fn should_ship_up_to_date_precompiled_code() {
+    for (name, code) in petrovich_build::precompiled(Path::new("src/rules.yml")).unwrap() {
+        let path = Path::new("src/generated").join(name);
+        assert!(
+            std::fs::read(&path).unwrap() == code,
+            "{} is outdated, run `cargo run -p petrovich-build --bin regenerate`",
+            path.display()
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Checks that the build script generates the same code from `rules.yml` and the upstream
+//! `rules.json` layout.
+
+use petrovich_build::rules;
+
+use std::path::Path;
+
+// Generated rule lists followed by the tables they refer to
+fn generate_code(rules: &rules::Rules) -> String {
+    let (mut output, mut tables) = (Vec::new(), Vec::new());
+    rules::generate_rules(rules, false, &mut output, &mut tables).unwrap();
+    output.append(&mut tables);
+    String::from_utf8(output).unwrap()
+}
+
+fn generate(path: &str) -> String {
+    generate_code(&rules::read_rules(Path::new(path)).unwrap())
+}
+
+#[test]
+fn should_generate_same_code_from_yaml_and_json() {
+    assert_eq!(
+        generate("src/rules.yml"),
+        generate("tests/fixtures/rules.json")
+    );
+}
+
+#[test]
+fn should_report_json_errors_with_line() {
+    let path = std::env::temp_dir().join("petrovich-malformed-rules.json");
+    std::fs::write(
+        &path,
+        "{\n  \"lastname\": {\n    \"exceptions\": \"none\"\n  }\n}\n",
+    )
+    .unwrap();
+    let error = match rules::read_rules(&path) {
+        Ok(_) => panic!("malformed rules were accepted"),
+        Err(error) => error.to_string(),
+    };
+    assert!(
+        error.starts_with(&format!("{}:3: ", path.display())),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn should_generate_empty_lists_for_left_out_parts() {
+    let mut rules = rules::read_rules(Path::new("src/rules.yml")).unwrap();
+    rules.retain(true, false, false);
+    let code = generate_code(&rules);
+    assert!(code.contains("бонч"));
+    assert!(!code.contains("оглы"));
+    assert!(code
+        .contains("    firstname: RuleList {\n        exceptions: Cow::Borrowed(&[\n        ]),"));
+}
+
+fn read_error(name: &str, yaml: &str) -> String {
+    let path = std::env::temp_dir().join(name);
+    std::fs::write(&path, yaml).unwrap();
+    let error = match rules::read_rules(&path) {
+        Ok(_) => panic!("invalid rules were accepted:\n{}", yaml),
+        Err(error) => error.to_string(),
+    };
+    let prefix = format!("{}: ", path.display());
+    assert!(error.starts_with(&prefix), "{}", error);
+    error[prefix.len()..].to_owned()
+}
+
+#[test]
+fn should_reject_invalid_rules() {
+    let rule = |test: &str, mods: &str| {
+        format!(
+            "lastname:\n  suffixes:\n    - gender: male\n      test: [{}]\n      mods: [{}]\n",
+            test, mods
+        )
+    };
+    assert_eq!(
+        read_error("petrovich-short-mods.yml", &rule("ов", "а, у, а, ым")),
+        "lastname.suffixes[0]: expected 5 mods, found 4"
+    );
+    assert_eq!(
+        read_error(
+            "petrovich-long-strip.yml",
+            &rule("ок, лок", "--ка, --ку, --ка, ---ком, --ке")
+        ),
+        "lastname.suffixes[0]: modifier \"---ком\" strips 3 letters, more than test \"ок\" has"
+    );
+    assert_eq!(
+        read_error(
+            "petrovich-uppercase-test.yml",
+            &rule("Ов", "а, у, а, ым, е")
+        ),
+        "lastname.suffixes[0]: test \"Ов\" is not lowercase"
+    );
+    assert_eq!(
+        read_error("petrovich-latin-test.yml", &rule("ov", "a, u, a, ym, e")),
+        "lastname.suffixes[0]: test \"ov\" has non-Cyrillic character 'o'"
+    );
+    assert_eq!(
+        read_error(
+            "petrovich-nfd-test.yml",
+            &rule("\"и\u{306}\"", "-я, -ю, -я, -ем, -е")
+        ),
+        "lastname.suffixes[0]: test \"и\\u{306}\" is not in NFC"
+    );
+}
+
+#[test]
+fn should_reject_contradicting_exceptions() {
+    let yaml = "\
+firstname:
+  exceptions:
+    - gender: male
+      test: [лев]
+      mods: [--ьва, --ьву, --ьва, --ьвом, --ьве]
+    - gender: female
+      test: [лев]
+      mods: [., ., ., ., .]
+    - gender: male
+      test: [пётр, лев]
+      mods: [--тра, --тру, --тра, --тром, --тре]
+";
+    assert_eq!(
+        read_error("petrovich-contradicting-exceptions.yml", yaml),
+        "firstname.exceptions[2]: test \"лев\" contradicts firstname.exceptions[0] for Male gender"
+    );
+}
+
+#[test]
+fn should_ship_without_dead_rules() {
+    let rules = rules::read_rules(Path::new("src/rules.yml")).unwrap();
+    assert_eq!(rules.dead_rules(), Vec::<String>::new());
+}
+
+#[test]
+fn should_find_dead_rules() {
+    let rules = rules::read_rules(Path::new("tests/fixtures/dead_rules.yml")).unwrap();
+    assert_eq!(
+        rules.dead_rules(),
+        [
+            "lastname.exceptions[1]: test \"дюма\" is taken by lastname.exceptions[0]",
+            "lastname.exceptions[2]: rule is unreachable, test \"гюго\" is taken by \
+             lastname.exceptions[1]",
+            "lastname.suffixes[3]: rule is unreachable, test \"ек\" is taken by \
+             lastname.suffixes[1], test \"ек\" is listed twice",
+            "firstname.suffixes[3]: rule is unreachable, test \"ша\" is taken by \
+             firstname.suffixes[0] for male, firstname.suffixes[1] for female and \
+             firstname.suffixes[2] for androgynous names",
+            "middlename.suffixes[0]: rule is unreachable, tags first_word and particle \
+             exclude each other",
+        ]
+    );
+}
@@ -0,0 +1,11 @@
+use petrovich::Inflectable;
+
+#[derive(Inflectable)]
+struct Employee {
+    #[petrovich(last)]
+    last: String,
+    #[petrovich(last)]
+    maiden_name: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use petrovich::Inflectable;
+
+#[derive(Inflectable)]
+enum Person {
+    Named(#[petrovich(last)] String),
+    Anonymous,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use petrovich::{Gender, Inflectable};
+
+#[derive(Inflectable)]
+struct Employee {
+    name: String,
+    #[petrovich(gender)]
+    gender: Gender,
+}
+
+fn main() {}
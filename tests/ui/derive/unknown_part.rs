@@ -0,0 +1,9 @@
+use petrovich::Inflectable;
+
+#[derive(Inflectable)]
+struct Employee {
+    #[petrovich(surname)]
+    last: String,
+}
+
+fn main() {}
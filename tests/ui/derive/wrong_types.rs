@@ -0,0 +1,11 @@
+use petrovich::Inflectable;
+
+#[derive(Inflectable)]
+struct Employee {
+    #[petrovich(last)]
+    last: u32,
+    #[petrovich(gender)]
+    gender: String,
+}
+
+fn main() {}
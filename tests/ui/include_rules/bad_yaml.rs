@@ -0,0 +1,5 @@
+use petrovich::{include_rules, Rules};
+
+static OVERRIDES: Rules = include_rules!("../../../tmp/include_rules/bad_yaml.yml");
+
+fn main() {}